@@ -0,0 +1,191 @@
+//! CI annotations
+//!
+//! This module turns drift, lint diagnostics, and data validation failures into
+//! findings located at the model source files, and renders them as GitHub Actions
+//! workflow commands (`::error file=...::message`) or a SARIF 2.1.0 log, so CI shows
+//! problems inline on pull requests.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::db::validation::DataViolation;
+use crate::diagnostics::{Diagnostics, Severity};
+use crate::models::registry::ModelInfo;
+
+/// How serious a finding is, in the terms GitHub and SARIF use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+}
+
+/// A problem to report, located at the model that declares the table when known
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub level: Level,
+    /// "drift", "data_violation", or a diagnostic code such as "reserved_keyword"
+    pub code: String,
+    pub message: String,
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+}
+
+/// Maps table names to the source location of the model that declares them
+pub struct ModelLocations {
+    locations: HashMap<String, (PathBuf, Option<usize>)>,
+}
+
+impl ModelLocations {
+    /// Locate each model's `struct` line in its source file
+    pub fn new<'a>(models: impl IntoIterator<Item = &'a ModelInfo>) -> Self {
+        let locations = models
+            .into_iter()
+            .map(|model| {
+                let line = struct_line(&model.file_path, &model.name);
+                (model.table_name.clone(), (model.file_path.clone(), line))
+            })
+            .collect();
+        
+        Self { locations }
+    }
+    
+    /// Build a finding about a table
+    pub fn finding(&self, level: Level, code: &str, message: String, table: Option<&str>) -> Finding {
+        let location = table.and_then(|table| self.locations.get(table));
+        Finding {
+            level,
+            code: code.to_string(),
+            message,
+            file: location.map(|(file, _)| file.clone()),
+            line: location.and_then(|(_, line)| *line),
+        }
+    }
+    
+    /// Findings for every difference between the database and the models
+    pub fn drift(&self, differences: &[(String, String)]) -> Vec<Finding> {
+        differences
+            .iter()
+            .map(|(table, message)| self.finding(Level::Error, "drift", message.clone(), Some(table)))
+            .collect()
+    }
+    
+    /// Findings for lint diagnostics; warnings stay warnings and the rest become notes
+    pub fn diagnostics(&self, diagnostics: &Diagnostics) -> Vec<Finding> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let level = match diagnostic.severity {
+                    Severity::Warning => Level::Warning,
+                    Severity::Info => Level::Note,
+                };
+                self.finding(level, &diagnostic.code, diagnostic.message.clone(), diagnostic.table.as_deref())
+            })
+            .collect()
+    }
+    
+    /// Findings for existing rows that would violate new constraints
+    pub fn violations(&self, violations: &[DataViolation]) -> Vec<Finding> {
+        violations
+            .iter()
+            .map(|violation| {
+                self.finding(Level::Error, "data_violation", violation.to_string(), Some(&violation.table))
+            })
+            .collect()
+    }
+}
+
+/// 1-based line of `struct <name>` in a file
+fn struct_line(path: &Path, name: &str) -> Option<usize> {
+    let source = std::fs::read_to_string(path).ok()?;
+    let needle = format!("struct {}", name);
+    
+    source
+        .lines()
+        .position(|line| {
+            line.find(&needle).is_some_and(|at| {
+                let rest = &line[at + needle.len()..];
+                !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+            })
+        })
+        .map(|index| index + 1)
+}
+
+/// Render findings as GitHub Actions workflow commands, one per line
+pub fn github_annotations(findings: &[Finding]) -> String {
+    findings
+        .iter()
+        .map(|finding| {
+            let command = match finding.level {
+                Level::Error => "error",
+                Level::Warning => "warning",
+                Level::Note => "notice",
+            };
+            
+            let mut properties = Vec::new();
+            if let Some(file) = &finding.file {
+                properties.push(format!("file={}", escape_property(&file.to_string_lossy())));
+            }
+            if let Some(line) = finding.line {
+                properties.push(format!("line={}", line));
+            }
+            properties.push(format!("title={}", escape_property(&finding.code)));
+            
+            format!("::{} {}::{}\n", command, properties.join(","), escape_data(&finding.message))
+        })
+        .collect()
+}
+
+/// Escape a workflow command message
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Render findings as a SARIF 2.1.0 log
+pub fn sarif(findings: &[Finding]) -> Value {
+    let mut rule_ids: Vec<&str> = findings.iter().map(|finding| finding.code.as_str()).collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+    
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            let mut result = json!({
+                "ruleId": finding.code,
+                "level": finding.level,
+                "message": { "text": finding.message },
+            });
+            if let Some(file) = &finding.file {
+                let mut location = json!({ "artifactLocation": { "uri": file.to_string_lossy().replace('\\', "/") } });
+                if let Some(line) = finding.line {
+                    location["region"] = json!({ "startLine": line });
+                }
+                result["locations"] = json!([{ "physicalLocation": location }]);
+            }
+            result
+        })
+        .collect();
+    
+    json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "schema_sync",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rule_ids.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                }
+            },
+            "results": results,
+        }]
+    })
+}
@@ -0,0 +1,90 @@
+//! Build script integration
+//!
+//! This module lets a crate's `build.rs` check, without a database, that the committed
+//! migrations still describe the models: the models are scanned, the migrations directory
+//! is replayed offline, and the two schemas are diffed. Drift is reported as cargo
+//! warnings, or fails the build, so a model change cannot ship without its migration.
+//!
+//! ```no_run
+//! // in build.rs's main
+//! schema_sync::build::check_migrations("schema_sync.toml", "error").unwrap();
+//! ```
+
+use std::path::Path;
+
+use crate::config::{self, Config};
+use crate::error::{Error, Result, SchemaMismatch};
+use crate::models::registry::ModelRegistry;
+use crate::schema::diff::SchemaDiff;
+use crate::schema::snapshot;
+use crate::schema::types::DatabaseSchema;
+
+/// Check from a build script that the migrations directory covers the models
+///
+/// `on_drift` is "warn" (print a cargo warning per difference) or "error" (also fail with
+/// `Error::SchemaMismatch`). Cargo is told to rerun the check when the config file, the
+/// model paths, or the migrations directory change. When `OUT_DIR` is set, the SQL that
+/// would close the gap is written to `$OUT_DIR/schema_sync_pending.sql`.
+pub fn check_migrations(config_path: &str, on_drift: &str) -> Result<()> {
+    if !matches!(on_drift, "warn" | "error") {
+        return Err(Error::ConfigError(format!(
+            "unknown drift mode '{}', expected \"warn\" or \"error\"",
+            on_drift
+        )));
+    }
+
+    let config = config::load_from_file(config_path)?;
+    println!("cargo:rerun-if-changed={}", config_path);
+    for path in &config.models.paths {
+        println!("cargo:rerun-if-changed={}", path);
+    }
+    println!("cargo:rerun-if-changed={}", config.migrations.directory);
+
+    let differences = migration_drift(&config)?;
+    if differences.is_empty() {
+        return Ok(());
+    }
+
+    for difference in &differences {
+        println!("cargo:warning=schema_sync: {} (no committed migration)", difference);
+    }
+    if let Ok(out_dir) = std::env::var("OUT_DIR") {
+        let pending = Path::new(&out_dir).join("schema_sync_pending.sql");
+        std::fs::write(&pending, pending_sql(&config)?.join("\n\n"))?;
+        println!("cargo:warning=schema_sync: the missing migration SQL was written to {}", pending.display());
+    }
+
+    match on_drift {
+        "error" => Err(Error::SchemaMismatch(SchemaMismatch { differences })),
+        _ => Ok(()),
+    }
+}
+
+/// Differences between the schema the migrations directory builds and the models
+pub fn migration_drift(config: &Config) -> Result<Vec<String>> {
+    let (migrated, models) = schemas(config)?;
+    Ok(SchemaDiff::generate(migrated, models, &config.schema).differences())
+}
+
+/// Migration SQL that would bring the migrations directory up to date with the models
+pub fn pending_sql(config: &Config) -> Result<Vec<String>> {
+    let (migrated, models) = schemas(config)?;
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    runtime.block_on(snapshot::migration_sql_between(migrated, models, config))
+}
+
+/// The schema replayed from the migrations directory and the one the models describe
+fn schemas(config: &Config) -> Result<(DatabaseSchema, DatabaseSchema)> {
+    let mut registry = ModelRegistry::new(&config.models);
+    registry.scan_and_register(config)?;
+    let models = registry.to_database_schema(config)?;
+
+    let directory = Path::new(&config.migrations.directory);
+    let migrated = if directory.is_dir() {
+        snapshot::schema_from_migrations(directory, &config.database.driver)?
+    } else {
+        DatabaseSchema::new(None)
+    };
+
+    Ok((migrated, models))
+}
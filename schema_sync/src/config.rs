@@ -1,6 +1,7 @@
 //! Configuration handling for SchemaSync
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
@@ -21,6 +22,8 @@ pub fn load_from_file(path: &str) -> Result<Config> {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub database: DatabaseConfig,
+    /// Optional separate connection (e.g. a read replica or low-privilege role) used only for schema analysis
+    pub analysis_database: Option<DatabaseConfig>,
     pub migrations: MigrationsConfig,
     pub models: ModelsConfig,
     pub schema: SchemaConfig,
@@ -31,17 +34,352 @@ pub struct Config {
     pub output: Option<OutputConfig>,
     pub security: Option<SecurityConfig>,
     pub performance: Option<PerformanceConfig>,
+    pub startup: Option<StartupConfig>,
+    /// Further databases synced from the same models, each with its own history (`[[targets]]`)
+    pub targets: Option<Vec<TargetConfig>>,
+}
+
+impl Config {
+    /// The configuration for syncing one target
+    ///
+    /// Everything not set on the target comes from this configuration. The target's
+    /// migration files go to a subdirectory named after it, so targets never share a file.
+    pub fn for_target(&self, target: &TargetConfig) -> Config {
+        let mut config = self.clone();
+        config.targets = None;
+        if let Some(database) = &target.database {
+            config.database = database.clone();
+        }
+        if let Some(history_table) = &target.history_table {
+            config.migrations.history_table = history_table.clone();
+        }
+        if target.history_schema.is_some() {
+            config.migrations.history_schema = target.history_schema.clone();
+        }
+        config.migrations.directory = Path::new(&self.migrations.directory)
+            .join(&target.name)
+            .to_string_lossy()
+            .into_owned();
+        config
+    }
+    
+    /// The configuration of every `[[targets]]` entry, by target name
+    ///
+    /// Fails when two targets share a name, or a database schema and history table, since
+    /// their histories would then overwrite each other. Tenants kept in separate schemas of
+    /// one database are separate targets.
+    pub fn target_configs(&self) -> Result<Vec<(String, Config)>> {
+        let targets = self.targets.as_deref().unwrap_or_default();
+        let mut configs: Vec<(String, Config)> = Vec::with_capacity(targets.len());
+        
+        for target in targets {
+            if target.name.is_empty() {
+                return Err(Error::ConfigError("every target needs a name".to_string()));
+            }
+            let config = self.for_target(target);
+            for (name, other) in &configs {
+                if *name == target.name {
+                    return Err(Error::ConfigError(format!("target '{}' is declared twice", name)));
+                }
+                if other.database.same_database(&config.database)
+                    && other.database.schema == config.database.schema
+                    && other.migrations.history_table_name() == config.migrations.history_table_name()
+                {
+                    return Err(Error::ConfigError(format!(
+                        "targets '{}' and '{}' use the same database schema and history table",
+                        name, target.name
+                    )));
+                }
+            }
+            configs.push((target.name.clone(), config));
+        }
+        
+        Ok(configs)
+    }
+}
+
+/// One database synced from the models alongside the others (`[[targets]]`), e.g. a tenant
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TargetConfig {
+    /// Name the target is reported under and its migration files are kept under
+    pub name: String,
+    /// Connection to the target; the top-level `[database]` when unset
+    pub database: Option<DatabaseConfig>,
+    /// History table for this target; `migrations.history_table` when unset
+    pub history_table: Option<String>,
+    /// Schema holding this target's history table; `migrations.history_schema` when unset
+    pub history_schema: Option<String>,
 }
 
 /// Database connection configuration
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct DatabaseConfig {
     pub driver: String,
-    pub url: String,
+    /// Full connection URL; when omitted it is built from the discrete fields below
+    pub url: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    /// Name of an environment variable holding the password
+    pub password_env: Option<String>,
+    /// Path to a file holding the password (e.g. a mounted secret)
+    pub password_file: Option<String>,
+    pub dbname: Option<String>,
+    /// Extra query parameters appended to the connection URL
+    pub params: Option<BTreeMap<String, String>>,
+    /// Fetch credentials from an external secret store instead of the fields above
+    pub credentials: Option<CredentialsConfig>,
+    /// Unix domain socket to connect through (a directory for Postgres, a socket file for MySQL)
+    pub socket: Option<String>,
+    /// Cloud SQL instance connection name (`project:region:instance`) served by the Cloud SQL Auth Proxy
+    pub cloud_sql_instance: Option<String>,
+    /// Directory where the Cloud SQL Auth Proxy creates its sockets (defaults to `/cloudsql`)
+    pub cloud_sql_socket_dir: Option<String>,
+    /// Authenticate with a generated RDS IAM token instead of a password (RDS / RDS Proxy)
+    pub iam_auth: Option<bool>,
+    /// Open every session read-only (defaults to true for `analysis_database`)
+    pub read_only: Option<bool>,
     pub pool_size: Option<u32>,
     pub timeout_seconds: Option<u64>,
     pub schema: Option<String>,
+    /// Shorthand for `tls.mode = "require"` (true) or `"disable"` (false)
     pub enable_ssl: Option<bool>,
+    /// Detailed TLS settings; take precedence over `enable_ssl`
+    pub tls: Option<TlsConfig>,
+    /// Number of times to retry connecting before giving up
+    pub connect_retries: Option<u32>,
+    /// Initial delay between connection attempts (milliseconds)
+    pub connect_backoff_ms: Option<u64>,
+    /// Upper bound for the delay between connection attempts (milliseconds)
+    pub connect_max_backoff_ms: Option<u64>,
+    /// SQLite pragmas and database header settings
+    pub sqlite: Option<SqliteConfig>,
+    /// Oracle release the generated SQL targets
+    pub oracle: Option<OracleConfig>,
+}
+
+/// TLS settings for Postgres and MySQL connections
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TlsConfig {
+    /// One of "disable", "prefer", "require", "verify-ca", "verify-full"
+    pub mode: Option<String>,
+    /// CA certificate used to verify the server
+    pub root_cert: Option<String>,
+    /// Client certificate for mutual TLS
+    pub client_cert: Option<String>,
+    /// Private key for the client certificate
+    pub client_key: Option<String>,
+    /// Whether the server hostname must match its certificate (upgrades verify-ca to verify-full)
+    pub verify_hostname: Option<bool>,
+}
+
+/// SQLite pragmas applied to every connection, and header fields recorded after migrating
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SqliteConfig {
+    /// One of "delete", "truncate", "persist", "memory", "wal", "off"
+    pub journal_mode: Option<String>,
+    /// Enforce foreign keys (on unless set to false)
+    pub foreign_keys: Option<bool>,
+    /// How long to wait for a locked database before failing (milliseconds)
+    pub busy_timeout_ms: Option<u64>,
+    /// One of "off", "normal", "full", "extra"
+    pub synchronous: Option<String>,
+    /// Further pragmas to set on every connection, by name
+    pub pragmas: Option<BTreeMap<String, String>>,
+    /// Value for `PRAGMA application_id`, marking the file as this application's database
+    pub application_id: Option<i32>,
+    /// Store the integer `migrations.schema_version` in `PRAGMA user_version` after migrating
+    pub record_user_version: Option<bool>,
+}
+
+/// Oracle settings that change the generated SQL
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OracleConfig {
+    /// Release as "major.minor", e.g. "11.2" or "19" (default: 19)
+    ///
+    /// Before 12.1 identity columns are emulated with a sequence and trigger; before 12.2
+    /// identifiers are limited to 30 characters.
+    pub version: Option<String>,
+}
+
+/// External secret store holding database credentials
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CredentialsConfig {
+    /// Provider name: "aws_secrets_manager", "vault", or "gcp_secret_manager"
+    pub provider: String,
+    /// AWS secret name or ARN, Vault secret path, or GCP secret version resource name
+    pub secret_id: String,
+    /// AWS region (defaults to `AWS_REGION` / `AWS_DEFAULT_REGION`)
+    pub region: Option<String>,
+    /// Vault server address (defaults to `VAULT_ADDR`)
+    pub address: Option<String>,
+}
+
+impl DatabaseConfig {
+    /// Get the Unix socket path to connect through, if any
+    ///
+    /// An explicit `socket` wins; otherwise a Cloud SQL instance name resolves to the
+    /// socket the Cloud SQL Auth Proxy creates under `cloud_sql_socket_dir`.
+    pub fn socket_path(&self) -> Option<String> {
+        if let Some(socket) = &self.socket {
+            return Some(socket.clone());
+        }
+        
+        self.cloud_sql_instance.as_ref().map(|instance| {
+            let dir = self.cloud_sql_socket_dir.as_deref().unwrap_or("/cloudsql");
+            format!("{}/{}", dir.trim_end_matches('/'), instance)
+        })
+    }
+    
+    /// Oracle release targeted by `driver = "oracle"`, as (major, minor)
+    pub fn oracle_release(&self) -> Result<(u32, u32)> {
+        let version = match self.oracle.as_ref().and_then(|oracle| oracle.version.as_deref()) {
+            Some(version) => version.trim(),
+            None => return Ok((19, 0)),
+        };
+        
+        let mut parts = version.split('.').map(str::parse::<u32>);
+        match (parts.next(), parts.next()) {
+            (Some(Ok(major)), None) => Ok((major, 0)),
+            (Some(Ok(major)), Some(Ok(minor))) => Ok((major, minor)),
+            _ => Err(Error::ConfigError(format!(
+                "Invalid oracle.version '{}' (expected a release such as \"11.2\" or \"19\")",
+                version
+            ))),
+        }
+    }
+    
+    /// Whether two configurations connect to the same database
+    pub fn same_database(&self, other: &DatabaseConfig) -> bool {
+        self.driver == other.driver
+            && self.url == other.url
+            && self.host == other.host
+            && self.port == other.port
+            && self.dbname == other.dbname
+            && self.socket_path() == other.socket_path()
+    }
+    
+    /// Get the connection URL, building it from the discrete fields if no `url` is set
+    pub fn connection_url(&self) -> Result<String> {
+        if let Some(url) = &self.url {
+            return Ok(url.clone());
+        }
+        
+        let dbname = self.dbname.as_deref().ok_or_else(|| {
+            Error::ConfigError("Database config needs either `url` or `dbname`".to_string())
+        })?;
+        
+        let mut url = if self.driver == "sqlite" {
+            format!("sqlite://{}", dbname)
+        } else {
+            let default_port = match self.driver.as_str() {
+                "postgres" => 5432,
+                "mysql" => 3306,
+                "clickhouse" => 8123,
+                // Oracle is reached through ORDS, not the listener on 1521
+                "oracle" => 8080,
+                _ => {
+                    return Err(Error::ConfigError(format!(
+                        "Cannot build a connection URL for driver: {}", self.driver
+                    )))
+                }
+            };
+            
+            let mut credentials = String::new();
+            if let Some(user) = &self.user {
+                credentials.push_str(&percent_encode(user));
+                if let Some(password) = self.resolve_password()? {
+                    credentials.push(':');
+                    credentials.push_str(&percent_encode(&password));
+                }
+                credentials.push('@');
+            }
+            
+            format!(
+                "{}://{}{}:{}/{}",
+                self.driver,
+                credentials,
+                self.host.as_deref().unwrap_or("localhost"),
+                self.port.unwrap_or(default_port),
+                percent_encode(dbname)
+            )
+        };
+        
+        if let Some(params) = self.params.as_ref().filter(|p| !p.is_empty()) {
+            let query: Vec<String> = params
+                .iter()
+                .map(|(key, value)| format!("{}={}", percent_encode(key), percent_encode(value)))
+                .collect();
+            url.push('?');
+            url.push_str(&query.join("&"));
+        }
+        
+        Ok(url)
+    }
+    
+    /// Resolve the password from the inline value, an environment variable, or a file
+    fn resolve_password(&self) -> Result<Option<String>> {
+        if let Some(password) = &self.password {
+            return Ok(Some(password.clone()));
+        }
+        
+        if let Some(var) = &self.password_env {
+            return std::env::var(var).map(Some).map_err(|_| {
+                Error::ConfigError(format!("Password environment variable not set: {}", var))
+            });
+        }
+        
+        if let Some(path) = &self.password_file {
+            let password = fs::read_to_string(path).map_err(|e| {
+                Error::ConfigError(format!("Failed to read password file {}: {}", path, e))
+            })?;
+            return Ok(Some(password.trim_end_matches(['\r', '\n']).to_string()));
+        }
+        
+        Ok(None)
+    }
+}
+
+/// Percent-encode a URL component
+pub(crate) fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    
+    encoded
+}
+
+/// Decode `%XX` escapes in URL credentials and database names
+#[cfg(any(feature = "clickhouse", feature = "oracle"))]
+pub(crate) fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
 }
 
 /// Migration settings configuration
@@ -55,6 +393,81 @@ pub struct MigrationsConfig {
     pub dry_run: bool,
     pub backup_before_migrate: bool,
     pub history_table: String,
+    /// Schema (Postgres) or database (MySQL) holding the history table; unset uses the
+    /// connection's default. A `history_table` that is already qualified ignores it.
+    pub history_schema: Option<String>,
+    /// Version tag (e.g. "1.4.0") recorded with migrations generated and applied by this build
+    pub schema_version: Option<String>,
+    /// Layout of the generated SQL; unset leaves it as generated
+    pub format: Option<FormatConfig>,
+    /// Parse every generated statement before it is returned or applied (default: false)
+    pub validate_sql: Option<bool>,
+    /// Overrides for how kinds of statement are written, first match wins
+    pub templates: Option<Vec<SqlTemplateConfig>>,
+    /// Apply plans only when they carry a valid approval signature (default: false)
+    pub require_signed_plans: Option<bool>,
+    /// Environment variable holding the plan signing key (default: "SCHEMA_SYNC_PLAN_KEY")
+    pub plan_key_env: Option<String>,
+    /// Re-analyze the tables a diff changed once it is applied, and fail if they still
+    /// differ from the models (default: false)
+    pub verify_after_apply: Option<bool>,
+    /// Record a fingerprint of each table a plan touches, so `apply --plan` can refuse to
+    /// run against tables altered since (default: true)
+    pub plan_fingerprints: Option<bool>,
+}
+
+impl ModelsConfig {
+    /// How deep below `path` models are scanned for; `None` is unlimited
+    pub fn scan_depth(&self, path: &str) -> Option<usize> {
+        self.path_depths
+            .as_ref()
+            .and_then(|depths| depths.get(path).copied())
+            .or(self.max_depth)
+            .or(if self.recursive_scan { None } else { Some(1) })
+    }
+}
+
+impl MigrationsConfig {
+    /// The plan signing key, read from the `plan_key_env` variable
+    pub fn plan_key(&self) -> Result<String> {
+        let variable = self.plan_key_env.as_deref().unwrap_or("SCHEMA_SYNC_PLAN_KEY");
+        match std::env::var(variable) {
+            Ok(key) if !key.is_empty() => Ok(key),
+            _ => Err(Error::ConfigError(format!("the plan signing key variable {} is not set", variable))),
+        }
+    }
+    
+    /// The history table name, qualified with `history_schema` when one is set
+    pub fn history_table_name(&self) -> String {
+        match &self.history_schema {
+            Some(schema) if !self.history_table.contains('.') => format!("{}.{}", schema, self.history_table),
+            _ => self.history_table.clone(),
+        }
+    }
+}
+
+/// A template replacing one kind of generated statement (`[[migrations.templates]]`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SqlTemplateConfig {
+    /// Statement kind, e.g. "create_table", "add_column", "create_index", or "alter_table"
+    /// (any ALTER TABLE); see `schema::templates::STATEMENT_KINDS`
+    pub statement: String,
+    /// Only statements on these tables; all tables when unset
+    pub tables: Option<Vec<String>>,
+    /// Replacement SQL; `{sql}` is the generated statement without its `;`, `{table}` its
+    /// table. A `;` is appended unless the template contains one.
+    pub template: String,
+}
+
+/// SQL formatting applied to generated migrations (`[migrations.format]`)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FormatConfig {
+    /// "upper" (the default), "lower", or "preserve"
+    pub keyword_case: Option<String>,
+    /// Spaces per indentation level (default 2)
+    pub indent: Option<usize>,
+    /// Statements longer than this are broken over several lines (default 100)
+    pub max_line_width: Option<usize>,
 }
 
 /// Model discovery configuration
@@ -63,8 +476,32 @@ pub struct ModelsConfig {
     pub paths: Vec<String>,
     pub exclude_paths: Option<Vec<String>>,
     pub attributes: Vec<String>,
+    /// Scan subdirectories of each path; `false` reads only the files directly in it
     pub recursive_scan: bool,
+    /// How many directory levels below each path are scanned, 1 being the path's own files;
+    /// takes precedence over `recursive_scan`
+    pub max_depth: Option<usize>,
+    /// `max_depth` for individual paths, keyed by the path as written in `paths`
+    pub path_depths: Option<BTreeMap<String, usize>>,
     pub derive_macros: Option<Vec<String>>,
+    /// Fail a scan that visits more files than this (default: 100000)
+    pub max_files: Option<usize>,
+    /// Fail a scan still running after this many seconds (default: 120)
+    pub scan_timeout_seconds: Option<u64>,
+    /// Skip files and directories excluded by `.gitignore` files, and `target/` (default: true)
+    pub respect_gitignore: Option<bool>,
+    /// "source" (the default) scans the files under `paths`; "expand" scans the macro-expanded
+    /// source of `expand_crates`, finding models that other macros generate
+    pub discovery: Option<String>,
+    /// Crate directories expanded in "expand" discovery (default: ["."])
+    pub expand_crates: Option<Vec<String>>,
+    /// Command printing a crate's expanded source, with `{crate}` replaced by the crate directory
+    /// (default: `cargo expand --manifest-path {crate}/Cargo.toml --lib --ugly`)
+    pub expand_command: Option<Vec<String>>,
+    /// Reuse the models parsed from files that haven't changed since the last scan (default: true)
+    pub cache: Option<bool>,
+    /// Directory the parsed models are cached in (default: ".schema_sync/cache")
+    pub cache_dir: Option<String>,
 }
 
 /// Schema generation behavior configuration
@@ -78,6 +515,56 @@ pub struct SchemaConfig {
     pub unique_constraints_as_indices: bool,
     pub add_updated_at_column: bool,
     pub add_created_at_column: bool,
+    /// Check existing data against new NOT NULL, UNIQUE, and foreign key constraints before applying (default: true)
+    pub validate_data: Option<bool>,
+    /// Column combinations the application filters on, checked by the index advisor
+    pub query_patterns: Option<Vec<QueryPattern>>,
+    /// Primary key column added to models that don't declare one
+    pub auto_primary_key: Option<AutoPrimaryKeyConfig>,
+    /// Add nullable created_by/updated_by columns referencing the user who made the change
+    pub add_audit_user_columns: Option<bool>,
+    /// Column type for created_by/updated_by (default: "BIGINT")
+    pub audit_user_type: Option<String>,
+    /// Column the audit columns reference, as "table.column" (default: "users.id")
+    pub audit_user_references: Option<String>,
+    /// Refresh settings for materialized views over tables a migration changes
+    pub materialized_views: Option<Vec<MaterializedViewConfig>>,
+    /// Tablespace for tables whose model doesn't name one (Postgres, MySQL)
+    pub tablespace: Option<String>,
+    /// Tablespace for indexes that don't name one (Postgres)
+    pub index_tablespace: Option<String>,
+}
+
+/// Generated primary key for models without an explicit one
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutoPrimaryKeyConfig {
+    /// Column name (default: "id")
+    #[serde(default = "default_auto_primary_key_name")]
+    pub name: String,
+    /// Key type: "Uuid" (database-generated UUID), "i64" (identity column), or the
+    /// application-generated "Ulid" and "Snowflake" (BIGINT)
+    #[serde(rename = "type")]
+    pub key_type: String,
+}
+
+fn default_auto_primary_key_name() -> String {
+    "id".to_string()
+}
+
+/// How a materialized view is refreshed after migrations change its tables
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MaterializedViewConfig {
+    pub name: String,
+    /// "auto" (CONCURRENTLY when the view has a unique index), "concurrently",
+    /// "blocking", or "never" (default: "auto")
+    pub refresh: Option<String>,
+}
+
+/// A column combination a table is commonly queried by
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueryPattern {
+    pub table: String,
+    pub columns: Vec<String>,
 }
 
 /// Naming conventions configuration
@@ -89,6 +576,68 @@ pub struct NamingConfig {
     pub constraint_pattern: String,
     pub pluralize_tables: bool,
     pub ignore_case_conflicts: bool,
+    /// Pattern for primary key names (default: "pk_{table}")
+    pub primary_key_pattern: Option<String>,
+    /// Pattern for the unique indexes of `unique = true` fields (default: "ix_{table}_{columns}")
+    pub unique_pattern: Option<String>,
+    /// Pattern for the CHECK constraints of validation rules (default: "ck_{table}_{column}")
+    pub check_pattern: Option<String>,
+    /// Explicit names used instead of the conventions, for legacy databases
+    pub overrides: Option<NamingOverrides>,
+    /// Extra pluralization rules for table names
+    pub pluralization: Option<PluralizationConfig>,
+    /// Spell table and column names derived from non-English identifiers in ASCII (default: false)
+    pub transliterate_identifiers: Option<bool>,
+    /// Name columns after serde `rename` and `rename_all` attributes, as the structs serialize (default: false)
+    pub serde_names: Option<bool>,
+}
+
+/// `[naming.pluralization]`: words the built-in pluralization gets wrong
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PluralizationConfig {
+    /// Singular to plural, such as `cactus = "cacti"`; used in both directions
+    #[serde(default)]
+    pub irregular: HashMap<String, String>,
+    /// Words with no separate plural, such as "data" or "metadata"
+    #[serde(default)]
+    pub uncountable: Vec<String>,
+    /// Structs whose table names are never pluralized, like `#[schema_sync(pluralize = false)]`
+    #[serde(default)]
+    pub skip_models: Vec<String>,
+}
+
+/// `[naming.overrides]`: table and column names taken as-is rather than derived
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NamingOverrides {
+    /// Struct name to table name
+    #[serde(default)]
+    pub tables: HashMap<String, String>,
+    /// Field name to column name, keyed by `field` for every model or `Struct.field` for one
+    #[serde(default)]
+    pub columns: HashMap<String, String>,
+}
+
+impl NamingConfig {
+    /// Table name configured for a struct in `[naming.overrides]`
+    pub fn table_override(&self, struct_name: &str) -> Option<&str> {
+        self.overrides.as_ref()?.tables.get(struct_name).map(String::as_str)
+    }
+    
+    /// Column name configured for a field in `[naming.overrides]`; a `Struct.field` key wins over `field`
+    pub fn column_override(&self, struct_name: &str, field_name: &str) -> Option<&str> {
+        let columns = &self.overrides.as_ref()?.columns;
+        columns
+            .get(&format!("{}.{}", struct_name, field_name))
+            .or_else(|| columns.get(field_name))
+            .map(String::as_str)
+    }
+    
+    /// Whether a table or column name was set explicitly and need not follow the conventions
+    pub fn is_overridden(&self, name: &str) -> bool {
+        self.overrides.as_ref().is_some_and(|overrides| {
+            overrides.tables.values().chain(overrides.columns.values()).any(|value| value == name)
+        })
+    }
 }
 
 /// Type mapping configuration
@@ -96,6 +645,24 @@ pub struct NamingConfig {
 pub struct TypeMappingConfig {
     pub custom: Option<Vec<CustomTypeMapping>>,
     pub override_: Option<std::collections::HashMap<String, String>>,
+    /// Storage for `ulid::Ulid`: "text" (CHAR(26), the default) or "binary" (16 bytes)
+    pub ulid_format: Option<String>,
+    /// Type for JSON values on Postgres: "binary" (JSONB, the default) or "text" (JSON)
+    pub json_format: Option<String>,
+    /// Type for timezone-aware timestamps and created_at/updated_at: "timestamptz" (default) or "timestamp"
+    pub timestamp_type: Option<String>,
+    /// Fractional-second digits for timestamp columns (database default when unset; MySQL defaults to 6)
+    pub timestamp_precision: Option<u8>,
+    /// Storage for enums declared with `#[schema_sync]`: "string" (the variant label, the default),
+    /// "int" (the discriminant), or "native" (a Postgres enum type or a MySQL `ENUM` column)
+    pub enum_storage: Option<String>,
+    /// Storage for `Duration` fields: "native" (the default: INTERVAL on Postgres, TIME on MySQL,
+    /// and whole seconds on SQLite) or "seconds" (whole seconds as BIGINT everywhere)
+    pub duration_format: Option<String>,
+    /// Precision of money columns: `Money` fields and decimals with a `currency` (default: 19)
+    pub money_precision: Option<u32>,
+    /// Scale of money columns (default: 4)
+    pub money_scale: Option<u32>,
 }
 
 /// Custom type mapping
@@ -120,8 +687,12 @@ pub struct LoggingConfig {
 pub struct HooksConfig {
     pub before_migration: Option<Vec<String>>,
     pub after_migration: Option<Vec<String>>,
+    /// Webhook URL notified after a schema change is applied (and on failure, unless `on_error` is set)
     pub on_schema_change: Option<String>,
+    /// Webhook URL notified when applying a schema change fails
     pub on_error: Option<String>,
+    /// Payload format for the webhooks: "json" (default) or "slack"
+    pub webhook_format: Option<String>,
 }
 
 /// Output generation configuration
@@ -150,4 +721,29 @@ pub struct PerformanceConfig {
     pub chunk_size: usize,
     pub parallel_migrations: bool,
     pub index_concurrently: bool,
+    /// Maximum time a DDL statement may wait for a lock (milliseconds)
+    pub lock_timeout_ms: Option<u64>,
+    /// Maximum time a single statement may run (milliseconds)
+    pub statement_timeout_ms: Option<u64>,
+    /// Number of times a migration is retried after a lock timeout
+    pub lock_retry_attempts: Option<u32>,
+    /// Initial delay before retrying after a lock timeout, doubled on each attempt (milliseconds)
+    pub lock_retry_backoff_ms: Option<u64>,
+    /// Add constraints to existing tables as NOT VALID and run VALIDATE CONSTRAINT as a separate step (Postgres)
+    pub validate_constraints_separately: Option<bool>,
+    /// Build new tables' non-unique indexes at the end of the plan, after their data is loaded (default: false)
+    pub create_indexes_last: Option<bool>,
+}
+
+/// Behaviour of `integrations::run_on_startup`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct StartupConfig {
+    /// "sync" (apply pending changes under a lock, the default) or "check" (only assert compatibility)
+    pub mode: Option<String>,
+    /// "fail" (return the error, the default) or "degrade" (start anyway and report unhealthy)
+    pub on_failure: Option<String>,
+    /// How long to wait for another instance's sync to finish (seconds, default 60)
+    pub lock_timeout_secs: Option<u64>,
+    /// Age after which a lock is assumed abandoned and taken over (seconds, default 600)
+    pub lock_stale_secs: Option<u64>,
 }
\ No newline at end of file
@@ -2,65 +2,237 @@
 //!
 //! This module provides functionality to establish and manage database connections.
 
+use rand::Rng;
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use std::str::FromStr;
+use std::time::Duration;
+use sqlx::{Connection, Database, Executor};
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use sqlx::Pool;
+#[cfg(feature = "mysql")]
 use sqlx::{
-    mysql::MySqlPoolOptions,
-    postgres::PgPoolOptions,
-    sqlite::SqlitePoolOptions,
-    Any, AnyPool, MySql, MySqlPool, Pool, Postgres, PgPool, Sqlite, SqlitePool,
+    mysql::{MySqlConnectOptions, MySqlPoolOptions, MySqlSslMode},
+    MySql,
+};
+#[cfg(feature = "postgres")]
+use sqlx::{
+    postgres::{PgConnectOptions, PgPoolOptions, PgSslMode},
+    Postgres,
+};
+#[cfg(feature = "sqlite")]
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous},
+    Sqlite,
 };
 
 use crate::config::DatabaseConfig;
 use crate::error::{Error, Result};
 
+/// A per-session setting applied around one migration, such as a lock timeout
+///
+/// Pooled connections outlive the migration, so the setting is undone afterwards: scoped to
+/// the migration's transaction with `local` where the database supports that, else by `reset`.
+#[derive(Debug, Clone)]
+pub struct SessionSetting {
+    /// Statement applying the setting to the session, e.g. `SET lock_timeout = '5000ms'`
+    pub set: String,
+    /// Statement applying it to the current transaction only, e.g. Postgres `SET LOCAL`
+    pub local: Option<String>,
+    /// Statement restoring the connection's previous value
+    pub reset: String,
+}
+
 /// Enumeration of supported database types
+///
+/// There is deliberately no `sqlx::Any` variant: schema analysis needs driver-specific
+/// queries and types. Use [`DatabaseConnection::connect_any`] to pick the driver from a URL.
 #[derive(Debug, Clone)]
 pub enum DatabaseConnection {
+    #[cfg(feature = "postgres")]
     Postgres(Pool<Postgres>),
+    #[cfg(feature = "mysql")]
     MySql(Pool<MySql>),
+    #[cfg(feature = "sqlite")]
     Sqlite(Pool<Sqlite>),
-    Any(AnyPool),
+    #[cfg(feature = "clickhouse")]
+    ClickHouse(crate::db::clickhouse::ClickHouseClient),
+    #[cfg(feature = "oracle")]
+    Oracle(crate::db::oracle::OracleClient),
 }
 
 impl DatabaseConnection {
     /// Create a new database connection from configuration
+    ///
+    /// Connection failures are retried with exponential backoff and jitter up to
+    /// `connect_retries` times, which covers databases that are still starting up.
     pub async fn connect(config: &DatabaseConfig) -> Result<Self> {
+        let config = &crate::db::credentials::resolve(config).await?;
+        let max_retries = config.connect_retries.unwrap_or(0);
+        let mut attempt = 0;
+        
+        loop {
+            match Self::connect_once(config).await {
+                Ok(connection) => return Ok(connection),
+                Err(Error::SqlxError(e)) if attempt < max_retries && is_transient(&e) => {
+                    attempt += 1;
+                    let delay = backoff_delay(config, attempt);
+                    tracing::warn!(
+                        attempt = attempt,
+                        max_retries = max_retries,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "Database connection failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    
+    /// Connect to a database URL, detecting the driver from its scheme
+    ///
+    /// Accepts `postgres://`, `postgresql://`, `mysql://`, `mariadb://`, `sqlite:`,
+    /// `clickhouse://`, and `oracle://` URLs. The result is the same concrete connection `connect` would return, so
+    /// analysis, generation, and migrations all work; all other settings use their defaults.
+    pub async fn connect_any(url: &str) -> Result<Self> {
+        let driver = driver_from_url(url).ok_or_else(|| {
+            Error::ConfigError(format!("Cannot detect database driver from URL: {}", url))
+        })?;
+        
+        let config = DatabaseConfig {
+            driver: driver.to_string(),
+            url: Some(url.to_string()),
+            ..Default::default()
+        };
+        
+        Self::connect(&config).await
+    }
+    
+    /// Make a single connection attempt
+    async fn connect_once(config: &DatabaseConfig) -> Result<Self> {
         let pool_size = config.pool_size.unwrap_or(10) as u32;
         let timeout_seconds = config.timeout_seconds.unwrap_or(30);
+        let url = config.connection_url()?;
         
         match config.driver.as_str() {
+            #[cfg(feature = "postgres")]
             "postgres" => {
+                let options = postgres_connect_options(config, &url)?;
                 let pool = PgPoolOptions::new()
                     .max_connections(pool_size)
                     .acquire_timeout(std::time::Duration::from_secs(timeout_seconds))
-                    .connect(&config.url)
+                    .connect_with(options)
                     .await?;
                     
                 Ok(DatabaseConnection::Postgres(pool))
             }
+            #[cfg(feature = "mysql")]
             "mysql" => {
+                let options = mysql_connect_options(config, &url)?;
+                let read_only = config.read_only == Some(true);
                 let pool = MySqlPoolOptions::new()
                     .max_connections(pool_size)
                     .acquire_timeout(std::time::Duration::from_secs(timeout_seconds))
-                    .connect(&config.url)
+                    .after_connect(move |conn, _meta| {
+                        Box::pin(async move {
+                            // MySQL has no connect-time option for this, so set it per session
+                            if read_only {
+                                conn.execute("SET SESSION TRANSACTION READ ONLY").await?;
+                            }
+                            Ok(())
+                        })
+                    })
+                    .connect_with(options)
                     .await?;
                     
                 Ok(DatabaseConnection::MySql(pool))
             }
+            #[cfg(feature = "sqlite")]
             "sqlite" => {
+                let options = sqlite_connect_options(config, &url)?;
                 let pool = SqlitePoolOptions::new()
                     .max_connections(pool_size)
                     .acquire_timeout(std::time::Duration::from_secs(timeout_seconds))
-                    .connect(&config.url)
+                    .connect_with(options)
                     .await?;
                     
                 Ok(DatabaseConnection::Sqlite(pool))
             }
+            #[cfg(feature = "clickhouse")]
+            "clickhouse" => {
+                let client = crate::db::clickhouse::ClickHouseClient::connect(config, &url)?;
+                Ok(DatabaseConnection::ClickHouse(client))
+            }
+            #[cfg(feature = "oracle")]
+            "oracle" => {
+                let client = crate::db::oracle::OracleClient::connect(config, &url)?;
+                Ok(DatabaseConnection::Oracle(client))
+            }
+            // Known drivers reach this arm only when their feature is disabled
+            driver if matches!(driver, "postgres" | "mysql" | "sqlite" | "clickhouse" | "oracle") => Err(Error::ConfigError(format!(
+                "Database driver '{}' is not enabled; rebuild schema_sync with the `{}` feature",
+                config.driver, config.driver
+            ))),
             _ => Err(Error::DatabaseError(format!(
                 "Unsupported database driver: {}", config.driver
             ))),
         }
     }
     
+    /// Check that the database is reachable and responding
+    pub async fn ping(&self) -> Result<()> {
+        match self.driver() {
+            // Before 23ai every Oracle query needs a FROM clause
+            "oracle" => self.execute("SELECT 1 FROM dual").await,
+            _ => self.execute("SELECT 1").await,
+        }
+    }
+    
+    /// Wait until the database responds to a ping, retrying like `connect`
+    ///
+    /// Used before applying migrations so a connection that dropped since the
+    /// schema was analyzed is detected (and re-established by the pool) up front.
+    pub async fn wait_until_healthy(&self, config: &DatabaseConfig) -> Result<()> {
+        let max_retries = config.connect_retries.unwrap_or(0);
+        let mut attempt = 0;
+        
+        loop {
+            match self.ping().await {
+                Ok(()) => return Ok(()),
+                Err(Error::SqlxError(e)) if attempt < max_retries && is_transient(&e) => {
+                    attempt += 1;
+                    let delay = backoff_delay(config, attempt);
+                    tracing::warn!(
+                        attempt = attempt,
+                        max_retries = max_retries,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "Database health check failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    
+    /// Name of the driver behind this connection, as used in `database.driver`
+    pub fn driver(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabaseConnection::Postgres(_) => "postgres",
+            #[cfg(feature = "mysql")]
+            DatabaseConnection::MySql(_) => "mysql",
+            #[cfg(feature = "sqlite")]
+            DatabaseConnection::Sqlite(_) => "sqlite",
+            #[cfg(feature = "clickhouse")]
+            DatabaseConnection::ClickHouse(_) => "clickhouse",
+            #[cfg(feature = "oracle")]
+            DatabaseConnection::Oracle(_) => "oracle",
+        }
+    }
+    
     /// Get the schema name from the connection
     pub fn get_schema(&self) -> Option<&str> {
         None // In a real implementation, this would extract the schema from the connection
@@ -69,22 +241,365 @@ impl DatabaseConnection {
     /// Execute a SQL query
     pub async fn execute(&self, sql: &str) -> Result<()> {
         match self {
+            #[cfg(feature = "postgres")]
             DatabaseConnection::Postgres(pool) => {
                 sqlx::query(sql).execute(pool).await?;
                 Ok(())
             }
+            #[cfg(feature = "mysql")]
             DatabaseConnection::MySql(pool) => {
                 sqlx::query(sql).execute(pool).await?;
                 Ok(())
             }
+            #[cfg(feature = "sqlite")]
             DatabaseConnection::Sqlite(pool) => {
                 sqlx::query(sql).execute(pool).await?;
                 Ok(())
             }
-            DatabaseConnection::Any(pool) => {
-                sqlx::query(sql).execute(pool).await?;
+            #[cfg(feature = "clickhouse")]
+            DatabaseConnection::ClickHouse(client) => client.execute(sql).await,
+            #[cfg(feature = "oracle")]
+            DatabaseConnection::Oracle(client) => client.execute(sql).await,
+        }
+    }
+    
+    /// Run a query and return its first column as text
+    pub async fn query_column(&self, sql: &str) -> Result<Vec<Option<String>>> {
+        let values = match self {
+            #[cfg(feature = "postgres")]
+            DatabaseConnection::Postgres(pool) => {
+                sqlx::query_scalar::<_, Option<String>>(sql).fetch_all(pool).await?
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseConnection::MySql(pool) => {
+                sqlx::query_scalar::<_, Option<String>>(sql).fetch_all(pool).await?
+            }
+            #[cfg(feature = "sqlite")]
+            DatabaseConnection::Sqlite(pool) => {
+                sqlx::query_scalar::<_, Option<String>>(sql).fetch_all(pool).await?
+            }
+            #[cfg(feature = "clickhouse")]
+            DatabaseConnection::ClickHouse(client) => client.query_column(sql).await?,
+            #[cfg(feature = "oracle")]
+            DatabaseConnection::Oracle(client) => client.query_column(sql).await?,
+        };
+        
+        Ok(values)
+    }
+    
+    /// Run a `SELECT COUNT(*)` style query and return the count
+    pub async fn query_count(&self, sql: &str) -> Result<i64> {
+        let count = match self {
+            #[cfg(feature = "postgres")]
+            DatabaseConnection::Postgres(pool) => {
+                sqlx::query_scalar::<_, i64>(sql).fetch_one(pool).await?
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseConnection::MySql(pool) => {
+                sqlx::query_scalar::<_, i64>(sql).fetch_one(pool).await?
+            }
+            #[cfg(feature = "sqlite")]
+            DatabaseConnection::Sqlite(pool) => {
+                sqlx::query_scalar::<_, i64>(sql).fetch_one(pool).await?
+            }
+            #[cfg(feature = "clickhouse")]
+            DatabaseConnection::ClickHouse(client) => {
+                let value = client.query_column(sql).await?.into_iter().next().flatten();
+                value.as_deref().and_then(|v| v.parse().ok()).ok_or_else(|| {
+                    Error::DatabaseError(format!("Query did not return a count: {}", sql))
+                })?
+            }
+            #[cfg(feature = "oracle")]
+            DatabaseConnection::Oracle(client) => {
+                let value = client.query_column(sql).await?.into_iter().next().flatten();
+                value.as_deref().and_then(|v| v.parse().ok()).ok_or_else(|| {
+                    Error::DatabaseError(format!("Query did not return a count: {}", sql))
+                })?
+            }
+        };
+        
+        Ok(count)
+    }
+    
+    /// Execute SQL on a single pooled connection after applying session settings
+    ///
+    /// The session settings (e.g. `SET lock_timeout`) run on the same connection as
+    /// the SQL itself, so they actually take effect for it, and are undone before the
+    /// connection goes back to the pool. When `transactional` is set the SQL is wrapped
+    /// in a transaction that is rolled back if it fails.
+    pub async fn execute_in_session(
+        &self,
+        session: &[SessionSetting],
+        sql: &str,
+        transactional: bool,
+    ) -> Result<()> {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabaseConnection::Postgres(pool) => {
+                let mut conn = pool.acquire().await?;
+                run_in_session::<Postgres>(&mut conn, session, sql, transactional).await
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseConnection::MySql(pool) => {
+                let mut conn = pool.acquire().await?;
+                run_in_session::<MySql>(&mut conn, session, sql, transactional).await
+            }
+            #[cfg(feature = "sqlite")]
+            DatabaseConnection::Sqlite(pool) => {
+                let mut conn = pool.acquire().await?;
+                run_in_session::<Sqlite>(&mut conn, session, sql, transactional).await
+            }
+            // ClickHouse DDL is not transactional and the HTTP interface takes one
+            // statement per request, so the statements run one after another
+            #[cfg(feature = "clickhouse")]
+            DatabaseConnection::ClickHouse(client) => {
+                let _ = (session, transactional);
+                for statement in crate::schema::impact::split_statements(sql) {
+                    client.execute(&statement).await?;
+                }
                 Ok(())
             }
+            // Oracle commits around every DDL statement, and each ORDS request is a new
+            // session, so the settings are sent along with each statement
+            #[cfg(feature = "oracle")]
+            DatabaseConnection::Oracle(client) => {
+                let _ = transactional;
+                let settings: Vec<String> = session.iter().map(|setting| setting.set.clone()).collect();
+                client.execute_script(&settings, sql).await
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Map a connection URL's scheme to a `database.driver` name
+pub fn driver_from_url(url: &str) -> Option<&'static str> {
+    let scheme = url.split(':').next()?.to_lowercase();
+    match scheme.as_str() {
+        "postgres" | "postgresql" => Some("postgres"),
+        "mysql" | "mariadb" => Some("mysql"),
+        "sqlite" => Some("sqlite"),
+        "clickhouse" | "clickhouses" => Some("clickhouse"),
+        "oracle" | "oracles" => Some("oracle"),
+        _ => None,
+    }
+}
+
+/// Determine the effective TLS mode from `tls.mode`, `tls.verify_hostname`, and `enable_ssl`
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn tls_mode(config: &DatabaseConfig) -> Result<Option<String>> {
+    let configured = config.tls.as_ref().and_then(|tls| tls.mode.as_deref());
+    let mode = match (configured, config.enable_ssl) {
+        (Some(mode), _) => mode.to_lowercase(),
+        (_, Some(true)) => "require".to_string(),
+        (_, Some(false)) => "disable".to_string(),
+        _ => return Ok(None),
+    };
+    
+    let verify_hostname = config.tls.as_ref().and_then(|tls| tls.verify_hostname);
+    let mode = match (mode.as_str(), verify_hostname) {
+        ("verify-ca", Some(true)) => "verify-full".to_string(),
+        ("verify-full", Some(false)) => "verify-ca".to_string(),
+        ("disable" | "prefer" | "require" | "verify-ca" | "verify-full", _) => mode,
+        _ => {
+            return Err(Error::ConfigError(format!(
+                "Unsupported TLS mode: {} (expected disable, prefer, require, verify-ca, or verify-full)",
+                mode
+            )))
+        }
+    };
+    
+    Ok(Some(mode))
+}
+
+/// Build Postgres connect options with socket and TLS settings applied
+#[cfg(feature = "postgres")]
+fn postgres_connect_options(config: &DatabaseConfig, url: &str) -> Result<PgConnectOptions> {
+    let mut options = PgConnectOptions::from_str(url)?;
+    
+    if let Some(socket) = config.socket_path() {
+        options = options.socket(socket);
+    }
+    
+    if config.read_only == Some(true) {
+        options = options.options([("default_transaction_read_only", "on")]);
+    }
+    
+    if let Some(mode) = tls_mode(config)? {
+        options = options.ssl_mode(match mode.as_str() {
+            "disable" => PgSslMode::Disable,
+            "prefer" => PgSslMode::Prefer,
+            "require" => PgSslMode::Require,
+            "verify-ca" => PgSslMode::VerifyCa,
+            _ => PgSslMode::VerifyFull,
+        });
+    }
+    
+    if let Some(tls) = &config.tls {
+        if let Some(path) = &tls.root_cert {
+            options = options.ssl_root_cert(path);
+        }
+        if let Some(path) = &tls.client_cert {
+            options = options.ssl_client_cert(path);
+        }
+        if let Some(path) = &tls.client_key {
+            options = options.ssl_client_key(path);
+        }
+    }
+    
+    Ok(options)
+}
+
+/// Build MySQL connect options with socket and TLS settings applied
+#[cfg(feature = "mysql")]
+fn mysql_connect_options(config: &DatabaseConfig, url: &str) -> Result<MySqlConnectOptions> {
+    let mut options = MySqlConnectOptions::from_str(url)?;
+    
+    if let Some(socket) = config.socket_path() {
+        options = options.socket(socket);
+    }
+    
+    if let Some(mode) = tls_mode(config)? {
+        options = options.ssl_mode(match mode.as_str() {
+            "disable" => MySqlSslMode::Disabled,
+            "prefer" => MySqlSslMode::Preferred,
+            "require" => MySqlSslMode::Required,
+            "verify-ca" => MySqlSslMode::VerifyCa,
+            _ => MySqlSslMode::VerifyIdentity,
+        });
+    }
+    
+    if let Some(tls) = &config.tls {
+        if let Some(path) = &tls.root_cert {
+            options = options.ssl_ca(path);
+        }
+        if let Some(path) = &tls.client_cert {
+            options = options.ssl_client_cert(path);
+        }
+        if let Some(path) = &tls.client_key {
+            options = options.ssl_client_key(path);
+        }
+    }
+    
+    Ok(options)
+}
+
+/// Build SQLite connect options with the configured pragmas applied
+#[cfg(feature = "sqlite")]
+fn sqlite_connect_options(config: &DatabaseConfig, url: &str) -> Result<SqliteConnectOptions> {
+    let mut options = SqliteConnectOptions::from_str(url)?
+        .read_only(config.read_only == Some(true));
+    
+    let sqlite = match &config.sqlite {
+        Some(sqlite) => sqlite,
+        None => return Ok(options),
+    };
+    
+    if let Some(mode) = &sqlite.journal_mode {
+        let mode = SqliteJournalMode::from_str(mode).map_err(|_| {
+            Error::ConfigError(format!(
+                "Unsupported SQLite journal_mode: {} (expected delete, truncate, persist, memory, wal, or off)",
+                mode
+            ))
+        })?;
+        options = options.journal_mode(mode);
+    }
+    if let Some(synchronous) = &sqlite.synchronous {
+        let synchronous = SqliteSynchronous::from_str(synchronous).map_err(|_| {
+            Error::ConfigError(format!(
+                "Unsupported SQLite synchronous setting: {} (expected off, normal, full, or extra)",
+                synchronous
+            ))
+        })?;
+        options = options.synchronous(synchronous);
+    }
+    if let Some(on) = sqlite.foreign_keys {
+        options = options.foreign_keys(on);
+    }
+    if let Some(ms) = sqlite.busy_timeout_ms {
+        options = options.busy_timeout(Duration::from_millis(ms));
+    }
+    for (name, value) in sqlite.pragmas.iter().flatten() {
+        options = options.pragma(name.clone(), value.clone());
+    }
+    
+    Ok(options)
+}
+
+/// Check whether a connection error is worth retrying
+fn is_transient(error: &sqlx::Error) -> bool {
+    matches!(
+        error,
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Tls(_)
+    )
+}
+
+/// Compute the delay before the given retry attempt (exponential backoff with equal jitter)
+///
+/// The delay is random between half and all of the capped exponential delay, so retries
+/// spread out without ever coming back sooner than half the backoff.
+fn backoff_delay(config: &DatabaseConfig, attempt: u32) -> Duration {
+    let base_ms = config.connect_backoff_ms.unwrap_or(500);
+    let max_ms = config.connect_max_backoff_ms.unwrap_or(30_000);
+    
+    let exponential = base_ms.saturating_mul(1u64 << attempt.saturating_sub(1).min(20));
+    let capped = exponential.min(max_ms).max(1);
+    
+    Duration::from_millis(rand::thread_rng().gen_range(capped / 2..=capped))
+}
+
+/// Run session settings followed by the SQL on one connection, then undo the settings
+async fn run_in_session<DB>(
+    conn: &mut DB::Connection,
+    session: &[SessionSetting],
+    sql: &str,
+    transactional: bool,
+) -> Result<()>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    // Settings scoped to the transaction end with it; the rest are reset afterwards
+    let (local, reset): (Vec<&SessionSetting>, Vec<&SessionSetting>) =
+        session.iter().partition(|setting| transactional && setting.local.is_some());
+    
+    let mut result = run_with_settings::<DB>(conn, &local, &reset, sql, transactional).await;
+    for setting in reset {
+        let restored = conn.execute(setting.reset.as_str()).await;
+        if let (Ok(()), Err(e)) = (&result, restored) {
+            result = Err(e.into());
+        }
+    }
+    
+    result
+}
+
+/// Apply `session` settings and run the SQL, with `local` settings inside its transaction
+async fn run_with_settings<DB>(
+    conn: &mut DB::Connection,
+    local: &[&SessionSetting],
+    session: &[&SessionSetting],
+    sql: &str,
+    transactional: bool,
+) -> Result<()>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    for setting in session {
+        conn.execute(setting.set.as_str()).await?;
+    }
+    
+    if transactional {
+        // The transaction is rolled back on drop if the SQL fails
+        let mut tx = conn.begin().await?;
+        for statement in local.iter().filter_map(|setting| setting.local.as_deref()) {
+            tx.execute(statement).await?;
+        }
+        tx.execute(sql).await?;
+        tx.commit().await?;
+    } else {
+        conn.execute(sql).await?;
+    }
+    
+    Ok(())
+}
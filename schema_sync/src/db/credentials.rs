@@ -0,0 +1,439 @@
+//! Credential providers
+//!
+//! This module fetches database credentials from external secret stores
+//! (AWS Secrets Manager, HashiCorp Vault, GCP Secret Manager). Each provider
+//! is compiled in only when its Cargo feature is enabled.
+//!
+//! A secret may hold either a plain connection URL, a bare password, or a JSON
+//! object with any of `url`, `host`, `port`, `username`/`user`, `password`, and
+//! `dbname` (the format used by AWS RDS-managed secrets).
+//!
+//! With `iam_auth` enabled, a short-lived RDS IAM authentication token is
+//! generated and used as the password (RDS and RDS Proxy endpoints).
+
+use serde_json::Value;
+
+use crate::config::{CredentialsConfig, DatabaseConfig};
+use crate::error::{Error, Result};
+
+/// Resolve credentials from the configured secret store, if any
+///
+/// Returns a copy of the configuration with the secret's values filled in.
+pub async fn resolve(config: &DatabaseConfig) -> Result<DatabaseConfig> {
+    let mut resolved = config.clone();
+    
+    if let Some(credentials) = &config.credentials {
+        let secret = fetch_secret(credentials).await?;
+        apply_secret(&mut resolved, &secret)?;
+        tracing::debug!(provider = %credentials.provider, "Loaded database credentials from secret store");
+    }
+    
+    if resolved.iam_auth == Some(true) {
+        resolved.password = Some(rds_auth_token(&resolved)?);
+    }
+    
+    Ok(resolved)
+}
+
+/// Fetch the raw secret value from the configured provider
+async fn fetch_secret(credentials: &CredentialsConfig) -> Result<String> {
+    match credentials.provider.as_str() {
+        #[cfg(feature = "aws-secrets")]
+        "aws_secrets_manager" => aws::fetch(credentials).await,
+        #[cfg(not(feature = "aws-secrets"))]
+        "aws_secrets_manager" => Err(missing_feature(credentials, "aws-secrets")),
+        #[cfg(feature = "vault")]
+        "vault" => vault::fetch(credentials).await,
+        #[cfg(not(feature = "vault"))]
+        "vault" => Err(missing_feature(credentials, "vault")),
+        #[cfg(feature = "gcp-secrets")]
+        "gcp_secret_manager" => gcp::fetch(credentials).await,
+        #[cfg(not(feature = "gcp-secrets"))]
+        "gcp_secret_manager" => Err(missing_feature(credentials, "gcp-secrets")),
+        other => Err(Error::ConfigError(format!(
+            "Unknown credential provider: {}", other
+        ))),
+    }
+}
+
+/// Generate an RDS IAM authentication token for the configured host and user
+#[cfg(feature = "aws-secrets")]
+fn rds_auth_token(config: &DatabaseConfig) -> Result<String> {
+    let host = config.host.as_deref().ok_or_else(|| {
+        Error::ConfigError("IAM authentication requires `host` to be set".to_string())
+    })?;
+    let user = config.user.as_deref().ok_or_else(|| {
+        Error::ConfigError("IAM authentication requires `user` to be set".to_string())
+    })?;
+    let port = config.port.unwrap_or(if config.driver == "mysql" { 3306 } else { 5432 });
+    let region = config.credentials.as_ref().and_then(|c| c.region.clone());
+    
+    aws::rds_auth_token(host, port, user, region)
+}
+
+#[cfg(not(feature = "aws-secrets"))]
+fn rds_auth_token(_config: &DatabaseConfig) -> Result<String> {
+    Err(Error::ConfigError(
+        "IAM authentication requires SchemaSync to be built with the 'aws-secrets' feature".to_string()
+    ))
+}
+
+/// Error for a provider whose Cargo feature is not enabled
+#[cfg(not(all(feature = "aws-secrets", feature = "vault", feature = "gcp-secrets")))]
+fn missing_feature(credentials: &CredentialsConfig, feature: &str) -> Error {
+    Error::ConfigError(format!(
+        "Credential provider '{}' requires SchemaSync to be built with the '{}' feature",
+        credentials.provider, feature
+    ))
+}
+
+/// Merge a secret value into the database configuration
+fn apply_secret(config: &mut DatabaseConfig, secret: &str) -> Result<()> {
+    let secret = secret.trim();
+    
+    let fields = match serde_json::from_str::<Value>(secret) {
+        Ok(Value::Object(fields)) => fields,
+        _ if secret.contains("://") => {
+            config.url = Some(secret.to_string());
+            return Ok(());
+        }
+        _ => {
+            config.password = Some(secret.to_string());
+            return Ok(());
+        }
+    };
+    
+    let field = |names: &[&str]| {
+        names.iter().find_map(|name| match fields.get(*name) {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        })
+    };
+    
+    if let Some(url) = field(&["url"]) {
+        config.url = Some(url);
+    }
+    if let Some(host) = field(&["host"]) {
+        config.host = Some(host);
+    }
+    if let Some(port) = field(&["port"]) {
+        config.port = Some(port.parse().map_err(|_| {
+            Error::ConfigError(format!("Invalid port in secret: {}", port))
+        })?);
+    }
+    if let Some(user) = field(&["username", "user"]) {
+        config.user = Some(user);
+    }
+    if let Some(password) = field(&["password"]) {
+        config.password = Some(password);
+    }
+    if let Some(dbname) = field(&["dbname", "database"]) {
+        config.dbname = Some(dbname);
+    }
+    
+    Ok(())
+}
+
+/// Build a shared HTTP client for secret store requests
+#[cfg(any(feature = "aws-secrets", feature = "vault", feature = "gcp-secrets"))]
+fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| Error::ConfigError(format!("Failed to create HTTP client: {}", e)))
+}
+
+/// Read an environment variable required by a provider
+#[cfg(any(feature = "aws-secrets", feature = "vault", feature = "gcp-secrets"))]
+fn required_env(name: &str) -> Result<String> {
+    std::env::var(name).map_err(|_| {
+        Error::ConfigError(format!("Environment variable {} is required for credential lookup", name))
+    })
+}
+
+/// Map a failed secret store request to a configuration error
+#[cfg(any(feature = "aws-secrets", feature = "vault", feature = "gcp-secrets"))]
+fn request_error(provider: &str, e: impl std::fmt::Display) -> Error {
+    Error::ConfigError(format!("Failed to fetch secret from {}: {}", provider, e))
+}
+
+/// AWS Secrets Manager, authenticated with SigV4 using credentials from the environment
+#[cfg(feature = "aws-secrets")]
+mod aws {
+    use hmac::{Hmac, Mac};
+    use sha2::{Digest, Sha256};
+    
+    use super::{http_client, request_error, required_env};
+    use crate::config::{percent_encode, CredentialsConfig};
+    use crate::error::{Error, Result};
+    
+    const SERVICE: &str = "secretsmanager";
+    
+    pub async fn fetch(credentials: &CredentialsConfig) -> Result<String> {
+        let region = resolve_region(credentials.region.clone())?;
+        let access_key = required_env("AWS_ACCESS_KEY_ID")?;
+        let secret_key = required_env("AWS_SECRET_ACCESS_KEY")?;
+        let session_token = std::env::var("AWS_SESSION_TOKEN").ok();
+        
+        let host = format!("{}.{}.amazonaws.com", SERVICE, region);
+        let body = serde_json::json!({ "SecretId": credentials.secret_id }).to_string();
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        
+        // Canonical headers must be sorted by lowercase name
+        let mut headers = vec![
+            ("content-type", "application/x-amz-json-1.1".to_string()),
+            ("host", host.clone()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        if let Some(token) = &session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+        headers.push(("x-amz-target", "secretsmanager.GetSecretValue".to_string()));
+        
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+            .collect();
+        let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+        
+        let canonical_request = format!(
+            "POST\n/\n\n{}\n{}\n{}",
+            canonical_headers,
+            signed_headers,
+            hex::encode(Sha256::digest(body.as_bytes()))
+        );
+        
+        let scope = format!("{}/{}/{}/aws4_request", date, region, SERVICE);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        
+        let k_signing = signing_key(&secret_key, &date, &region, SERVICE);
+        let signature = hex::encode(hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+        
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            access_key, scope, signed_headers, signature
+        );
+        
+        let mut request = http_client()?
+            .post(format!("https://{}/", host))
+            .header("authorization", authorization);
+        for (name, value) in &headers {
+            if *name != "host" {
+                request = request.header(*name, value);
+            }
+        }
+        
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| request_error("AWS Secrets Manager", e))?;
+        let payload: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| request_error("AWS Secrets Manager", e))?;
+        
+        payload["SecretString"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| Error::ConfigError(format!(
+                "AWS secret {} has no SecretString", credentials.secret_id
+            )))
+    }
+    
+    /// Generate a presigned `rds-db:connect` token, valid for 15 minutes
+    pub fn rds_auth_token(host: &str, port: u16, user: &str, region: Option<String>) -> Result<String> {
+        let region = resolve_region(region)?;
+        let access_key = required_env("AWS_ACCESS_KEY_ID")?;
+        let secret_key = required_env("AWS_SECRET_ACCESS_KEY")?;
+        let session_token = std::env::var("AWS_SESSION_TOKEN").ok();
+        
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/rds-db/aws4_request", date, region);
+        let endpoint = format!("{}:{}", host, port);
+        
+        // Query parameters must be sorted by name
+        let mut params = vec![
+            ("Action", "connect".to_string()),
+            ("DBUser", user.to_string()),
+            ("X-Amz-Algorithm", "AWS4-HMAC-SHA256".to_string()),
+            ("X-Amz-Credential", format!("{}/{}", access_key, scope)),
+            ("X-Amz-Date", amz_date.clone()),
+            ("X-Amz-Expires", "900".to_string()),
+        ];
+        if let Some(token) = session_token {
+            params.push(("X-Amz-Security-Token", token));
+        }
+        params.push(("X-Amz-SignedHeaders", "host".to_string()));
+        
+        let query = params
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, percent_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        
+        let canonical_request = format!(
+            "GET\n/\n{}\nhost:{}\n\nhost\n{}",
+            query,
+            endpoint,
+            hex::encode(Sha256::digest(b""))
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        
+        let k_signing = signing_key(&secret_key, &date, &region, "rds-db");
+        let signature = hex::encode(hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+        
+        Ok(format!("{}/?{}&X-Amz-Signature={}", endpoint, query, signature))
+    }
+    
+    fn resolve_region(region: Option<String>) -> Result<String> {
+        match region {
+            Some(region) => Ok(region),
+            None => std::env::var("AWS_REGION").or_else(|_| required_env("AWS_DEFAULT_REGION")),
+        }
+    }
+    
+    /// Derive the SigV4 signing key for a date, region, and service
+    fn signing_key(secret_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+        let k_date = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), date.as_bytes());
+        let k_region = hmac_sha256(&k_date, region.as_bytes());
+        let k_service = hmac_sha256(&k_region, service.as_bytes());
+        hmac_sha256(&k_service, b"aws4_request")
+    }
+    
+    fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(data);
+        mac.finalize().into_bytes().to_vec()
+    }
+}
+
+/// HashiCorp Vault KV secrets (v1 or v2), authenticated with `VAULT_TOKEN`
+#[cfg(feature = "vault")]
+mod vault {
+    use super::{http_client, request_error, required_env};
+    use crate::config::CredentialsConfig;
+    use crate::error::{Error, Result};
+    
+    pub async fn fetch(credentials: &CredentialsConfig) -> Result<String> {
+        let address = match &credentials.address {
+            Some(address) => address.clone(),
+            None => required_env("VAULT_ADDR")?,
+        };
+        let token = required_env("VAULT_TOKEN")?;
+        
+        let url = format!(
+            "{}/v1/{}",
+            address.trim_end_matches('/'),
+            credentials.secret_id.trim_start_matches('/')
+        );
+        
+        let payload: serde_json::Value = http_client()?
+            .get(url)
+            .header("X-Vault-Token", token)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| request_error("Vault", e))?
+            .json()
+            .await
+            .map_err(|e| request_error("Vault", e))?;
+        
+        // KV v2 nests the secret under data.data, KV v1 under data
+        let data = match payload["data"].get("data") {
+            Some(inner) if inner.is_object() => inner,
+            _ => &payload["data"],
+        };
+        
+        if data.is_object() {
+            Ok(data.to_string())
+        } else {
+            Err(Error::ConfigError(format!(
+                "Vault secret {} has no data", credentials.secret_id
+            )))
+        }
+    }
+}
+
+/// GCP Secret Manager, authenticated with `GOOGLE_OAUTH_ACCESS_TOKEN` or the metadata server
+#[cfg(feature = "gcp-secrets")]
+mod gcp {
+    use base64::Engine;
+    
+    use super::{http_client, request_error};
+    use crate::config::CredentialsConfig;
+    use crate::error::{Error, Result};
+    
+    const METADATA_TOKEN_URL: &str =
+        "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+    
+    pub async fn fetch(credentials: &CredentialsConfig) -> Result<String> {
+        let client = http_client()?;
+        
+        let token = match std::env::var("GOOGLE_OAUTH_ACCESS_TOKEN") {
+            Ok(token) => token,
+            Err(_) => {
+                let payload: serde_json::Value = client
+                    .get(METADATA_TOKEN_URL)
+                    .header("Metadata-Flavor", "Google")
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .map_err(|e| request_error("GCP metadata server", e))?
+                    .json()
+                    .await
+                    .map_err(|e| request_error("GCP metadata server", e))?;
+                
+                payload["access_token"]
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| Error::ConfigError("GCP metadata server returned no access token".to_string()))?
+            }
+        };
+        
+        // Accept both ".../versions/N" and a bare secret name (latest version)
+        let name = if credentials.secret_id.contains("/versions/") {
+            credentials.secret_id.clone()
+        } else {
+            format!("{}/versions/latest", credentials.secret_id)
+        };
+        
+        let payload: serde_json::Value = client
+            .get(format!("https://secretmanager.googleapis.com/v1/{}:access", name))
+            .bearer_auth(token)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| request_error("GCP Secret Manager", e))?
+            .json()
+            .await
+            .map_err(|e| request_error("GCP Secret Manager", e))?;
+        
+        let encoded = payload["payload"]["data"]
+            .as_str()
+            .ok_or_else(|| Error::ConfigError(format!("GCP secret {} has no payload", name)))?;
+        
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| Error::ConfigError(format!("Invalid GCP secret payload: {}", e)))?;
+        
+        String::from_utf8(decoded)
+            .map_err(|e| Error::ConfigError(format!("GCP secret is not valid UTF-8: {}", e)))
+    }
+}
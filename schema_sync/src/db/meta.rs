@@ -0,0 +1,184 @@
+//! Schema metadata table
+//!
+//! This module stores facts about the synced schema, such as its fingerprint, in the
+//! `schema_sync_meta` table so they can be checked without analyzing the database.
+
+use crate::db::connection::DatabaseConnection;
+use crate::error::{Error, Result};
+
+/// Name of the table holding schema metadata
+pub const META_TABLE: &str = "schema_sync_meta";
+
+/// Metadata key under which the model schema fingerprint is stored
+pub const FINGERPRINT_KEY: &str = "schema_fingerprint";
+
+/// Prefix of the metadata keys under which each table's extra SQL checksum is stored
+pub const EXTRA_SQL_KEY_PREFIX: &str = "extra_sql:";
+
+/// Metadata key for the checksum of a table's extra SQL
+pub fn extra_sql_key(table: &str) -> String {
+    format!("{}{}", EXTRA_SQL_KEY_PREFIX, table)
+}
+
+/// Ensure the metadata table exists, widening the `name` column of one created by an older version
+pub async fn ensure_meta_table(connection: &DatabaseConnection) -> Result<()> {
+    let sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            name VARCHAR(255) NOT NULL PRIMARY KEY,
+            value VARCHAR(255) NOT NULL
+        )",
+        META_TABLE
+    );
+    // No IF NOT EXISTS before Oracle 23ai, so an existing table's error is ignored instead
+    let sql = match connection.driver() {
+        "oracle" => crate::schema::dialect::oracle_guarded(&sql.replacen(" IF NOT EXISTS", "", 1), &[955]),
+        _ => sql,
+    };
+
+    connection.execute(&sql).await?;
+    upgrade_meta_table(connection).await?;
+    Ok(())
+}
+
+/// Widen the `name` column of a metadata table created with `VARCHAR(64)` by older versions
+///
+/// Keys such as `extra_sql:<table>` outgrow 64 characters with long table names. SQLite
+/// doesn't enforce the length, so only Postgres and MySQL tables are altered. Returns
+/// whether the table was upgraded.
+pub async fn upgrade_meta_table(connection: &DatabaseConnection) -> Result<bool> {
+    let (schema, alter_sql) = match connection.driver() {
+        "postgres" => (
+            "current_schema()",
+            format!("ALTER TABLE {} ALTER COLUMN name TYPE VARCHAR(255)", META_TABLE),
+        ),
+        "mysql" => (
+            "DATABASE()",
+            format!("ALTER TABLE {} MODIFY name VARCHAR(255) NOT NULL", META_TABLE),
+        ),
+        _ => return Ok(false),
+    };
+
+    let narrow = connection
+        .query_count(&format!(
+            "SELECT COUNT(*) FROM information_schema.columns \
+             WHERE table_schema = {} AND table_name = '{}' AND column_name = 'name' \
+             AND character_maximum_length < 255",
+            schema, META_TABLE
+        ))
+        .await?;
+    if narrow == 0 {
+        return Ok(false);
+    }
+
+    connection.execute(&alter_sql).await?;
+    tracing::info!(table = META_TABLE, "Widened the metadata table's name column");
+    Ok(true)
+}
+
+/// Store a metadata value, replacing any previous one
+pub async fn write_value(connection: &DatabaseConnection, name: &str, value: &str) -> Result<()> {
+    ensure_meta_table(connection).await?;
+
+    let name = name.replace('\'', "''");
+    connection
+        .execute(&format!("DELETE FROM {} WHERE name = '{}'", META_TABLE, name))
+        .await?;
+    connection
+        .execute(&format!(
+            "INSERT INTO {} (name, value) VALUES ('{}', '{}')",
+            META_TABLE,
+            name,
+            value.replace('\'', "''")
+        ))
+        .await
+}
+
+/// Read a metadata value; `None` if it was never written
+///
+/// A database that has never been synced has no metadata table, which also reads as `None`.
+pub async fn read_value(connection: &DatabaseConnection, name: &str) -> Result<Option<String>> {
+    let sql = format!(
+        "SELECT value FROM {} WHERE name = '{}'",
+        META_TABLE,
+        name.replace('\'', "''")
+    );
+
+    match connection.query_column(&sql).await {
+        Ok(values) => Ok(values.into_iter().flatten().next()),
+        Err(Error::SqlxError(sqlx::Error::Database(_))) => Ok(None),
+        // ORA-00942: table or view does not exist
+        Err(Error::DatabaseError(message)) if message.starts_with("ORA-00942") => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Metadata key under which the startup sync lock is held
+pub const SYNC_LOCK_KEY: &str = "sync_lock";
+
+/// Try to take a named lock; `None` if someone else holds it
+///
+/// The lock is a row in the metadata table, so it works for every driver and across pooled
+/// connections. A lock taken more than `stale_after` ago is assumed to belong to a process
+/// that died and is taken over. Returns the token to pass to `unlock`.
+pub async fn try_lock(
+    connection: &DatabaseConnection,
+    name: &str,
+    stale_after: std::time::Duration,
+) -> Result<Option<String>> {
+    ensure_meta_table(connection).await?;
+
+    let now = chrono::Utc::now().timestamp();
+    let token = format!("{} {}", uuid::Uuid::new_v4(), now);
+    let escaped = name.replace('\'', "''");
+
+    // Only insert when the lock looks free: a failed INSERT is best avoided, since the
+    // SQLite driver can replay it on the pooled connection
+    let mut held = read_value(connection, name).await?;
+    if held.is_none() {
+        let insert = format!(
+            "INSERT INTO {} (name, value) VALUES ('{}', '{}')",
+            META_TABLE, escaped, token
+        );
+        match connection.execute(&insert).await {
+            Ok(()) => return Ok(Some(token)),
+            // Taken by someone else in the meantime
+            Err(Error::SqlxError(sqlx::Error::Database(e))) if e.is_unique_violation() => {}
+            // ORA-00001: unique constraint violated
+            Err(Error::DatabaseError(message)) if message.starts_with("ORA-00001") => {}
+            Err(e) => return Err(e),
+        }
+        held = read_value(connection, name).await?;
+    }
+
+    // Held already; take it over only if the holder has been at it for too long
+    let taken_at = held
+        .as_deref()
+        .and_then(|value| value.rsplit(' ').next())
+        .and_then(|timestamp| timestamp.parse::<i64>().ok());
+    match (held, taken_at) {
+        (Some(held), Some(taken_at)) if now - taken_at > stale_after.as_secs() as i64 => {
+            tracing::warn!(lock = %name, "Taking over a stale lock");
+            connection
+                .execute(&format!(
+                    "UPDATE {} SET value = '{}' WHERE name = '{}' AND value = '{}'",
+                    META_TABLE, token, escaped, held.replace('\'', "''")
+                ))
+                .await?;
+            // Another process may have taken it over first
+            Ok((read_value(connection, name).await?.as_deref() == Some(token.as_str())).then_some(token))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Release a lock taken with `try_lock`; a lock taken over by someone else is left alone
+pub async fn unlock(connection: &DatabaseConnection, name: &str, token: &str) -> Result<()> {
+    connection
+        .execute(&format!(
+            "DELETE FROM {} WHERE name = '{}' AND value = '{}'",
+            META_TABLE,
+            name.replace('\'', "''"),
+            token.replace('\'', "''")
+        ))
+        .await
+}
@@ -6,113 +6,707 @@ use chrono::Utc;
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
-use crate::config::MigrationsConfig;
-use crate::db::connection::DatabaseConnection;
-use crate::error::{Error, Result};
+use crate::config::{MigrationsConfig, PerformanceConfig, SqliteConfig};
+use crate::db::connection::{DatabaseConnection, SessionSetting};
+use crate::error::{classify_sqlx_error, Error, MigrationFailure, Result};
+use crate::events::EventHandler;
+use crate::schema::dialect::{oracle_guarded, Dialect};
 
 /// Apply migrations to the database
+///
+/// Applying stops at the first statement the database rejects; that statement is reported in
+/// `ApplyReport::failed` rather than as an error. Errors are kept for failures that leave the
+/// run in an unknown state, such as writing migration files or the history table.
 pub async fn apply_migrations(
     connection: &DatabaseConnection,
     migrations: Vec<String>,
     config: &MigrationsConfig,
-) -> Result<()> {
+    performance: Option<&PerformanceConfig>,
+    events: Option<&dyn EventHandler>,
+) -> Result<ApplyReport> {
+    let started = Instant::now();
+
     // Create migrations directory if it doesn't exist
     fs::create_dir_all(&config.directory)?;
 
     // Create migration history table if it doesn't exist
-    ensure_migration_history_table(connection, &config.history_table).await?;
+    ensure_migration_history_table(connection, &config.history_table_name()).await?;
+
+    // Session settings applied before every migration
+    let session = session_settings(connection, performance).await?;
+    let mut report = ApplyReport::default();
 
     for (i, migration_sql) in migrations.iter().enumerate() {
+        if report.failed.is_some() {
+            report.skipped.push(migration_sql.clone());
+            continue;
+        }
+
         let migration_id = generate_migration_id(i);
         let filename = format!("{}_{}.sql", migration_id, "schema_sync_migration");
         let filepath = Path::new(&config.directory).join(&filename);
 
-        // Write migration to file
+        // Write migration to file, tagged with the schema version it belongs to
         let mut file = File::create(&filepath)?;
+        if let Some(version) = &config.schema_version {
+            writeln!(file, "{}{}", VERSION_HEADER, version)?;
+        }
         file.write_all(migration_sql.as_bytes())?;
 
+        if config.dry_run {
+            report.skipped.push(migration_sql.clone());
+            continue;
+        }
+
         // Apply migration
-        if !config.dry_run {
-            tracing::info!(migration_id = migration_id, "Applying migration");
+        tracing::info!(migration_id = migration_id, "Applying migration");
+        let statement_started = Instant::now();
 
-            if config.transaction_per_migration {
-                apply_migration_in_transaction(connection, migration_sql).await?;
-            } else {
-                connection.execute(migration_sql).await?;
-            }
+        let result = apply_migration_with_retry(
+            connection,
+            &session,
+            migration_sql,
+            config.transaction_per_migration,
+            performance,
+        )
+        .await;
+
+        if let Err(error) = result {
+            tracing::error!(migration_id = migration_id, error = %error, "Migration failed");
+            report.failed = Some(FailedMigration {
+                migration_id,
+                sql: migration_sql.clone(),
+                error,
+                duration: statement_started.elapsed(),
+            });
+            continue;
+        }
+
+        let applied = AppliedMigration {
+            migration_id,
+            sql: migration_sql.clone(),
+            duration: statement_started.elapsed(),
+        };
+
+        // Record migration in history table
+        record_migration(
+            connection,
+            &config.history_table_name(),
+            &applied,
+            &filename,
+            config.schema_version.as_deref(),
+        )
+        .await?;
+
+        tracing::info!(
+            migration_id = applied.migration_id,
+            "Migration applied successfully"
+        );
+
+        if let Some(events) = events {
+            events.on_statement_applied(&applied.migration_id, migration_sql).await;
+        }
+        report.applied.push(applied);
+    }
+
+    report.duration = started.elapsed();
+    Ok(report)
+}
+
+/// Outcome of applying a batch of migrations
+#[derive(Debug, Default)]
+pub struct ApplyReport {
+    /// Migrations that ran and were recorded in the history table, in order
+    pub applied: Vec<AppliedMigration>,
+    /// SQL of the migrations that did not run, because of a dry run or an earlier failure
+    pub skipped: Vec<String>,
+    /// The migration the database rejected, if any
+    pub failed: Option<FailedMigration>,
+    /// Time taken by the whole batch
+    pub duration: Duration,
+}
+
+/// A migration that ran successfully
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    pub migration_id: String,
+    pub sql: String,
+    pub duration: Duration,
+}
+
+/// A migration the database rejected
+#[derive(Debug)]
+pub struct FailedMigration {
+    pub migration_id: String,
+    pub sql: String,
+    pub error: Error,
+    pub duration: Duration,
+}
+
+impl ApplyReport {
+    /// IDs of the applied migrations, in order
+    pub fn migration_ids(&self) -> Vec<String> {
+        self.applied.iter().map(|migration| migration.migration_id.clone()).collect()
+    }
+
+    /// Whether no migration failed
+    pub fn is_success(&self) -> bool {
+        self.failed.is_none()
+    }
+
+    /// Turn a failed migration into an error, for callers that only care whether everything applied
+    pub fn into_result(mut self) -> Result<Self> {
+        match self.failed.take() {
+            Some(failed) => Err(failed.error),
+            None => Ok(self),
+        }
+    }
+}
+
+/// Header line that tags a migration file with its schema version
+pub const VERSION_HEADER: &str = "-- schema_version: ";
+
+/// A migration file read back from the migrations directory
+#[derive(Debug, Clone)]
+pub struct MigrationFile {
+    pub migration_id: String,
+    pub filename: String,
+    pub schema_version: Option<String>,
+    pub sql: String,
+}
+
+/// Read all `.sql` migrations from a directory in the order they were generated
+pub fn read_migration_files(directory: &str) -> Result<Vec<MigrationFile>> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("sql") {
+            continue;
+        }
+
+        let filename = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        files.push(MigrationFile::new(filename, fs::read_to_string(&path)?));
+    }
+
+    files.sort_by(|a, b| a.filename.cmp(&b.filename));
+    Ok(files)
+}
+
+impl MigrationFile {
+    /// Describe a migration from its file name and contents
+    pub fn new(filename: &str, sql: String) -> Self {
+        let schema_version = sql
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix(VERSION_HEADER))
+            .map(|version| version.trim().to_string());
+
+        // Generated names are "<timestamp>_<sequence>_<name>.sql"
+        let migration_id = filename.splitn(3, '_').take(2).collect::<Vec<_>>().join("_");
+
+        Self {
+            migration_id,
+            filename: filename.to_string(),
+            schema_version,
+            sql,
+        }
+    }
+}
+
+/// Migration files compiled into the binary by `embed_migrations!`
+///
+/// Lets a deployed service bring its database up to date at startup without the
+/// migrations directory on disk; see `SchemaSyncClient::apply_embedded_migrations`.
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedMigrations {
+    files: &'static [(&'static str, &'static str)],
+}
+
+impl EmbeddedMigrations {
+    /// Wrap `(file name, contents)` pairs; normally called by `embed_migrations!`
+    pub const fn new(files: &'static [(&'static str, &'static str)]) -> Self {
+        Self { files }
+    }
+
+    /// The embedded migrations, in the order they were generated
+    pub fn files(&self) -> Vec<MigrationFile> {
+        let mut files: Vec<MigrationFile> = self
+            .files
+            .iter()
+            .map(|(filename, sql)| MigrationFile::new(filename, sql.to_string()))
+            .collect();
+        files.sort_by(|a, b| a.filename.cmp(&b.filename));
+        files
+    }
+}
+
+/// Apply pending migration files up to and including the last one tagged `version`
+pub async fn upgrade_to(
+    connection: &DatabaseConnection,
+    version: &str,
+    config: &MigrationsConfig,
+    performance: Option<&PerformanceConfig>,
+    events: Option<&dyn EventHandler>,
+) -> Result<()> {
+    let files = read_migration_files(&config.directory)?;
+    let target = last_index_of_version(&files, version)?;
+
+    apply_pending(connection, &files[..=target], config, performance, events)
+        .await?
+        .into_result()
+        .map(|_| ())
+}
+
+/// Apply the migration files not yet recorded in the history table, in order
+///
+/// As with `apply_migrations`, a statement the database rejects stops the run and is
+/// reported in `ApplyReport::failed`.
+pub async fn apply_pending(
+    connection: &DatabaseConnection,
+    files: &[MigrationFile],
+    config: &MigrationsConfig,
+    performance: Option<&PerformanceConfig>,
+    events: Option<&dyn EventHandler>,
+) -> Result<ApplyReport> {
+    let started = Instant::now();
+
+    ensure_migration_history_table(connection, &config.history_table_name()).await?;
+    let applied = applied_migrations(connection, &config.history_table_name()).await?;
+    let session = session_settings(connection, performance).await?;
+    let mut report = ApplyReport::default();
+
+    for file in files {
+        if applied.contains(&file.filename) {
+            continue;
+        }
+        if report.failed.is_some() {
+            report.skipped.push(file.sql.clone());
+            continue;
+        }
+
+        tracing::info!(
+            migration_id = file.migration_id,
+            schema_version = file.schema_version.as_deref(),
+            "Applying migration"
+        );
+        let statement_started = Instant::now();
+
+        let result = apply_migration_with_retry(
+            connection,
+            &session,
+            &file.sql,
+            config.transaction_per_migration,
+            performance,
+        )
+        .await;
+
+        if let Err(error) = result {
+            report.failed = Some(FailedMigration {
+                migration_id: file.migration_id.clone(),
+                sql: file.sql.clone(),
+                error,
+                duration: statement_started.elapsed(),
+            });
+            continue;
+        }
+
+        let applied = AppliedMigration {
+            migration_id: file.migration_id.clone(),
+            sql: file.sql.clone(),
+            duration: statement_started.elapsed(),
+        };
+        record_migration(
+            connection,
+            &config.history_table_name(),
+            &applied,
+            &file.filename,
+            file.schema_version.as_deref(),
+        )
+        .await?;
+
+        if let Some(events) = events {
+            events.on_statement_applied(&file.migration_id, &file.sql).await;
+        }
+        report.applied.push(applied);
+    }
+
+    report.duration = started.elapsed();
+    Ok(report)
+}
+
+/// Apply SQL that reverts the given migrations, then remove them from the history table
+pub async fn revert_migrations(
+    connection: &DatabaseConnection,
+    revert_sql: &[String],
+    reverted: &[String],
+    config: &MigrationsConfig,
+    performance: Option<&PerformanceConfig>,
+) -> Result<()> {
+    let session = session_settings(connection, performance).await?;
+
+    for sql in revert_sql {
+        apply_migration_with_retry(
+            connection,
+            &session,
+            sql,
+            config.transaction_per_migration,
+            performance,
+        )
+        .await?;
+    }
 
-            // Record migration in history table
-            record_migration(connection, &config.history_table, &migration_id, &filename).await?;
+    forget_migrations(connection, &config.history_table_name(), reverted).await
+}
 
-            tracing::info!(
-                migration_id = migration_id,
-                "Migration applied successfully"
-            );
+/// Index of the last migration file tagged with `version`
+pub fn last_index_of_version(files: &[MigrationFile], version: &str) -> Result<usize> {
+    files
+        .iter()
+        .rposition(|file| file.schema_version.as_deref() == Some(version))
+        .ok_or_else(|| {
+            MigrationFailure::new(
+                "unknown_version",
+                format!("No migrations are tagged with schema version {}", version),
+            )
+            .into()
+        })
+}
+
+/// Most recent schema version recorded in the history table
+pub async fn current_schema_version(
+    connection: &DatabaseConnection,
+    table_name: &str,
+) -> Result<Option<String>> {
+    let sql = format!(
+        "SELECT schema_version FROM {} WHERE schema_version IS NOT NULL ORDER BY migration_id DESC",
+        table_name
+    );
+
+    Ok(connection.query_column(&sql).await?.into_iter().flatten().next())
+}
+
+/// Filenames of all migrations recorded in the history table
+pub async fn applied_migrations(
+    connection: &DatabaseConnection,
+    table_name: &str,
+) -> Result<Vec<String>> {
+    let sql = format!("SELECT name FROM {}", table_name);
+    Ok(connection.query_column(&sql).await?.into_iter().flatten().collect())
+}
+
+/// Remove migrations from the history table after they have been reverted
+pub async fn forget_migrations(
+    connection: &DatabaseConnection,
+    table_name: &str,
+    filenames: &[String],
+) -> Result<()> {
+    for filename in filenames {
+        let sql = format!("DELETE FROM {} WHERE name = '{}'", table_name, filename.replace('\'', "''"));
+        connection.execute(&sql).await?;
+    }
+
+    Ok(())
+}
+
+/// Record the persistent SQLite header fields after migrating
+///
+/// `application_id` is written when it differs from the configured value, and with
+/// `record_user_version` an integer schema version is stored in `user_version`.
+/// Other databases have no equivalent, so this does nothing for them.
+pub async fn record_sqlite_header(
+    connection: &DatabaseConnection,
+    config: &SqliteConfig,
+    schema_version: Option<&str>,
+) -> Result<()> {
+    if connection.driver() != "sqlite" {
+        return Ok(());
+    }
+
+    if let Some(application_id) = config.application_id {
+        if connection.query_count("PRAGMA application_id").await? != i64::from(application_id) {
+            connection.execute(&format!("PRAGMA application_id = {}", application_id)).await?;
         }
     }
 
+    if config.record_user_version != Some(true) {
+        return Ok(());
+    }
+    let schema_version = match schema_version {
+        Some(version) => version,
+        None => return Ok(()),
+    };
+    match sqlite_user_version(schema_version) {
+        Some(user_version) => {
+            connection.execute(&format!("PRAGMA user_version = {}", user_version)).await?;
+        }
+        None => tracing::warn!(
+            schema_version = schema_version,
+            "Schema version is not an integer, so it was not recorded in PRAGMA user_version"
+        ),
+    }
+
     Ok(())
 }
 
-/// Ensure the migration history table exists
-async fn ensure_migration_history_table(
+/// The `PRAGMA user_version` value for a schema version tag such as "12" or "v12"
+pub fn sqlite_user_version(schema_version: &str) -> Option<i32> {
+    let version = schema_version.trim();
+    version.strip_prefix(['v', 'V']).unwrap_or(version).parse().ok()
+}
+
+/// Columns added to the history table after its first release, with their definitions
+///
+/// History tables created by an older SchemaSync lack some of them; they are added the
+/// first time a newer version uses the table.
+const HISTORY_COLUMNS: [(&str, &str); 4] = [
+    ("checksum", "VARCHAR(64) NULL"),
+    ("execution_time_ms", "INTEGER NULL"),
+    ("schema_version", "VARCHAR(64) NULL"),
+    ("applied_by", "VARCHAR(255) NULL"),
+];
+
+/// Ensure the migration history table exists, with every column this version writes
+///
+/// The table is created with the connected database's own types. A qualified name puts it
+/// in that schema, which is created first on Postgres.
+pub(crate) async fn ensure_migration_history_table(
     connection: &DatabaseConnection,
     table_name: &str,
 ) -> Result<()> {
-    let create_table_sql = format!(
-        "CREATE TABLE IF NOT EXISTS {} (
-            id SERIAL PRIMARY KEY,
+    let dialect = Dialect::from_driver(connection.driver()).ok_or_else(|| {
+        Error::ConfigError(format!("Unsupported database type: {}", connection.driver()))
+    })?;
+
+    if let (Dialect::Postgres, Some((schema, _))) = (dialect, table_name.rsplit_once('.')) {
+        connection.execute(&format!("CREATE SCHEMA IF NOT EXISTS {}", schema)).await?;
+    }
+
+    connection.execute(&history_table_sql(dialect, table_name)).await?;
+    upgrade_history_table(connection, table_name).await?;
+
+    Ok(())
+}
+
+/// DDL creating the migration history table in `dialect`
+pub fn history_table_sql(dialect: Dialect, table_name: &str) -> String {
+    let columns: String = HISTORY_COLUMNS
+        .iter()
+        .map(|(name, definition)| format!(",\n            {} {}", name, definition))
+        .collect();
+
+    // Oracle wants the default ahead of NOT NULL, and before 23ai has no IF NOT EXISTS, so
+    // an existing table's error is ignored instead
+    if dialect == Dialect::Oracle {
+        let sql = format!(
+            "CREATE TABLE {} (
+            id {},
             migration_id VARCHAR(255) NOT NULL,
             name VARCHAR(255) NOT NULL,
-            applied_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
-            checksum VARCHAR(64) NULL,
-            execution_time_ms INTEGER NULL
+            applied_at {} DEFAULT {} NOT NULL{}
         )",
-        table_name
-    );
+            table_name,
+            dialect.serial_primary_key(),
+            dialect.timestamp_type(),
+            dialect.current_timestamp(),
+            columns
+        );
+        return oracle_guarded(&sql, &[955]);
+    }
+
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            id {},
+            migration_id VARCHAR(255) NOT NULL,
+            name VARCHAR(255) NOT NULL,
+            applied_at {} NOT NULL DEFAULT {}{}
+        ){}",
+        table_name,
+        dialect.serial_primary_key(),
+        dialect.timestamp_type(),
+        dialect.current_timestamp(),
+        columns,
+        // ClickHouse tables need an engine; history is read back in application order
+        if dialect == Dialect::ClickHouse { " ENGINE = MergeTree ORDER BY applied_at" } else { "" }
+    )
+}
+
+/// Add the columns a history table created by an older version is missing
+///
+/// Returns the names of the columns added. Rows recorded before the upgrade keep NULL in them.
+pub async fn upgrade_history_table(connection: &DatabaseConnection, table_name: &str) -> Result<Vec<String>> {
+    let probe = |columns: &str| format!("SELECT {} FROM {} WHERE 1 = 0", columns, table_name);
+
+    // One query when the table is already current
+    let all_columns: Vec<&str> = HISTORY_COLUMNS.iter().map(|(name, _)| *name).collect();
+    if connection.execute(&probe(&all_columns.join(", "))).await.is_ok() {
+        return Ok(Vec::new());
+    }
+
+    let mut added = Vec::new();
+    for (column, definition) in HISTORY_COLUMNS {
+        if connection.execute(&probe(column)).await.is_ok() {
+            continue;
+        }
+        let alter_sql = match connection.driver() {
+            // Oracle adds columns in parentheses, without the COLUMN keyword
+            "oracle" => format!("ALTER TABLE {} ADD ({} {})", table_name, column, definition),
+            _ => format!("ALTER TABLE {} ADD COLUMN {} {}", table_name, column, definition),
+        };
+        connection.execute(&alter_sql).await?;
+        tracing::info!(table = table_name, column = column, "Upgraded the migration history table");
+        added.push(column.to_string());
+    }
 
-    connection.execute(&create_table_sql).await
+    Ok(added)
 }
 
-/// Apply a migration within a transaction
-async fn apply_migration_in_transaction(
+/// Build the per-session timeout settings for the connected database
+///
+/// Each setting carries the statement that undoes it, so the timeouts don't stay on the
+/// pooled connection for later reads or for application queries sharing the pool.
+async fn session_settings(
     connection: &DatabaseConnection,
-    migration_sql: &str,
-) -> Result<()> {
-    // Start transaction SQL depends on database type
-    let start_transaction = "BEGIN;";
-    let commit_transaction = "COMMIT;";
+    performance: Option<&PerformanceConfig>,
+) -> Result<Vec<SessionSetting>> {
+    let performance = match performance {
+        Some(performance) => performance,
+        None => return Ok(Vec::new()),
+    };
 
-    // Execute start transaction
-    connection.execute(start_transaction).await?;
+    let mut settings = Vec::new();
 
-    // Execute migration SQL
-    match connection.execute(migration_sql).await {
-        Ok(_) => {
-            // Commit transaction
-            connection.execute(commit_transaction).await?;
-            Ok(())
+    match connection {
+        #[cfg(feature = "postgres")]
+        DatabaseConnection::Postgres(_) => {
+            let timeouts = [
+                ("lock_timeout", performance.lock_timeout_ms),
+                ("statement_timeout", performance.statement_timeout_ms),
+            ];
+            for (name, ms) in timeouts {
+                if let Some(ms) = ms {
+                    settings.push(SessionSetting {
+                        set: format!("SET {} = '{}ms'", name, ms),
+                        local: Some(format!("SET LOCAL {} = '{}ms'", name, ms)),
+                        reset: format!("RESET {}", name),
+                    });
+                }
+            }
+        }
+        #[cfg(feature = "mysql")]
+        DatabaseConnection::MySql(_) => {
+            // lock_wait_timeout is in whole seconds; max_execution_time only affects
+            // SELECT statements, so there is no MySQL equivalent of statement_timeout for DDL
+            if let Some(ms) = performance.lock_timeout_ms {
+                let seconds = ms.div_ceil(1000).max(1);
+                settings.push(SessionSetting {
+                    set: format!("SET SESSION lock_wait_timeout = {}", seconds),
+                    local: None,
+                    reset: "SET SESSION lock_wait_timeout = DEFAULT".to_string(),
+                });
+            }
         }
-        Err(e) => {
-            // Rollback transaction
-            let rollback_transaction = "ROLLBACK;";
-            let _ = connection.execute(rollback_transaction).await;
-            Err(e)
+        #[cfg(feature = "sqlite")]
+        DatabaseConnection::Sqlite(pool) => {
+            // Pooled connections share their connect options, so any of them has the
+            // busy timeout to go back to
+            if let Some(ms) = performance.lock_timeout_ms {
+                let previous: i64 = sqlx::query_scalar("PRAGMA busy_timeout").fetch_one(pool).await?;
+                settings.push(SessionSetting {
+                    set: format!("PRAGMA busy_timeout = {}", ms),
+                    local: None,
+                    reset: format!("PRAGMA busy_timeout = {}", previous),
+                });
+            }
+        }
+        // Every HTTP request is its own session, so there is nothing to carry over
+        #[cfg(feature = "clickhouse")]
+        DatabaseConnection::ClickHouse(_) => {}
+        // DDL_LOCK_TIMEOUT is in whole seconds; each ORDS request is a new session, so the
+        // setting is sent with every statement and ends with it
+        #[cfg(feature = "oracle")]
+        DatabaseConnection::Oracle(_) => {
+            if let Some(ms) = performance.lock_timeout_ms {
+                settings.push(SessionSetting {
+                    set: format!("ALTER SESSION SET DDL_LOCK_TIMEOUT = {}", ms.div_ceil(1000).max(1)),
+                    local: None,
+                    reset: "ALTER SESSION SET DDL_LOCK_TIMEOUT = 0".to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(settings)
+}
+
+/// Apply a migration, retrying with exponential backoff when it hits a lock timeout
+async fn apply_migration_with_retry(
+    connection: &DatabaseConnection,
+    session: &[SessionSetting],
+    migration_sql: &str,
+    transactional: bool,
+    performance: Option<&PerformanceConfig>,
+) -> Result<()> {
+    let max_retries = performance.and_then(|p| p.lock_retry_attempts).unwrap_or(0);
+    let mut backoff_ms = performance.and_then(|p| p.lock_retry_backoff_ms).unwrap_or(500);
+    let mut attempt = 0;
+
+    loop {
+        match connection
+            .execute_in_session(session, migration_sql, transactional)
+            .await
+        {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < max_retries && is_lock_timeout(&e) => {
+                attempt += 1;
+                tracing::warn!(
+                    attempt = attempt,
+                    max_retries = max_retries,
+                    backoff_ms = backoff_ms,
+                    "Migration hit a lock timeout, retrying"
+                );
+                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                backoff_ms = backoff_ms.saturating_mul(2);
+            }
+            Err(e) => return Err(e.in_statement(migration_sql)),
         }
     }
 }
 
+/// Check whether an error was caused by waiting too long for a lock
+fn is_lock_timeout(error: &Error) -> bool {
+    matches!(error, Error::SqlxError(e) if classify_sqlx_error(e) == Some("lock_timeout"))
+}
+
 /// Record a migration in the history table
 async fn record_migration(
     connection: &DatabaseConnection,
     table_name: &str,
-    migration_id: &str,
+    migration: &AppliedMigration,
     filename: &str,
+    schema_version: Option<&str>,
 ) -> Result<()> {
+    let quoted = |value: Option<&str>| match value {
+        Some(value) => format!("'{}'", value.replace('\'', "''")),
+        None => "NULL".to_string(),
+    };
+    let checksum = format!("{:x}", md5::compute(migration.sql.as_bytes()));
+    let applied_by = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok();
     let sql = format!(
-        "INSERT INTO {} (migration_id, name, applied_at) VALUES ('{}', '{}', CURRENT_TIMESTAMP)",
-        table_name, migration_id, filename
+        "INSERT INTO {} (migration_id, name, applied_at, checksum, execution_time_ms, schema_version, applied_by) \
+         VALUES ({}, {}, CURRENT_TIMESTAMP, '{}', {}, {}, {})",
+        table_name,
+        quoted(Some(&migration.migration_id)),
+        quoted(Some(filename)),
+        checksum,
+        migration.duration.as_millis().min(i32::MAX as u128),
+        quoted(schema_version),
+        quoted(applied_by.as_deref())
     );
 
     connection.execute(&sql).await
@@ -2,9 +2,16 @@
 //!
 //! This module handles database connections and migrations.
 
+#[cfg(feature = "clickhouse")]
+pub mod clickhouse;
 pub mod connection;
+pub mod credentials;
 pub mod executor;
+pub mod meta;
 pub mod migrations;
+#[cfg(feature = "oracle")]
+pub mod oracle;
+pub mod validation;
 
 // Re-export key types
 pub use connection::DatabaseConnection;
\ No newline at end of file
@@ -239,6 +239,10 @@ impl ModelRegistry {
                     is_unique: field.unique,
                     is_generated: false,
                     generation_expression: None,
+                    charset: None,
+                    default_fn: None,
+                    storage: None,
+                    collation: None,
                 };
                 
                 table.add_column(column);
@@ -269,6 +273,10 @@ impl ModelRegistry {
                     is_unique: false,
                     is_generated: false,
                     generation_expression: None,
+                    charset: None,
+                    default_fn: None,
+                    storage: None,
+                    collation: None,
                 });
             }
             
@@ -282,6 +290,10 @@ impl ModelRegistry {
                     is_unique: false,
                     is_generated: false,
                     generation_expression: None,
+                    charset: None,
+                    default_fn: None,
+                    storage: None,
+                    collation: None,
                 });
             }
             
@@ -0,0 +1,183 @@
+//! Pre-apply data validation
+//!
+//! This module checks live data against the constraints a schema diff is about to add
+//! (NOT NULL, UNIQUE, foreign keys), so violations are reported up front instead of
+//! surfacing as a DDL failure halfway through a migration.
+
+use std::fmt;
+
+use crate::db::connection::DatabaseConnection;
+use crate::error::{MigrationFailure, Result};
+use crate::schema::diff::SchemaDiff;
+use crate::schema::types::ForeignKey;
+
+/// Existing rows that would violate a constraint being added
+#[derive(Debug, Clone)]
+pub struct DataViolation {
+    pub table: String,
+    pub columns: Vec<String>,
+    /// Constraint being added: "NOT NULL", "UNIQUE", or "FOREIGN KEY"
+    pub constraint: String,
+    /// Number of offending rows (or duplicate groups for UNIQUE)
+    pub count: i64,
+    /// Query that finds the offending rows
+    pub query: String,
+}
+
+impl fmt::Display for DataViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} on {}({}): {} violating row(s), found by: {}",
+            self.constraint,
+            self.table,
+            self.columns.join(", "),
+            self.count,
+            self.query
+        )
+    }
+}
+
+/// Run validation queries for every constraint the diff adds to existing tables
+pub async fn validate_diff(
+    connection: &DatabaseConnection,
+    diff: &SchemaDiff,
+) -> Result<Vec<DataViolation>> {
+    let mut violations = Vec::new();
+
+    // New NOT NULL columns without a default cannot be filled on tables that have rows
+    for (table_name, columns) in &diff.columns_to_add {
+        for column in columns.iter().filter(|c| !c.nullable && c.default.is_none()) {
+            let query = format!("SELECT COUNT(*) FROM {}", table_name);
+            check(
+                connection,
+                &mut violations,
+                table_name,
+                vec![column.name.clone()],
+                "NOT NULL",
+                query,
+            )
+            .await?;
+        }
+    }
+
+    for (table_name, changes) in &diff.columns_to_alter {
+        for change in changes {
+            if change.from.nullable && !change.to.nullable {
+                let query = format!(
+                    "SELECT COUNT(*) FROM {} WHERE {} IS NULL",
+                    table_name, change.column_name
+                );
+                check(
+                    connection,
+                    &mut violations,
+                    table_name,
+                    vec![change.column_name.clone()],
+                    "NOT NULL",
+                    query,
+                )
+                .await?;
+            }
+
+            if !change.from.is_unique && change.to.is_unique {
+                let query = format!(
+                    "SELECT COUNT(*) FROM (SELECT {col} FROM {table} WHERE {col} IS NOT NULL GROUP BY {col} HAVING COUNT(*) > 1) duplicates",
+                    col = change.column_name,
+                    table = table_name
+                );
+                check(
+                    connection,
+                    &mut violations,
+                    table_name,
+                    vec![change.column_name.clone()],
+                    "UNIQUE",
+                    query,
+                )
+                .await?;
+            }
+        }
+    }
+
+    for (table_name, foreign_keys) in &diff.foreign_keys_to_create {
+        for fk in foreign_keys {
+            let query = orphan_query(table_name, fk);
+            check(
+                connection,
+                &mut violations,
+                table_name,
+                fk.columns.clone(),
+                "FOREIGN KEY",
+                query,
+            )
+            .await?;
+        }
+    }
+
+    Ok(violations)
+}
+
+/// Fail with a report listing every violation, if there are any
+pub fn ensure_no_violations(violations: &[DataViolation]) -> Result<()> {
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    let report: Vec<String> = violations.iter().map(|v| format!("  - {}", v)).collect();
+    let failure = MigrationFailure::new(
+        "data_violation",
+        format!(
+            "existing data violates {} constraint(s) the migration would add:\n{}",
+            violations.len(),
+            report.join("\n")
+        ),
+    );
+    Err(failure.table(&violations[0].table).into())
+}
+
+/// Count rows for a check query and record a violation if any are found
+async fn check(
+    connection: &DatabaseConnection,
+    violations: &mut Vec<DataViolation>,
+    table: &str,
+    columns: Vec<String>,
+    constraint: &str,
+    query: String,
+) -> Result<()> {
+    let count = connection.query_count(&query).await?;
+
+    if count > 0 {
+        violations.push(DataViolation {
+            table: table.to_string(),
+            columns,
+            constraint: constraint.to_string(),
+            count,
+            query,
+        });
+    }
+
+    Ok(())
+}
+
+/// Count child rows whose (non-null) key has no matching parent row
+fn orphan_query(table_name: &str, fk: &ForeignKey) -> String {
+    let join: Vec<String> = fk
+        .columns
+        .iter()
+        .zip(&fk.ref_columns)
+        .map(|(column, ref_column)| format!("child.{} = parent.{}", column, ref_column))
+        .collect();
+    let not_null: Vec<String> = fk
+        .columns
+        .iter()
+        .map(|column| format!("child.{} IS NOT NULL", column))
+        .collect();
+
+    format!(
+        "SELECT COUNT(*) FROM {} child LEFT JOIN {} parent ON {} WHERE {} AND parent.{} IS NULL",
+        table_name,
+        fk.ref_table,
+        join.join(" AND "),
+        not_null.join(" AND "),
+        fk.ref_columns.first().map(String::as_str).unwrap_or("id")
+    )
+}
@@ -0,0 +1,269 @@
+//! Schema diagnostics
+//!
+//! This module collects non-fatal findings about a schema (risky identifiers, naming
+//! inconsistencies, missing indexes, and features the target dialect cannot express)
+//! so they can be reported to the user instead of scattered through the logs.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::config::Config;
+use crate::schema::graph::DependencyGraph;
+use crate::schema::types::DatabaseSchema;
+use crate::utils::naming::{apply_naming_convention, is_sql_keyword};
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+}
+
+/// A single finding about the schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable identifier for the kind of finding, e.g. "reserved_keyword"
+    pub code: String,
+    pub message: String,
+    pub table: Option<String>,
+    pub column: Option<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}[{}]: {}", severity, self.code, self.message)
+    }
+}
+
+/// A collection of diagnostics
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    /// Create an empty collection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a diagnostic
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.items.push(diagnostic);
+    }
+
+    /// Add a warning about a table or column
+    pub fn warn(&mut self, code: &str, message: String, table: Option<&str>, column: Option<&str>) {
+        self.push(Diagnostic {
+            severity: Severity::Warning,
+            code: code.to_string(),
+            message,
+            table: table.map(str::to_string),
+            column: column.map(str::to_string),
+        });
+    }
+
+    /// Add an informational note about a table or column
+    pub fn info(&mut self, code: &str, message: String, table: Option<&str>, column: Option<&str>) {
+        self.push(Diagnostic {
+            severity: Severity::Info,
+            code: code.to_string(),
+            message,
+            table: table.map(str::to_string),
+            column: column.map(str::to_string),
+        });
+    }
+
+    /// Append all diagnostics from another collection
+    pub fn extend(&mut self, other: Diagnostics) {
+        self.items.extend(other.items);
+    }
+
+    /// Iterate over the diagnostics
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.items.iter()
+    }
+
+    /// Diagnostics with the given code
+    pub fn with_code<'a>(&'a self, code: &'a str) -> impl Iterator<Item = &'a Diagnostic> {
+        self.items.iter().filter(move |d| d.code == code)
+    }
+
+    /// Check whether any warnings were collected
+    pub fn has_warnings(&self) -> bool {
+        self.items.iter().any(|d| d.severity == Severity::Warning)
+    }
+
+    /// Number of diagnostics
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check whether the collection is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Check a schema (usually the one built from the models) for likely problems
+pub fn check_schema(schema: &DatabaseSchema, config: &Config) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+
+    let mut table_names: Vec<&String> = schema.tables.keys().collect();
+    table_names.sort();
+
+    for table_name in table_names {
+        let table = &schema.tables[table_name];
+
+        if is_sql_keyword(&table.name) {
+            diagnostics.warn(
+                "reserved_keyword",
+                format!("table '{}' is an SQL keyword and must be quoted in raw queries", table.name),
+                Some(&table.name),
+                None,
+            );
+        }
+
+        if apply_naming_convention(&table.name, &config.naming.table_style) != table.name
+            && !config.naming.is_overridden(&table.name)
+        {
+            diagnostics.warn(
+                "naming_style",
+                format!("table '{}' does not follow the {} table style", table.name, config.naming.table_style),
+                Some(&table.name),
+                None,
+            );
+        }
+
+        for column in &table.columns {
+            if is_sql_keyword(&column.name) {
+                diagnostics.warn(
+                    "reserved_keyword",
+                    format!(
+                        "column '{}.{}' is an SQL keyword and must be quoted in raw queries",
+                        table.name, column.name
+                    ),
+                    Some(&table.name),
+                    Some(&column.name),
+                );
+            }
+
+            if apply_naming_convention(&column.name, &config.naming.column_style) != column.name
+                && !config.naming.is_overridden(&column.name)
+            {
+                diagnostics.warn(
+                    "naming_style",
+                    format!(
+                        "column '{}.{}' does not follow the {} column style",
+                        table.name, column.name, config.naming.column_style
+                    ),
+                    Some(&table.name),
+                    Some(&column.name),
+                );
+            }
+        }
+
+        if !config.schema.index_foreign_keys {
+            for fk in &table.foreign_keys {
+                let indexed = table
+                    .indexes
+                    .iter()
+                    .any(|index| index.columns.starts_with(&fk.columns))
+                    || table
+                        .primary_key
+                        .as_ref()
+                        .is_some_and(|pk| pk.columns.starts_with(&fk.columns));
+
+                if !indexed {
+                    diagnostics.warn(
+                        "unindexed_foreign_key",
+                        format!(
+                            "foreign key {}({}) has no index; joins and cascading deletes will scan the table",
+                            table.name,
+                            fk.columns.join(", ")
+                        ),
+                        Some(&table.name),
+                        fk.columns.first().map(String::as_str),
+                    );
+                }
+            }
+        }
+
+        // SQLite cannot generate UUIDs, so a UUID key without a default is filled by the application
+        if config.database.driver == "sqlite" {
+            let pk_columns = table.primary_key.as_ref().map(|pk| pk.columns.as_slice()).unwrap_or_default();
+            for column in table.columns.iter().filter(|c| pk_columns.contains(&c.name)) {
+                if column.data_type.eq_ignore_ascii_case("UUID") && column.default.is_none() {
+                    diagnostics.info(
+                        "application_managed_uuid",
+                        format!(
+                            "'{}.{}' has no database default on sqlite; the application must generate its UUIDs",
+                            table.name, column.name
+                        ),
+                        Some(&table.name),
+                        Some(&column.name),
+                    );
+                }
+            }
+        }
+
+        // Time-ordered keys already sort rows by creation time
+        if let Some(auto_pk) = config
+            .schema
+            .auto_primary_key
+            .as_ref()
+            .filter(|pk| pk.key_type == "Ulid" || pk.key_type == "Snowflake")
+        {
+            let keyed_by_auto_pk = table
+                .primary_key
+                .as_ref()
+                .is_some_and(|pk| pk.columns == [auto_pk.name.clone()]);
+
+            if keyed_by_auto_pk {
+                for index in table.indexes.iter().filter(|i| !i.is_unique && i.columns == ["created_at"]) {
+                    diagnostics.info(
+                        "redundant_timestamp_index",
+                        format!(
+                            "index '{}' may be redundant: {} keys on '{}' are time-ordered, so ordering and range scans can use the primary key",
+                            index.name, auto_pk.key_type, table.name
+                        ),
+                        Some(&table.name),
+                        Some("created_at"),
+                    );
+                }
+            }
+        }
+
+        let has_timestamps = table
+            .columns
+            .iter()
+            .any(|c| c.name == "created_at" || c.name == "updated_at");
+        if !has_timestamps {
+            diagnostics.info(
+                "missing_timestamps",
+                format!("table '{}' has no created_at or updated_at column", table.name),
+                Some(&table.name),
+                None,
+            );
+        }
+    }
+
+    // Tables in a reference cycle are created first and their keys added afterwards,
+    // and rows in them cannot be inserted without a deferrable key or a nullable column
+    for cycle in DependencyGraph::new(schema).cycles() {
+        diagnostics.info(
+            "foreign_key_cycle",
+            format!("tables {} reference each other in a cycle", cycle.join(", ")),
+            cycle.first().map(String::as_str),
+            None,
+        );
+    }
+
+    diagnostics
+}
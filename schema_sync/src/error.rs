@@ -1,7 +1,10 @@
 //! Error types for SchemaSync
 
+use std::fmt;
 use thiserror::Error;
 
+use crate::schema::dialect::Capability;
+
 /// Result type for SchemaSync operations
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -18,7 +21,7 @@ pub enum Error {
     SchemaAnalysisError(String),
     
     #[error("Migration error: {0}")]
-    MigrationError(String),
+    Migration(Box<MigrationFailure>),
     
     #[error("Model registration error: {0}")]
     ModelRegistrationError(String),
@@ -41,10 +44,254 @@ pub enum Error {
     #[error("Syntax error: {0}")]
     SyntaxError(String),
     
+    #[error("Schema mismatch: {0}")]
+    SchemaMismatch(SchemaMismatch),
+    
+    #[error("Schema changed since plan: {0}")]
+    SchemaChangedSincePlan(SchemaChangedSincePlan),
+    
+    #[error("Unsupported operation: {0}")]
+    UnsupportedOperation(UnsupportedOperation),
+    
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
 
+/// Differences between the database and the registered models, reported by `assert_compatible`
+#[derive(Debug, Clone)]
+pub struct SchemaMismatch {
+    /// One line per difference, e.g. "missing column users.email"
+    pub differences: Vec<String>,
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "database differs from the models in {} way(s):\n  - {}",
+            self.differences.len(),
+            self.differences.join("\n  - ")
+        )
+    }
+}
+
+/// Tables altered between making a plan and applying it, reported by `apply_plan`
+#[derive(Debug, Clone)]
+pub struct SchemaChangedSincePlan {
+    /// Tables whose schema no longer matches the fingerprint recorded in the plan
+    pub tables: Vec<String>,
+}
+
+impl fmt::Display for SchemaChangedSincePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} changed after the plan was made; generate a new plan",
+            self.tables.join(", ")
+        )
+    }
+}
+
+/// A change the target database cannot make, named by the capability it lacks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedOperation {
+    /// The `database.driver` of the target database
+    pub dialect: String,
+    pub capability: Capability,
+    pub table: Option<String>,
+    pub column: Option<String>,
+    /// What to do instead, e.g. recreate the table
+    pub hint: Option<String>,
+}
+
+impl UnsupportedOperation {
+    /// Whether the change can only be made by recreating the table
+    pub fn needs_rebuild(&self) -> bool {
+        matches!(
+            self.capability,
+            Capability::DropColumn
+                | Capability::AlterColumn
+                | Capability::AddForeignKey
+                | Capability::DropForeignKey
+                | Capability::AlterCheckConstraints
+        )
+    }
+}
+
+impl fmt::Display for UnsupportedOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} lacks the {} capability", self.dialect, self.capability)?;
+        match (&self.table, &self.column) {
+            (Some(table), Some(column)) => write!(f, " needed for {}.{}", table, column)?,
+            (Some(table), None) => write!(f, " needed for {}", table)?,
+            _ => {}
+        }
+        if let Some(hint) = &self.hint {
+            write!(f, "; {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
+/// A failed migration step, with a machine-readable code and the objects involved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationFailure {
+    /// Stable identifier such as "lock_timeout", "table_rebuild_required", or "unsupported_driver"
+    pub code: String,
+    pub message: String,
+    pub table: Option<String>,
+    pub column: Option<String>,
+    /// The SQL that failed, when the database rejected a statement
+    pub statement: Option<String>,
+    /// The database's own error code (SQLSTATE, or the SQLite result code)
+    pub database_code: Option<String>,
+}
+
+impl MigrationFailure {
+    /// Create a failure with the given code and message
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.into(),
+            table: None,
+            column: None,
+            statement: None,
+            database_code: None,
+        }
+    }
+    
+    /// Set the table the failure concerns
+    pub fn table(mut self, table: &str) -> Self {
+        self.table = Some(table.to_string());
+        self
+    }
+    
+    /// Set the column the failure concerns
+    pub fn column(mut self, column: &str) -> Self {
+        self.column = Some(column.to_string());
+        self
+    }
+    
+    /// Set the SQL that failed
+    pub fn statement(mut self, statement: &str) -> Self {
+        self.statement = Some(statement.to_string());
+        self
+    }
+}
+
+impl fmt::Display for MigrationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<MigrationFailure> for Error {
+    fn from(failure: MigrationFailure) -> Self {
+        Error::Migration(Box::new(failure))
+    }
+}
+
+impl Error {
+    /// Stable, machine-readable identifier for the kind of error
+    pub fn code(&self) -> &str {
+        match self {
+            Error::ConfigError(_) => "config",
+            Error::DatabaseError(_) | Error::SqlxError(_) => "database",
+            Error::SchemaAnalysisError(_) => "schema_analysis",
+            Error::Migration(failure) => &failure.code,
+            Error::ModelRegistrationError(_) => "model_registration",
+            Error::TypeMappingError(_) => "type_mapping",
+            Error::IoError(_) => "io",
+            Error::SerializationError(_) => "serialization",
+            Error::ValidationError(_) => "validation",
+            Error::SyntaxError(_) => "syntax",
+            Error::SchemaMismatch(_) => "schema_mismatch",
+            Error::SchemaChangedSincePlan(_) => "schema_changed_since_plan",
+            Error::UnsupportedOperation(_) => "unsupported_operation",
+            Error::Unknown(_) => "unknown",
+        }
+    }
+    
+    /// Whether the same operation may succeed if tried again later
+    ///
+    /// True for lock timeouts, deadlocks, serialization failures, and lost connections.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Migration(failure) => RETRYABLE_CODES.contains(&failure.code.as_str()),
+            Error::SqlxError(e) => matches!(classify_sqlx_error(e), Some(code) if RETRYABLE_CODES.contains(&code)),
+            _ => false,
+        }
+    }
+    
+    /// Whether a migration was refused because it would lose data or break existing rows
+    ///
+    /// True for rows that violate a constraint being added and for changes that need
+    /// the table rebuilt. Resolve these by fixing the data or migrating by hand.
+    pub fn is_destructive_block(&self) -> bool {
+        match self {
+            Error::Migration(failure) => matches!(failure.code.as_str(), "data_violation" | "table_rebuild_required"),
+            Error::UnsupportedOperation(operation) => operation.needs_rebuild(),
+            _ => false,
+        }
+    }
+    
+    /// Attach the statement that produced a database error
+    ///
+    /// Database errors become `Error::Migration` failures coded by their cause
+    /// ("lock_timeout", "deadlock", "serialization_failure", "connection_lost", or
+    /// "statement_failed"); other errors are returned unchanged.
+    pub fn in_statement(self, statement: &str) -> Self {
+        let e = match &self {
+            Error::SqlxError(e) => e,
+            _ => return self,
+        };
+        
+        let mut failure = MigrationFailure::new(
+            classify_sqlx_error(e).unwrap_or("statement_failed"),
+            e.to_string(),
+        )
+        .statement(statement);
+        if let sqlx::Error::Database(db_error) = e {
+            failure.database_code = db_error.code().map(|code| code.to_string());
+            failure.table = db_error.table().map(str::to_string);
+        }
+        
+        failure.into()
+    }
+}
+
+/// Failure codes worth retrying
+const RETRYABLE_CODES: &[&str] = &["lock_timeout", "deadlock", "serialization_failure", "connection_lost"];
+
+/// Failure code for a database error with a recognizable cause
+pub(crate) fn classify_sqlx_error(error: &sqlx::Error) -> Option<&'static str> {
+    let db_error = match error {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Tls(_) => {
+            return Some("connection_lost")
+        }
+        sqlx::Error::Database(db_error) => db_error,
+        _ => return None,
+    };
+    
+    // MySQL reports most of these with the generic HY000 SQLSTATE, so check the error number
+    #[cfg(feature = "mysql")]
+    if let Some(mysql_error) = db_error.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+        return match mysql_error.number() {
+            1205 => Some("lock_timeout"),
+            1213 => Some("deadlock"),
+            _ => None,
+        };
+    }
+    
+    // 55P03 = Postgres lock_not_available, 5/6 = SQLITE_BUSY/SQLITE_LOCKED
+    match db_error.code().as_deref() {
+        Some("55P03") | Some("5") | Some("6") => Some("lock_timeout"),
+        Some("40P01") => Some("deadlock"),
+        Some("40001") => Some("serialization_failure"),
+        _ => None,
+    }
+}
+
 // The #[from] attribute on SqlxError already implements this conversion
 // so we don't need a separate implementation
 
@@ -0,0 +1,32 @@
+//! Event callbacks
+//!
+//! This module lets applications embedding SchemaSync observe each step of a sync,
+//! and stop it before anything is applied, by setting an [`EventHandler`] on the client.
+//! Approval flows, notifications, and audit logging can be built on it without
+//! reimplementing the workflow.
+
+use async_trait::async_trait;
+
+use crate::error::{Error, Result};
+use crate::models::registry::ModelInfo;
+use crate::schema::diff::SchemaDiff;
+
+/// Callbacks invoked by `SchemaSyncClient`; every method defaults to doing nothing
+#[async_trait]
+pub trait EventHandler: Send + Sync {
+    /// Called for each model found by `register_models`, in name order
+    async fn on_model_registered(&self, _model: &ModelInfo) {}
+
+    /// Called with each diff between the models and the database
+    ///
+    /// Returning an error stops the sync before any migration is generated or applied.
+    async fn on_diff_computed(&self, _diff: &SchemaDiff) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called after each migration is applied and recorded in the history table
+    async fn on_statement_applied(&self, _migration_id: &str, _sql: &str) {}
+
+    /// Called when a client operation fails, before the error is returned
+    async fn on_error(&self, _error: &Error) {}
+}
@@ -0,0 +1,156 @@
+//! Prometheus drift exporter
+//!
+//! This module runs drift detection on an interval and serves the result as Prometheus
+//! gauges on `/metrics`, for environments where changes are not applied automatically
+//! and drift should raise an alert instead. Nothing is ever applied.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::schema::diff::SchemaDiff;
+use crate::SchemaSyncClient;
+
+/// Result of the latest drift check
+#[derive(Debug, Clone, Default)]
+pub struct DriftMetrics {
+    /// Differences per drifted table
+    pub tables: BTreeMap<String, usize>,
+    /// Columns missing, unexpected, or changed
+    pub columns_drifted: usize,
+    /// When the last check finished, successfully or not
+    pub last_check: Option<DateTime<Utc>>,
+    pub last_check_success: bool,
+    pub checks_total: u64,
+    pub check_errors_total: u64,
+}
+
+impl DriftMetrics {
+    /// Record the outcome of a drift check
+    ///
+    /// A failed check keeps the drift figures of the last successful one.
+    pub fn record(&mut self, result: &Result<SchemaDiff>) {
+        self.checks_total += 1;
+        self.last_check = Some(Utc::now());
+        self.last_check_success = result.is_ok();
+
+        match result {
+            Ok(diff) => {
+                self.tables.clear();
+                for (table, _) in diff.table_differences() {
+                    *self.tables.entry(table).or_default() += 1;
+                }
+                self.columns_drifted = diff.columns_to_add.values().map(Vec::len).sum::<usize>()
+                    + diff.columns_to_drop.values().map(Vec::len).sum::<usize>()
+                    + diff.columns_to_alter.values().map(Vec::len).sum::<usize>();
+            }
+            Err(e) => {
+                self.check_errors_total += 1;
+                tracing::error!(error = %e, "Drift check failed");
+            }
+        }
+    }
+
+    /// The metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut gauge = |name: &str, help: &str, value: String| {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} gauge\n{} {}", name, help, name, name, value);
+        };
+
+        gauge("schema_sync_tables_drifted", "Tables that differ from the models", self.tables.len().to_string());
+        gauge("schema_sync_columns_drifted", "Columns missing, unexpected, or changed", self.columns_drifted.to_string());
+        gauge(
+            "schema_sync_last_check_timestamp_seconds",
+            "Unix time of the last drift check",
+            self.last_check.map_or(0, |at| at.timestamp()).to_string(),
+        );
+        gauge(
+            "schema_sync_last_check_success",
+            "Whether the last drift check succeeded",
+            (self.last_check_success as u8).to_string(),
+        );
+
+        let _ = writeln!(out, "# HELP schema_sync_table_differences Differences per drifted table");
+        let _ = writeln!(out, "# TYPE schema_sync_table_differences gauge");
+        for (table, count) in &self.tables {
+            let _ = writeln!(out, "schema_sync_table_differences{{table=\"{}\"}} {}", escape_label(table), count);
+        }
+
+        for (name, help, value) in [
+            ("schema_sync_checks_total", "Drift checks run", self.checks_total),
+            ("schema_sync_check_errors_total", "Drift checks that failed", self.check_errors_total),
+        ] {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter\n{} {}", name, help, name, name, value);
+        }
+
+        out
+    }
+}
+
+/// Escape a Prometheus label value
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Check for drift every `interval` and serve the metrics on `listen` (e.g. "0.0.0.0:9187")
+///
+/// Runs until the listener fails. The models are registered once, at startup.
+pub async fn run(config: Config, listen: &str, interval: Duration) -> Result<()> {
+    let mut client = SchemaSyncClient::new(config).await?;
+    client.register_models().await?;
+
+    let metrics = Arc::new(RwLock::new(DriftMetrics::default()));
+    let listener = TcpListener::bind(listen).await?;
+    tracing::info!(address = %listen, "Serving drift metrics");
+
+    let checked = metrics.clone();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let result = client.generate_schema_diff().await;
+            checked.write().await.record(&result);
+        }
+    });
+
+    serve_metrics(listener, metrics).await
+}
+
+/// Answer `GET /metrics` with the current metrics, and anything else with 404
+pub async fn serve_metrics(listener: TcpListener, metrics: Arc<RwLock<DriftMetrics>>) -> Result<()> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let metrics = metrics.clone();
+
+        tokio::spawn(async move {
+            let mut request = [0u8; 1024];
+            let Ok(read) = stream.read(&mut request).await else {
+                return;
+            };
+            let request_line = String::from_utf8_lossy(&request[..read]);
+            let path = request_line.split_whitespace().nth(1).unwrap_or_default().to_string();
+
+            let (status, body) = if path == "/metrics" {
+                ("200 OK", metrics.read().await.render())
+            } else {
+                ("404 Not Found", "Not found\n".to_string())
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
@@ -0,0 +1,361 @@
+//! Web framework startup integration
+//!
+//! This module runs the schema sync (or a compatibility check) while a service starts,
+//! before its HTTP server binds, e.g. from an Axum or Actix `main`. When several replicas
+//! start together only one of them syncs; the others wait for it. The outcome is kept as a
+//! `SchemaStatus` that a health endpoint can serve. With `[[targets]]` configured,
+//! `run_targets_on_startup` does the same for each target and reports them together.
+//!
+//! ```no_run
+//! # async fn start() -> schema_sync::Result<()> {
+//! let config = schema_sync::config::load_from_file("schema_sync.toml")?;
+//! let status = schema_sync::integrations::run_on_startup(config).await?;
+//!
+//! // e.g. with Axum:
+//! // .route("/health/schema", get(move || async move {
+//! //     (StatusCode::from_u16(status.http_status()).unwrap(), Json(status.to_json()))
+//! // }))
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::config::{Config, StartupConfig};
+use crate::db::meta;
+use crate::error::{Error, Result};
+use crate::SchemaSyncClient;
+
+/// How often a waiting instance checks whether the sync lock was released
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Outcome of the startup sync or check, for health endpoints
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaStatus {
+    /// "synced" (changes were applied), "in_sync" (nothing to do), "incompatible" (the
+    /// database does not match the models), or "error"
+    pub state: String,
+    /// What differs when the state is "incompatible"
+    pub differences: Vec<String>,
+    /// The error message when the state is "error"
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl SchemaStatus {
+    fn new(state: &str) -> Self {
+        Self {
+            state: state.to_string(),
+            differences: Vec::new(),
+            error: None,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Status of a service that started despite a failed sync or check
+    pub fn from_error(error: &Error) -> Self {
+        match error {
+            Error::SchemaMismatch(mismatch) => Self {
+                differences: mismatch.differences.clone(),
+                ..Self::new("incompatible")
+            },
+            _ => Self {
+                error: Some(error.to_string()),
+                ..Self::new("error")
+            },
+        }
+    }
+
+    /// Whether the database matches the models
+    pub fn is_healthy(&self) -> bool {
+        matches!(self.state.as_str(), "synced" | "in_sync")
+    }
+
+    /// HTTP status for a health endpoint: 200 when healthy, 503 otherwise
+    pub fn http_status(&self) -> u16 {
+        if self.is_healthy() {
+            200
+        } else {
+            503
+        }
+    }
+
+    /// JSON body for a health endpoint
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+/// Sync or check the schema before the service starts serving
+///
+/// Behaves as configured by `[startup]`: "sync" mode applies pending changes while holding
+/// a lock in the `schema_sync_meta` table, "check" mode runs `assert_compatible`. With
+/// `on_failure = "degrade"` a failure is logged and returned as an unhealthy status rather
+/// than an error, so the service can start and report it on its health endpoint.
+pub async fn run_on_startup(config: Config) -> Result<SchemaStatus> {
+    let startup = config.startup.clone().unwrap_or_default();
+    let mode = startup.mode.as_deref().unwrap_or("sync");
+    if !matches!(mode, "sync" | "check") {
+        return Err(Error::ConfigError(format!(
+            "unknown startup mode '{}', expected \"sync\" or \"check\"",
+            mode
+        )));
+    }
+    let on_failure = startup.on_failure.as_deref().unwrap_or("fail");
+    if !matches!(on_failure, "fail" | "degrade") {
+        return Err(Error::ConfigError(format!(
+            "unknown startup on_failure '{}', expected \"fail\" or \"degrade\"",
+            on_failure
+        )));
+    }
+
+    let result = match mode {
+        "check" => check(config).await,
+        _ => sync(config, &startup).await,
+    };
+
+    match result {
+        Err(e) if on_failure == "degrade" => {
+            tracing::error!(error = %e, "Schema startup step failed, starting degraded");
+            Ok(SchemaStatus::from_error(&e))
+        }
+        result => result,
+    }
+}
+
+/// Status of every `[[targets]]` entry after `run_targets_on_startup`
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetsStatus {
+    /// Each target's own status, in the order they are configured
+    pub targets: Vec<TargetStatus>,
+}
+
+/// Status of one target
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetStatus {
+    pub name: String,
+    #[serde(flatten)]
+    pub status: SchemaStatus,
+}
+
+impl TargetsStatus {
+    /// Whether every target matches the models
+    pub fn is_healthy(&self) -> bool {
+        self.targets.iter().all(|target| target.status.is_healthy())
+    }
+
+    /// Names of the targets that are not healthy
+    pub fn unhealthy(&self) -> Vec<&str> {
+        self.targets
+            .iter()
+            .filter(|target| !target.status.is_healthy())
+            .map(|target| target.name.as_str())
+            .collect()
+    }
+
+    /// HTTP status for a health endpoint: 200 when every target is healthy, 503 otherwise
+    pub fn http_status(&self) -> u16 {
+        if self.is_healthy() {
+            200
+        } else {
+            503
+        }
+    }
+
+    /// JSON body for a health endpoint
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+/// Sync or check every `[[targets]]` entry, one after another
+///
+/// Each target has its own connection, history table, and lock, so a target that fails
+/// is reported in its status without stopping the others or touching their history.
+/// Only configuration errors are returned as errors; `on_failure` does not apply.
+pub async fn run_targets_on_startup(config: Config) -> Result<TargetsStatus> {
+    let targets = config.target_configs()?;
+    if targets.is_empty() {
+        return Err(Error::ConfigError("no [[targets]] are configured".to_string()));
+    }
+
+    let mut statuses = Vec::with_capacity(targets.len());
+    for (name, mut config) in targets {
+        let startup = config.startup.get_or_insert_with(Default::default);
+        startup.on_failure = Some("degrade".to_string());
+        tracing::info!(target_name = %name, "Syncing target");
+
+        let status = run_on_startup(config).await?;
+        if !status.is_healthy() {
+            tracing::error!(target_name = %name, error = ?status.error, "Target failed");
+        }
+        statuses.push(TargetStatus { name, status });
+    }
+
+    Ok(TargetsStatus { targets: statuses })
+}
+
+/// Assert that the database matches the models
+async fn check(config: Config) -> Result<SchemaStatus> {
+    let mut client = SchemaSyncClient::new(config).await?;
+    client.register_models().await?;
+    client.assert_compatible().await?;
+
+    Ok(SchemaStatus::new("in_sync"))
+}
+
+/// Sync the database unless the last sync already matches the models
+async fn sync(config: Config, startup: &StartupConfig) -> Result<SchemaStatus> {
+    let mut client = SchemaSyncClient::new(config).await?;
+    client.register_models().await?;
+    if client.is_up_to_date().await? {
+        return Ok(SchemaStatus::new("in_sync"));
+    }
+
+    let timeout = Duration::from_secs(startup.lock_timeout_secs.unwrap_or(60));
+    let stale_after = Duration::from_secs(startup.lock_stale_secs.unwrap_or(600));
+    let token = acquire_sync_lock(&client, timeout, stale_after).await?;
+    // Another instance may have synced while this one waited
+    let result = match client.is_up_to_date().await {
+        Ok(true) => Ok(SchemaStatus::new("in_sync")),
+        Ok(false) => client.sync_database().await.map(|()| SchemaStatus::new("synced")),
+        Err(e) => Err(e),
+    };
+    meta::unlock(&client.db_connection, meta::SYNC_LOCK_KEY, &token).await?;
+
+    result
+}
+
+/// Wait for the sync lock, giving up after `timeout`
+async fn acquire_sync_lock(client: &SchemaSyncClient, timeout: Duration, stale_after: Duration) -> Result<String> {
+    let started = Instant::now();
+
+    loop {
+        if let Some(token) = meta::try_lock(&client.db_connection, meta::SYNC_LOCK_KEY, stale_after).await? {
+            return Ok(token);
+        }
+        if started.elapsed() >= timeout {
+            return Err(Error::DatabaseError(format!(
+                "timed out after {}s waiting for another instance's schema sync",
+                timeout.as_secs()
+            )));
+        }
+        tracing::info!("Waiting for another instance's schema sync to finish");
+        tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+    }
+}
+
+/// Options for `run_job`
+#[derive(Debug, Clone)]
+pub struct JobOptions {
+    /// Budget for the whole job, including waiting for the lock
+    pub timeout: Duration,
+    /// Exit straight away (successfully) when another job holds the lock, instead of waiting
+    pub skip_if_locked: bool,
+}
+
+impl Default for JobOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(600),
+            skip_if_locked: false,
+        }
+    }
+}
+
+/// Machine-readable outcome of `run_job`
+#[derive(Debug, Clone, Serialize)]
+pub struct JobResult {
+    /// "applied", "up_to_date", "skipped" (another job held the lock), "failed", or "timed_out"
+    pub status: String,
+    /// Ids of the migrations that were applied
+    pub applied: Vec<String>,
+    pub error: Option<String>,
+    pub duration_ms: u128,
+}
+
+impl JobResult {
+    /// Process exit code: 0 on success (including "skipped"), 1 on failure, 2 on timeout
+    pub fn exit_code(&self) -> i32 {
+        match self.status.as_str() {
+            "applied" | "up_to_date" | "skipped" => 0,
+            "timed_out" => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// Sync the schema as a one-shot job, e.g. a Kubernetes Job or init container
+///
+/// The sync lock is a lease as long as the timeout budget: a job that runs out of budget
+/// is abandoned without releasing it, and the next job takes it over once the lease has
+/// expired. Errors are reported in the result rather than returned.
+pub async fn run_job(config: Config, options: &JobOptions) -> JobResult {
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(options.timeout, job(config, options)).await;
+
+    let (status, applied, error) = match outcome {
+        Ok(Ok((status, applied))) => (status.to_string(), applied, None),
+        Ok(Err(e)) => ("failed".to_string(), Vec::new(), Some(e.to_string())),
+        Err(_) => (
+            "timed_out".to_string(),
+            Vec::new(),
+            Some(format!("the job did not finish within {}s", options.timeout.as_secs())),
+        ),
+    };
+
+    JobResult {
+        status,
+        applied,
+        error,
+        duration_ms: started.elapsed().as_millis(),
+    }
+}
+
+/// The job itself: its status and the applied migration ids
+async fn job(config: Config, options: &JobOptions) -> Result<(&'static str, Vec<String>)> {
+    let mut client = SchemaSyncClient::new(config).await?;
+    client.register_models().await?;
+    if client.is_up_to_date().await? {
+        return Ok(("up_to_date", Vec::new()));
+    }
+
+    let connection = client.db_connection.clone();
+    let token = match meta::try_lock(&connection, meta::SYNC_LOCK_KEY, options.timeout).await? {
+        Some(token) => token,
+        None if options.skip_if_locked => return Ok(("skipped", Vec::new())),
+        // The outer timeout ends the wait
+        None => acquire_sync_lock(&client, Duration::MAX, options.timeout).await?,
+    };
+
+    let result = apply_pending(&mut client).await;
+    meta::unlock(&connection, meta::SYNC_LOCK_KEY, &token).await?;
+
+    result
+}
+
+/// Apply the pending diff and record the model fingerprint, as `sync_database` does
+async fn apply_pending(client: &mut SchemaSyncClient) -> Result<(&'static str, Vec<String>)> {
+    // Another job may have synced while this one waited
+    if client.is_up_to_date().await? {
+        return Ok(("up_to_date", Vec::new()));
+    }
+
+    let diff = client.generate_schema_diff().await?;
+    let applied = if diff.is_empty() {
+        Vec::new()
+    } else {
+        client.apply_diff(&diff).await?.into_result()?.migration_ids()
+    };
+    if client.config.migrations.dry_run {
+        return Ok(("up_to_date", applied));
+    }
+
+    let fingerprint = client.model_registry.to_database_schema(&client.config)?.fingerprint();
+    meta::write_value(&client.db_connection, meta::FINGERPRINT_KEY, &fingerprint).await?;
+
+    Ok((if applied.is_empty() { "up_to_date" } else { "applied" }, applied))
+}
@@ -3,18 +3,33 @@
 //! SchemaSync allows you to define your database schema using Rust structs and automatically
 //! generates and applies migrations to keep your database in sync with your code.
 
+#[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
+compile_error!("schema_sync needs at least one database driver feature: postgres, mysql, or sqlite");
+
+pub mod annotations;
+pub mod build;
 pub mod config;
 pub mod db;
+pub mod diagnostics;
 pub mod error;
+pub mod events;
+pub mod exporter;
+pub mod integrations;
 pub mod models;
 pub mod schema;
 pub mod utils;
+pub mod webhooks;
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
 
 // Re-export main types for easier access
 pub use config::Config;
 pub use db::connection::DatabaseConnection;
-pub use error::{Error, Result};
-pub use schema_sync_macros::{schema_sync, SchemaSync};
+pub use db::migrations::{ApplyReport, EmbeddedMigrations};
+pub use error::{Error, MigrationFailure, Result, SchemaChangedSincePlan, SchemaMismatch, UnsupportedOperation};
+pub use events::EventHandler;
+pub use schema_sync_macros::{embed_migrations, schema_sync, SchemaSync};
 pub use models::registry::ModelRegistry;
 pub use schema::analyzer::SchemaAnalyzer;
 pub use schema::diff::SchemaDiff;
@@ -32,26 +47,76 @@ pub struct SchemaSyncClient {
     db_connection: DatabaseConnection,
     model_registry: ModelRegistry,
     schema_analyzer: SchemaAnalyzer,
+    event_handler: Option<Arc<dyn EventHandler>>,
 }
 
 impl SchemaSyncClient {
     /// Create a new SchemaSync client from configuration
+    ///
+    /// When `analysis_database` is configured, schema analysis runs over that
+    /// (read-only by default) connection and only migrations use `database`.
     pub async fn new(config: Config) -> Result<Self> {
+        if config.migrations.verify_after_apply == Some(true) && !SchemaAnalyzer::supports_driver(&config.database.driver) {
+            return Err(Error::ConfigError(format!(
+                "migrations.verify_after_apply needs a database whose schema can be analyzed, which {} is not",
+                config.database.driver
+            )));
+        }
+        
         let db_connection = DatabaseConnection::connect(&config.database).await?;
         let model_registry = ModelRegistry::new(&config.models);
-        let schema_analyzer = SchemaAnalyzer::new(db_connection.clone());
+        
+        let analysis_connection = match &config.analysis_database {
+            Some(analysis_config) => {
+                if analysis_config.driver != config.database.driver {
+                    return Err(Error::ConfigError(format!(
+                        "analysis_database driver '{}' must match database driver '{}'",
+                        analysis_config.driver, config.database.driver
+                    )));
+                }
+                
+                let mut analysis_config = analysis_config.clone();
+                analysis_config.read_only.get_or_insert(true);
+                DatabaseConnection::connect(&analysis_config).await?
+            }
+            None => db_connection.clone(),
+        };
+        let schema_analyzer = SchemaAnalyzer::new(analysis_connection);
 
         Ok(Self {
             config,
             db_connection,
             model_registry,
             schema_analyzer,
+            event_handler: None,
         })
     }
 
+    /// Receive callbacks for registered models, computed diffs, applied migrations, and errors
+    pub fn set_event_handler(&mut self, handler: impl EventHandler + 'static) {
+        self.event_handler = Some(Arc::new(handler));
+    }
+
+    /// Pass an error to the event handler before returning it
+    async fn report<T>(&self, result: Result<T>) -> Result<T> {
+        if let (Err(e), Some(handler)) = (&result, &self.event_handler) {
+            handler.on_error(e).await;
+        }
+        result
+    }
+
     /// Scan directories for model definitions and register them
     pub async fn register_models(&mut self) -> Result<()> {
-        self.model_registry.scan_and_register(&self.config)?;
+        let result = self.model_registry.scan_and_register(&self.config);
+        self.report(result).await?;
+
+        if let Some(handler) = &self.event_handler {
+            let mut models: Vec<_> = self.model_registry.get_models().values().collect();
+            models.sort_by(|a, b| a.name.cmp(&b.name));
+            for model in models {
+                handler.on_model_registered(model).await;
+            }
+        }
         Ok(())
     }
 
@@ -60,12 +125,99 @@ impl SchemaSyncClient {
         self.schema_analyzer.analyze().await
     }
 
+    /// Access the schema analyzer, e.g. to check a single table with `analyze_table`
+    pub fn schema_analyzer(&self) -> &SchemaAnalyzer {
+        &self.schema_analyzer
+    }
+
+    /// Check the registered models for likely problems
+    ///
+    /// Reports SQL-keyword identifiers, names that break the configured naming styles,
+    /// foreign keys without an index (when `index_foreign_keys` is off), and tables
+    /// without timestamp columns.
+    pub fn diagnostics(&self) -> Result<diagnostics::Diagnostics> {
+        let model_schema = self.model_registry.to_database_schema(&self.config)?;
+        Ok(diagnostics::check_schema(&model_schema, &self.config))
+    }
+
+    /// Suggest indexes to add or drop
+    ///
+    /// Checks the registered models for unindexed foreign keys and `query_patterns`, then
+    /// adds suggestions from the live database's usage statistics where available.
+    /// Nothing is applied.
+    pub async fn index_advice(&self) -> Result<Vec<schema::advisor::IndexSuggestion>> {
+        let model_schema = self.model_registry.to_database_schema(&self.config)?;
+        let patterns = self.config.schema.query_patterns.clone().unwrap_or_default();
+        let mut suggestions = schema::advisor::advise(&model_schema, &patterns);
+
+        let db_schema = self.schema_analyzer.analyze().await?;
+        for suggestion in schema::advisor::advise_from_statistics(&self.db_connection, &db_schema).await? {
+            let duplicate = suggestions.iter().any(|s| {
+                s.kind == suggestion.kind && s.table == suggestion.table && s.columns == suggestion.columns
+            });
+            if !duplicate {
+                suggestions.push(suggestion);
+            }
+        }
+
+        Ok(suggestions)
+    }
+
     /// Generate a schema diff between registered models and database
+    ///
+    /// The event handler's `on_diff_computed` can reject the diff, which fails this call.
     pub async fn generate_schema_diff(&self) -> Result<SchemaDiff> {
+        let result = self.compute_schema_diff().await;
+        self.report(result).await
+    }
+
+    async fn compute_schema_diff(&self) -> Result<SchemaDiff> {
         let db_schema = self.schema_analyzer.analyze().await?;
-        let model_schema = self.model_registry.to_database_schema(&self.config)?;
+        let model_schema = self.model_schema_for(&db_schema)?;
+        let diff = SchemaDiff::generate(db_schema, model_schema, &self.config.schema);
         
-        Ok(SchemaDiff::generate(db_schema, model_schema, &self.config.schema))
+        if let Some(handler) = &self.event_handler {
+            handler.on_diff_computed(&diff).await?;
+        }
+        Ok(diff)
+    }
+
+    /// Schema of the registered models, with names spelled the way `current` stores them
+    /// when `naming.ignore_case_conflicts` is set
+    fn model_schema_for(&self, current: &schema::types::DatabaseSchema) -> Result<schema::types::DatabaseSchema> {
+        let mut model_schema = self.model_registry.to_database_schema(&self.config)?;
+        if let (true, Some(dialect)) = (self.config.naming.ignore_case_conflicts, self.dialect()) {
+            model_schema.align_identifiers(current, dialect);
+        }
+        Ok(model_schema)
+    }
+
+    fn dialect(&self) -> Option<schema::dialect::Dialect> {
+        schema::dialect::Dialect::from_driver(&self.config.database.driver)
+    }
+
+    /// Group a diff's differences by table, with each table's row estimate and size
+    ///
+    /// The figures come from the database's statistics, not from counting rows.
+    pub async fn drift_report(&self, diff: &SchemaDiff) -> Result<schema::DriftReport> {
+        let stats = self.schema_analyzer.table_stats().await?;
+        Ok(schema::DriftReport::new(diff, &stats))
+    }
+
+    /// Check live data against the constraints a diff would add
+    ///
+    /// Returns a "data_violation" `Error::Migration` listing every violation (with the query
+    /// that finds the offending rows) so the problem can be fixed before any DDL runs.
+    pub async fn validate_data(&self, diff: &SchemaDiff) -> Result<()> {
+        if self.config.schema.validate_data == Some(false) {
+            return Ok(());
+        }
+
+        let result = match db::validation::validate_diff(&self.db_connection, diff).await {
+            Ok(violations) => db::validation::ensure_no_violations(&violations),
+            Err(e) => Err(e),
+        };
+        self.report(result).await
     }
 
     /// Generate migration SQL from schema diff
@@ -74,24 +226,515 @@ impl SchemaSyncClient {
         generator.generate_migration_sql(diff).await
     }
 
+    /// Estimate the lock, rewrite, and table size of each generated statement (Postgres)
+    ///
+    /// Nothing is executed. Other drivers return no estimates.
+    pub async fn estimate_impact(&self, migrations: &[String]) -> Result<Vec<schema::impact::StatementImpact>> {
+        schema::impact::estimate(&self.db_connection, migrations).await
+    }
+
+    /// The plan for `diff`: its changes and generated migrations, in the versioned JSON
+    /// plan format read by external tools
+    ///
+    /// Unless `migrations.plan_fingerprints = false`, the plan records a fingerprint of each
+    /// table it touches, checked again by `apply_plan`. That reads the tables from the
+    /// database; drivers whose schema can't be analyzed get a plan without fingerprints.
+    pub async fn plan(&self, diff: &SchemaDiff) -> Result<schema::Plan> {
+        let migrations = self.generate_migrations(diff).await?;
+        let driver = &self.config.database.driver;
+        let mut plan = schema::Plan::new(diff, &migrations, driver);
+        
+        if self.config.migrations.plan_fingerprints != Some(false) {
+            if SchemaAnalyzer::supports_driver(driver) {
+                let analyzed = self.analyze_named_tables(plan.tables().iter()).await?;
+                plan.schema_fingerprints = Some(analyzed.table_fingerprints());
+            } else {
+                tracing::warn!(driver = %driver, "Tables can't be analyzed on this driver; the plan records no fingerprints");
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Write an HTML report of the database schema next to the models' schema
+    ///
+    /// The report goes to `schema_diff.html` in `output.output_directory` and shows each
+    /// changed table side by side with the migration SQL. Returns the report's path.
+    pub async fn write_html_report(&self) -> Result<std::path::PathBuf> {
+        let output = self.config.output.as_ref().ok_or_else(|| {
+            Error::ConfigError("an [output] section with output_directory is required for the HTML report".to_string())
+        })?;
+
+        let current = self.schema_analyzer.analyze().await?;
+        let desired = self.model_schema_for(&current)?;
+        let diff = SchemaDiff::generate(current.clone(), desired.clone(), &self.config.schema);
+        let migrations = self.generate_migrations(&diff).await?;
+
+        let html = schema::report::html_report(&current, &desired, &diff, &migrations);
+        schema::report::write_html_report(&output.output_directory, &html)
+    }
+
     /// Apply migrations to database
-    pub async fn apply_migrations(&self, migrations: Vec<String>) -> Result<()> {
+    ///
+    /// A statement the database rejects stops the run and is reported in `ApplyReport::failed`,
+    /// alongside the statements applied before it; use `ApplyReport::into_result` to treat it
+    /// as an error. Errors are returned for failures outside the statements themselves.
+    ///
+    /// With `migrations.verify_after_apply`, the tables the statements create, alter, or drop
+    /// are read back afterwards and compared with the models.
+    pub async fn apply_migrations(&self, migrations: Vec<String>) -> Result<ApplyReport> {
+        let result = match self.run_migrations(migrations).await {
+            Ok(report) => {
+                let touched = report
+                    .applied
+                    .iter()
+                    .flat_map(|migration| schema::impact::split_statements(&migration.sql))
+                    .filter_map(|statement| schema::impact::classify(&statement).table)
+                    .collect();
+                self.verify_report(report, &touched).await
+            }
+            Err(e) => Err(e),
+        };
+        self.report(result).await
+    }
+
+    async fn run_migrations(&self, migrations: Vec<String>) -> Result<ApplyReport> {
         if self.config.migrations.dry_run {
             // Just log the migrations without applying
             for (i, migration) in migrations.iter().enumerate() {
                 tracing::info!(migration_number = i + 1, sql = migration, "Migration SQL (dry run)");
             }
-            return Ok(());
+            return Ok(ApplyReport {
+                skipped: migrations,
+                ..Default::default()
+            });
         }
 
-        db::migrations::apply_migrations(
+        // Make sure the connection is still alive before touching the schema
+        self.db_connection.wait_until_healthy(&self.config.database).await?;
+
+        let report = db::migrations::apply_migrations(
             &self.db_connection, 
             migrations, 
-            &self.config.migrations
+            &self.config.migrations,
+            self.config.performance.as_ref(),
+            self.event_handler.as_deref(),
+        ).await?;
+
+        match (&report.failed, &self.event_handler) {
+            (Some(failed), Some(handler)) => handler.on_error(&failed.error).await,
+            (None, _) => {
+                self.record_sqlite_header(self.config.migrations.schema_version.as_deref()).await?;
+            }
+            _ => {}
+        }
+        Ok(report)
+    }
+
+    /// Check existing data against a diff, then generate and apply its migrations
+    ///
+    /// The `[hooks]` webhooks are notified of the outcome, with the diff summary and
+    /// the applied migration IDs, or the error. A rejected statement is reported in
+    /// `ApplyReport::failed`, as with `apply_migrations`.
+    pub async fn apply_diff(&self, diff: &SchemaDiff) -> Result<ApplyReport> {
+        let result = self.run_diff(diff).await;
+        
+        if let (Some(hooks), false) = (&self.config.hooks, self.config.migrations.dry_run) {
+            let error = match &result {
+                Ok(report) => report.failed.as_ref().map(|failed| failed.error.to_string()),
+                Err(e) => Some(e.to_string()),
+            };
+            let event = webhooks::SchemaChangeEvent {
+                status: if error.is_none() { "applied" } else { "failed" }.to_string(),
+                changes: diff.differences(),
+                migration_ids: result.as_ref().map(ApplyReport::migration_ids).unwrap_or_default(),
+                error,
+            };
+            webhooks::notify(hooks, &event).await;
+        }
+        
+        result
+    }
+
+    /// Apply the migrations of a plan, exactly as they were written into it
+    ///
+    /// With `migrations.require_signed_plans`, the plan must carry a signature made with the
+    /// key in `migrations.plan_key_env` over its current SQL (see `Plan::sign`), which
+    /// needs the `plan-signing` feature.
+    ///
+    /// When the plan recorded table fingerprints, the tables it touches are analyzed again
+    /// first; if any was altered since the plan was made, nothing runs and
+    /// `Error::SchemaChangedSincePlan` names them.
+    ///
+    /// With `migrations.verify_after_apply`, the plan's tables are read back afterwards and
+    /// compared with the models.
+    pub async fn apply_plan(&self, plan: &schema::Plan) -> Result<ApplyReport> {
+        let result = self.check_plan(plan);
+        self.report(result).await?;
+        let result = self.check_plan_base(plan).await;
+        self.report(result).await?;
+        let result = match self.run_migrations(plan.sql()).await {
+            Ok(report) => self.verify_report(report, &plan.tables()).await,
+            Err(e) => Err(e),
+        };
+        self.report(result).await
+    }
+
+    fn check_plan(&self, plan: &schema::Plan) -> Result<()> {
+        if plan.driver != self.config.database.driver {
+            return Err(Error::ConfigError(format!(
+                "the plan was generated for {}, but database.driver is {}",
+                plan.driver, self.config.database.driver
+            )));
+        }
+        if self.config.migrations.require_signed_plans != Some(true) {
+            return Ok(());
+        }
+
+        #[cfg(feature = "plan-signing")]
+        {
+            let key = self.config.migrations.plan_key()?;
+            plan.verify(key.as_bytes())
+        }
+        #[cfg(not(feature = "plan-signing"))]
+        Err(Error::ConfigError(
+            "migrations.require_signed_plans needs schema_sync built with the \"plan-signing\" feature".to_string(),
+        ))
+    }
+
+    /// Check that the tables a plan touches still match the fingerprints it recorded
+    async fn check_plan_base(&self, plan: &schema::Plan) -> Result<()> {
+        if plan.schema_fingerprints.is_none() {
+            return Ok(());
+        }
+        
+        let current = self.analyze_named_tables(plan.tables().iter()).await?;
+        let tables = plan.changed_tables(&current);
+        if tables.is_empty() {
+            return Ok(());
+        }
+        
+        Err(Error::SchemaChangedSincePlan(SchemaChangedSincePlan { tables }))
+    }
+
+    async fn run_diff(&self, diff: &SchemaDiff) -> Result<ApplyReport> {
+        // Make sure existing data satisfies the new constraints
+        self.validate_data(diff).await?;
+        
+        let migrations = self.generate_migrations(diff).await?;
+        let result = self.run_migrations(migrations).await;
+        let report = self.report(result).await?;
+        
+        // Extra SQL only runs with the table's creation, so that is when its checksum is taken
+        if report.failed.is_none() && !self.config.migrations.dry_run {
+            for table in &diff.tables_to_create {
+                if let Some(checksum) = table.extra_sql_checksum() {
+                    let key = db::meta::extra_sql_key(&table.name);
+                    db::meta::write_value(&self.db_connection, &key, &checksum).await?;
+                }
+            }
+            
+            if self.config.migrations.verify_after_apply == Some(true) {
+                let result = self.verify_applied(&diff.touched_tables()).await;
+                self.report(result).await?;
+            }
+        }
+        
+        Ok(report)
+    }
+
+    /// Verify the `touched` tables after a successful run, with `migrations.verify_after_apply`
+    async fn verify_report(&self, report: ApplyReport, touched: &BTreeSet<String>) -> Result<ApplyReport> {
+        let applied = report.failed.is_none() && !self.config.migrations.dry_run;
+        if applied && self.config.migrations.verify_after_apply == Some(true) {
+            self.verify_applied(touched).await?;
+        }
+        Ok(report)
+    }
+
+    /// Check that the `touched` tables now match the models
+    ///
+    /// Catches DDL that ran without doing what was intended, such as a type the database
+    /// silently coerced. Fails with `Error::SchemaMismatch` listing what still differs.
+    async fn verify_applied(&self, touched: &BTreeSet<String>) -> Result<()> {
+        let applied = self.analyze_named_tables(touched.iter()).await?;
+        let mut model_schema = self.model_schema_for(&applied)?;
+        
+        // Compare in the database's own type names, as the analyzer reports them
+        let generator = MigrationGenerator::new(&self.config);
+        for column in model_schema.tables.values_mut().flat_map(|table| table.columns.iter_mut()) {
+            column.data_type = generator.native_data_type(&column.data_type)?;
+        }
+        
+        let differences = SchemaDiff::differences_in(touched, applied, &model_schema, &self.config.schema);
+        if differences.is_empty() {
+            tracing::info!(tables = touched.len(), "Applied schema verified against the models");
+            return Ok(());
+        }
+        
+        Err(Error::SchemaMismatch(SchemaMismatch { differences }))
+    }
+
+    /// Analyze the named tables, skipping those that don't exist
+    ///
+    /// With `naming.ignore_case_conflicts`, a table not found as named is looked up again
+    /// as the database would fold its name.
+    async fn analyze_named_tables(&self, table_names: impl Iterator<Item = &String>) -> Result<schema::types::DatabaseSchema> {
+        let mut db_schema = schema::types::DatabaseSchema::new(None);
+        for table_name in table_names {
+            let mut table = self.schema_analyzer.analyze_table(table_name).await?;
+            if let (None, true, Some(dialect)) = (&table, self.config.naming.ignore_case_conflicts, self.dialect()) {
+                table = self.schema_analyzer.analyze_table(&dialect.fold_identifier(table_name)).await?;
+            }
+            if let Some(table) = table {
+                db_schema.add_table(table);
+            }
+        }
+        Ok(db_schema)
+    }
+
+    /// Record the SQLite `application_id` and `user_version`, when configured
+    async fn record_sqlite_header(&self, schema_version: Option<&str>) -> Result<()> {
+        match &self.config.database.sqlite {
+            Some(sqlite) => {
+                db::migrations::record_sqlite_header(&self.db_connection, sqlite, schema_version).await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Schema version most recently applied to the database, from the history table
+    pub async fn current_schema_version(&self) -> Result<Option<String>> {
+        db::migrations::current_schema_version(
+            &self.db_connection,
+            &self.config.migrations.history_table_name(),
         ).await
     }
 
+    /// Apply the embedded migrations the database has not seen yet
+    ///
+    /// Meant for application startup, with migrations compiled in by `embed_migrations!`,
+    /// so the migrations directory need not be deployed. Applied files are recorded in the
+    /// history table under their file names, just as `upgrade_to` records them.
+    pub async fn apply_embedded_migrations(&self, migrations: &EmbeddedMigrations) -> Result<ApplyReport> {
+        let result = self.run_embedded_migrations(migrations).await;
+        self.report(result).await
+    }
+
+    async fn run_embedded_migrations(&self, migrations: &EmbeddedMigrations) -> Result<ApplyReport> {
+        let files = migrations.files();
+        self.db_connection.wait_until_healthy(&self.config.database).await?;
+
+        let report = db::migrations::apply_pending(
+            &self.db_connection,
+            &files,
+            &self.config.migrations,
+            self.config.performance.as_ref(),
+            self.event_handler.as_deref(),
+        ).await?;
+
+        match (&report.failed, &self.event_handler) {
+            (Some(failed), Some(handler)) => handler.on_error(&failed.error).await,
+            (None, _) => {
+                let version = files.iter().rev().find_map(|file| file.schema_version.as_deref());
+                self.record_sqlite_header(version).await?;
+            }
+            _ => {}
+        }
+        Ok(report)
+    }
+
+    /// Apply the pending migrations from the migrations directory up to the given version tag
+    pub async fn upgrade_to(&self, version: &str) -> Result<()> {
+        let result = self.run_upgrade(version).await;
+        self.report(result).await
+    }
+
+    async fn run_upgrade(&self, version: &str) -> Result<()> {
+        self.db_connection.wait_until_healthy(&self.config.database).await?;
+
+        db::migrations::upgrade_to(
+            &self.db_connection,
+            version,
+            &self.config.migrations,
+            self.config.performance.as_ref(),
+            self.event_handler.as_deref(),
+        ).await?;
+
+        self.record_sqlite_header(Some(version)).await
+    }
+
+    /// Revert the database to the schema as of the given version tag
+    ///
+    /// The target schema is rebuilt by replaying the migrations up to that tag, and the
+    /// live schema is migrated to it. This drops tables and columns added by later versions.
+    /// Only tables the migration files create are changed; others, including SchemaSync's
+    /// own history and metadata tables, are left alone.
+    pub async fn downgrade_to(&self, version: &str) -> Result<()> {
+        let result = self.run_downgrade(version).await;
+        self.report(result).await
+    }
+
+    /// The SQL `downgrade_to` would run to revert the database to the given version tag
+    pub async fn downgrade_sql(&self, version: &str) -> Result<Vec<String>> {
+        let files = db::migrations::read_migration_files(&self.config.migrations.directory)?;
+        self.revert_sql(&files, version).await
+    }
+
+    async fn revert_sql(&self, files: &[db::migrations::MigrationFile], version: &str) -> Result<Vec<String>> {
+        let target = db::migrations::last_index_of_version(files, version)?;
+        let driver = &self.config.database.driver;
+
+        let mut target_schema = schema::types::DatabaseSchema::new(None);
+        for file in files.iter().take(target + 1) {
+            schema::snapshot::replay_sql(&mut target_schema, &file.sql, driver)?;
+        }
+        let mut migrated_schema = target_schema.clone();
+        for file in &files[target + 1..] {
+            schema::snapshot::replay_sql(&mut migrated_schema, &file.sql, driver)?;
+        }
+
+        // Only tables the migration files know about are compared, never SchemaSync's own
+        let history_table = self.config.migrations.history_table_name();
+        let own_tables = [
+            history_table.rsplit('.').next().unwrap_or_default(),
+            db::meta::META_TABLE,
+        ];
+        let known_tables: BTreeSet<String> = target_schema
+            .tables
+            .keys()
+            .chain(migrated_schema.tables.keys())
+            .filter(|name| !own_tables.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        target_schema.tables.retain(|name, _| known_tables.contains(name));
+        let current_schema = self.analyze_named_tables(known_tables.iter()).await?;
+
+        // Reverting necessarily removes what later versions added
+        let mut schema_config = self.config.schema.clone();
+        schema_config.allow_table_removal = true;
+        schema_config.allow_column_removal = true;
+
+        let diff = SchemaDiff::generate(current_schema, target_schema, &schema_config);
+        self.generate_migrations(&diff).await
+    }
+
+    async fn run_downgrade(&self, version: &str) -> Result<()> {
+        let files = db::migrations::read_migration_files(&self.config.migrations.directory)?;
+        let target = db::migrations::last_index_of_version(&files, version)?;
+        let revert_sql = self.revert_sql(&files, version).await?;
+
+        if self.config.migrations.dry_run {
+            for (i, migration) in revert_sql.iter().enumerate() {
+                tracing::info!(migration_number = i + 1, sql = migration, "Downgrade SQL (dry run)");
+            }
+            return Ok(());
+        }
+
+        self.db_connection.wait_until_healthy(&self.config.database).await?;
+
+        let applied = db::migrations::applied_migrations(
+            &self.db_connection,
+            &self.config.migrations.history_table_name(),
+        ).await?;
+        let reverted: Vec<String> = files[target + 1..]
+            .iter()
+            .filter(|file| applied.contains(&file.filename))
+            .map(|file| file.filename.clone())
+            .collect();
+
+        db::migrations::revert_migrations(
+            &self.db_connection,
+            &revert_sql,
+            &reverted,
+            &self.config.migrations,
+            self.config.performance.as_ref(),
+        ).await?;
+
+        self.record_sqlite_header(Some(version)).await
+    }
+
+    /// Whether the database was last synced with the registered models
+    ///
+    /// Compares the fingerprint recorded by `sync_database` with one computed from the
+    /// models, without analyzing the database. Register the models first. A database
+    /// changed by hand since the last sync still counts as up to date.
+    pub async fn is_up_to_date(&self) -> Result<bool> {
+        let model_schema = self.model_registry.to_database_schema(&self.config)?;
+        let stored = db::meta::read_value(&self.db_connection, db::meta::FINGERPRINT_KEY).await?;
+        
+        Ok(stored.as_deref() == Some(model_schema.fingerprint().as_str()))
+    }
+
+    /// Tables whose model extra SQL differs from what ran when the table was created
+    ///
+    /// Extra SQL (`extra_sql_after_create` and field `extra_sql` hooks) is only run with
+    /// the table's creation migration, and its checksum recorded then. Editing it later
+    /// changes nothing in the database, so the tables listed here need the change applied
+    /// by hand. Tables with extra SQL that never ran, because the table predates it, are
+    /// listed too. Register the models first.
+    pub async fn changed_extra_sql(&self) -> Result<Vec<String>> {
+        let model_schema = self.model_registry.to_database_schema(&self.config)?;
+        let mut changed = Vec::new();
+        
+        for table in model_schema.tables.values() {
+            let Some(checksum) = table.extra_sql_checksum() else {
+                continue;
+            };
+            let stored = db::meta::read_value(&self.db_connection, &db::meta::extra_sql_key(&table.name)).await?;
+            if stored.as_deref() != Some(checksum.as_str()) {
+                changed.push(table.name.clone());
+            }
+        }
+        
+        changed.sort();
+        Ok(changed)
+    }
+
+    /// Collect drift, lint diagnostics, and data violations as findings for CI annotations
+    ///
+    /// Each finding points at the model that declares its table. Drift and data violations
+    /// are errors; nothing is applied. Register the models first.
+    pub async fn check(&self) -> Result<Vec<annotations::Finding>> {
+        let locations = annotations::ModelLocations::new(self.model_registry.get_models().values());
+        let diff = self.generate_schema_diff().await?;
+        
+        let mut findings = locations.drift(&diff.table_differences());
+        findings.extend(locations.diagnostics(&self.diagnostics()?));
+        if self.config.schema.validate_data != Some(false) {
+            let violations = db::validation::validate_diff(&self.db_connection, &diff).await?;
+            findings.extend(locations.violations(&violations));
+        }
+        
+        Ok(findings)
+    }
+
+    /// Fail with `Error::SchemaMismatch` unless the database matches the registered models
+    ///
+    /// Meant for application startup. A matching fingerprint from the last sync is accepted
+    /// straight away; otherwise only the model tables are analyzed and diffed, and the
+    /// error lists every difference. Register the models first.
+    pub async fn assert_compatible(&self) -> Result<()> {
+        let model_schema = self.model_registry.to_database_schema(&self.config)?;
+        let stored = db::meta::read_value(&self.db_connection, db::meta::FINGERPRINT_KEY).await?;
+        if stored.as_deref() == Some(model_schema.fingerprint().as_str()) {
+            return Ok(());
+        }
+        
+        let db_schema = self.analyze_named_tables(model_schema.tables.keys()).await?;
+        let model_schema = self.model_schema_for(&db_schema)?;
+        let diff = SchemaDiff::generate(db_schema, model_schema, &self.config.schema);
+        if diff.is_empty() {
+            return Ok(());
+        }
+        
+        Err(Error::SchemaMismatch(SchemaMismatch {
+            differences: diff.differences(),
+        }))
+    }
+
     /// Complete workflow: scan models, analyze db, generate and apply migrations
+    ///
+    /// Afterwards the model schema's fingerprint is recorded for `is_up_to_date`.
     pub async fn sync_database(&mut self) -> Result<()> {
         // Register all models
         self.register_models().await?;
@@ -101,13 +744,23 @@ impl SchemaSyncClient {
         
         if diff.is_empty() {
             tracing::info!("Database schema is already in sync with models");
+        } else {
+            self.apply_diff(&diff).await?.into_result()?;
+        }
+        
+        if self.config.migrations.dry_run {
             return Ok(());
         }
         
-        // Generate migrations
-        let migrations = self.generate_migrations(&diff).await?;
+        for table in self.changed_extra_sql().await? {
+            tracing::warn!(
+                table = %table,
+                "Extra SQL for this table differs from what ran when it was created; apply the change by hand"
+            );
+        }
         
-        // Apply migrations
-        self.apply_migrations(migrations).await
+        let fingerprint = self.model_registry.to_database_schema(&self.config)?.fingerprint();
+        let result = db::meta::write_value(&self.db_connection, db::meta::FINGERPRINT_KEY, &fingerprint).await;
+        self.report(result).await
     }
 }
\ No newline at end of file
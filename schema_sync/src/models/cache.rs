@@ -0,0 +1,69 @@
+//! Parsed model cache
+//!
+//! Parsing every model file on each scan is slow for large codebases. The models found in a
+//! file are stored under `models.cache_dir`, keyed by a hash of the file's path and contents
+//! and of the settings that shape parsing, so a scan only parses the files that changed.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::models::registry::ModelInfo;
+
+/// Default directory for cached models
+pub const DEFAULT_CACHE_DIR: &str = ".schema_sync/cache";
+
+/// Models parsed from earlier scans, one entry per file version
+pub struct ModelCache {
+    dir: PathBuf,
+    /// Hash of the release and of the settings a file's models depend on
+    fingerprint: String,
+}
+
+impl ModelCache {
+    /// The cache described by `models.cache` and `models.cache_dir`, or `None` when disabled
+    pub fn new(config: &Config) -> Option<Self> {
+        if config.models.cache == Some(false) {
+            return None;
+        }
+
+        let settings = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "driver": config.database.driver,
+            "attributes": config.models.attributes,
+            "naming": config.naming,
+        });
+        Some(Self {
+            dir: PathBuf::from(config.models.cache_dir.as_deref().unwrap_or(DEFAULT_CACHE_DIR)),
+            fingerprint: format!("{:x}", md5::compute(settings.to_string().as_bytes())),
+        })
+    }
+
+    /// Key for the version of `path` whose contents are `content`
+    pub fn key(&self, path: &Path, content: &str) -> String {
+        let mut context = md5::Context::new();
+        context.consume(self.fingerprint.as_bytes());
+        context.consume([0]);
+        context.consume(path.to_string_lossy().as_bytes());
+        context.consume([0]);
+        context.consume(content.as_bytes());
+        format!("{:x}", context.compute())
+    }
+
+    /// The models cached under `key`; a missing or unreadable entry is a miss
+    pub fn get(&self, key: &str) -> Option<Vec<ModelInfo>> {
+        let json = std::fs::read_to_string(self.entry(key)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Cache the models parsed from a file under `key`
+    pub fn put(&self, key: &str, models: &[ModelInfo]) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.entry(key), serde_json::to_string(models)?)?;
+        Ok(())
+    }
+
+    fn entry(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! This module handles model registration and discovery.
 
+pub mod cache;
 pub mod registry;
 
 // Re-export key types
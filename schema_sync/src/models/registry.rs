@@ -2,17 +2,27 @@
 //!
 //! This module manages the registration and discovery of model structs.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use walkdir::WalkDir;
+use ignore::WalkBuilder;
 use regex::Regex;
-use syn::{parse_file, Attribute, Fields, Item, ItemStruct};
+use syn::{parse_file, Attribute, Fields, Item, ItemEnum, ItemStruct};
 use quote::ToTokens;
+use serde::{Deserialize, Serialize};
 
-use crate::config::{Config, ModelsConfig};
+use crate::config::{AutoPrimaryKeyConfig, Config, ModelsConfig};
 use crate::error::{Error, Result};
-use crate::schema::types::{DatabaseSchema, FieldDefinition, Table};
-use crate::utils::naming::apply_naming_convention;
+use crate::models::cache::ModelCache;
+use crate::schema::dialect::{clickhouse_type, is_uuid_generator, Dialect};
+use crate::schema::types::{
+    normalize_deferrable, normalize_referential_action, Column, Constraint, DatabaseSchema,
+    FieldDefinition, ForeignKey, Index, Table,
+};
+use crate::utils::naming::{
+    apply_naming_convention, get_max_identifier_length, render_name, sanitize_identifier_for, NameParts,
+};
 
 /// A model that can be synchronized with the database
 pub trait SchemaSyncModel {
@@ -33,13 +43,41 @@ pub struct ModelRegistry {
 }
 
 /// Information about a registered model
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelInfo {
     pub name: String,
     pub file_path: PathBuf,
     pub table_name: String,
     pub fields: Vec<FieldDefinition>,
     pub attributes: HashMap<String, String>,
+    /// Multi-column foreign keys declared with `#[schema_sync(foreign_key(...))]`
+    pub foreign_keys: Vec<ForeignKey>,
+    /// Table constraints such as `#[schema_sync(exclude(...))]`
+    pub constraints: Vec<Constraint>,
+    /// Indexes declared with `#[schema_sync(index(...))]`
+    pub indexes: Vec<Index>,
+    /// Variants of an enum declared with `#[schema_sync]`; such a model is a column type
+    #[serde(default)]
+    pub variants: Vec<EnumVariant>,
+}
+
+/// One variant of a `#[schema_sync]` enum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumVariant {
+    /// The variant's Rust name, e.g. `InProgress`
+    pub name: String,
+    /// The string stored for it, after `rename` / `rename_all`
+    pub label: String,
+    /// The integer stored for it: the explicit discriminant, or one more than the previous
+    pub discriminant: i64,
+}
+
+impl ModelInfo {
+    /// A boolean struct option such as `#[schema_sync(timestamps = false)]`, which
+    /// overrides the matching global setting for this model only
+    pub fn flag(&self, key: &str) -> Option<bool> {
+        self.attributes.get(key).and_then(|value| value.parse().ok())
+    }
 }
 
 impl ModelRegistry {
@@ -56,15 +94,28 @@ impl ModelRegistry {
         let attribute_patterns: Vec<Regex> = self.config.attributes
             .iter()
             .map(|attr| {
-                Regex::new(&format!(r"#\[{}.*\]", attr.trim_start_matches("#[").trim_end_matches("]")))
+                // Attributes are matched as token streams print them, e.g. `# [schema_sync]`
+                Regex::new(&format!(r"#\s*\[\s*{}.*\]", attr.trim_start_matches("#[").trim_end_matches("]")))
                     .map_err(|e| Error::ModelRegistrationError(format!("Invalid attribute regex: {}", e)))
             })
             .collect::<Result<Vec<Regex>>>()?;
         
+        let cache = ModelCache::new(config);
+        match self.config.discovery.as_deref().unwrap_or("source") {
+            "source" => {}
+            "expand" => return self.scan_expanded(&attribute_patterns, config, cache.as_ref()),
+            other => {
+                return Err(Error::ConfigError(format!(
+                    "unknown models.discovery '{}'; expected \"source\" or \"expand\"",
+                    other
+                )));
+            }
+        }
+        
         // Create a copy of the paths to avoid borrowing self
         let paths = self.config.paths.clone();
-        let recursive_scan = self.config.recursive_scan;
         let exclude_paths = self.config.exclude_paths.clone().unwrap_or_default();
+        let mut budget = ScanBudget::new(&self.config);
         
         for path in &paths {
             let base_path = Path::new(path);
@@ -76,26 +127,19 @@ impl ModelRegistry {
             }
             
             // Walk directory and find Rust files
-            for entry in WalkDir::new(base_path)
-                .follow_links(true)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                let path = entry.path();
+            for entry in self.walk(base_path, self.config.scan_depth(path)) {
+                let entry = entry?;
+                let path = entry.as_path();
                 
                 // Skip if path is in excluded paths
                 if exclude_paths.iter().any(|exclude| path.starts_with(exclude)) {
                     continue;
                 }
+                budget.visit(path)?;
                 
                 // Only process .rs files
                 if path.is_file() && path.extension().map_or(false, |ext| ext == "rs") {
-                    self.process_file(path, &attribute_patterns, config)?;
-                }
-                
-                // If not recursive, don't go into subdirectories
-                if !recursive_scan && path.is_dir() && path != base_path {
-                    continue;
+                    self.process_file(path, &attribute_patterns, config, cache.as_ref())?;
                 }
             }
         }
@@ -103,29 +147,178 @@ impl ModelRegistry {
         Ok(())
     }
     
+    /// Every file and directory under `base_path`, at most `max_depth` levels down
+    ///
+    /// Unless `respect_gitignore = false`, what `.gitignore` files exclude is skipped, as are
+    /// `target/` and `.git/`, which hold no models but can be very large. Symbolic links are
+    /// followed; one that leads back to a directory it is in fails the scan, since it makes
+    /// the tree endless. Entries that can't be read are skipped.
+    fn walk(&self, base_path: &Path, max_depth: Option<usize>) -> Box<dyn Iterator<Item = Result<PathBuf>>> {
+        if self.config.respect_gitignore == Some(false) {
+            let entries = WalkDir::new(base_path)
+                .follow_links(true)
+                .max_depth(max_depth.unwrap_or(usize::MAX))
+                .into_iter()
+                .filter_map(|entry| match entry {
+                    Ok(entry) => Some(Ok(entry.into_path())),
+                    Err(e) => match (e.path(), e.loop_ancestor()) {
+                        (Some(child), Some(ancestor)) => Some(Err(symlink_loop(child, ancestor))),
+                        _ => None,
+                    },
+                });
+            return Box::new(entries);
+        }
+        
+        let entries = WalkBuilder::new(base_path)
+            .follow_links(true)
+            .max_depth(max_depth)
+            .hidden(false)
+            .require_git(false)
+            .filter_entry(|entry| entry.depth() == 0 || !matches!(entry.file_name().to_str(), Some("target" | ".git")))
+            .build()
+            .filter_map(|entry| match entry {
+                Ok(entry) => Some(Ok(entry.into_path())),
+                Err(e) => find_loop(&e).map(|(child, ancestor)| Err(symlink_loop(child, ancestor))),
+            });
+        Box::new(entries)
+    }
+    
+    /// Register the models in the macro-expanded source of each of `expand_crates`
+    fn scan_expanded(
+        &mut self,
+        attribute_patterns: &[Regex],
+        config: &Config,
+        cache: Option<&ModelCache>,
+    ) -> Result<()> {
+        let default_crates = vec![".".to_string()];
+        let crates = self.config.expand_crates.clone().unwrap_or(default_crates);
+        let command = self.config.expand_command.clone().unwrap_or_else(|| {
+            ["cargo", "expand", "--manifest-path", "{crate}/Cargo.toml", "--lib", "--ugly"]
+                .map(str::to_string)
+                .to_vec()
+        });
+        let (program, args) = command
+            .split_first()
+            .ok_or_else(|| Error::ConfigError("models.expand_command is empty".to_string()))?;
+        
+        for crate_dir in &crates {
+            let output = std::process::Command::new(program)
+                .args(args.iter().map(|arg| arg.replace("{crate}", crate_dir)))
+                .output()
+                .map_err(|e| Error::ModelRegistrationError(format!("Failed to run {} to expand {}: {}", program, crate_dir, e)))?;
+            if !output.status.success() {
+                return Err(Error::ModelRegistrationError(format!(
+                    "{} failed to expand {}: {}",
+                    program,
+                    crate_dir,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+            
+            let source = String::from_utf8_lossy(&output.stdout);
+            let manifest = Path::new(crate_dir).join("Cargo.toml");
+            self.register_source(&manifest, &source, attribute_patterns, config, cache, true)?;
+        }
+        
+        Ok(())
+    }
+    
     /// Process a Rust file and extract model definitions
     fn process_file(
         &mut self,
         file_path: &Path,
         attribute_patterns: &[Regex],
         config: &Config,
+        cache: Option<&ModelCache>,
     ) -> Result<()> {
         let file_content = std::fs::read_to_string(file_path)?;
-        let syntax = parse_file(&file_content)
-            .map_err(|e| Error::SyntaxError(format!("Failed to parse file: {}", e)))?;
+        self.register_source(file_path, &file_content, attribute_patterns, config, cache, false)
+    }
+    
+    /// Register the models defined in `source`, read from `file_path`
+    ///
+    /// With a cache, source that is unchanged since it was last parsed registers the
+    /// models cached for it instead of being parsed again.
+    fn register_source(
+        &mut self,
+        file_path: &Path,
+        source: &str,
+        attribute_patterns: &[Regex],
+        config: &Config,
+        cache: Option<&ModelCache>,
+        expanded: bool,
+    ) -> Result<()> {
+        let key = cache.map(|cache| cache.key(file_path, source));
+        let cached = cache.zip(key.as_deref()).and_then(|(cache, key)| cache.get(key));
         
-        for item in syntax.items {
-            if let Item::Struct(item_struct) = item {
-                // Check if struct has one of the required attributes
-                if self.has_schema_sync_attribute(&item_struct.attrs, attribute_patterns) {
-                    self.register_model(file_path, item_struct, config)?;
+        let models = match cached {
+            Some(models) => models,
+            None => {
+                let syntax = parse_file(source)
+                    .map_err(|e| Error::SyntaxError(format!("Failed to parse file: {}", e)))?;
+                
+                // Expansion consumes the `#[schema_sync]` attribute, leaving the trait impl it generated
+                // and the attribute's options in a `SCHEMA_SYNC_OPTIONS` constant
+                let implemented = expanded.then(|| model_impls(&syntax.items));
+                let models = self.find_models(file_path, syntax.items, attribute_patterns, implemented.as_ref(), config)?;
+                
+                if let (Some(cache), Some(key)) = (cache, key.as_deref()) {
+                    // A cache that can't be written only costs the next scan a parse
+                    if let Err(e) = cache.put(key, &models) {
+                        tracing::warn!(path = %file_path.display(), error = %e, "Failed to cache parsed models");
+                    }
                 }
+                models
             }
+        };
+        
+        for model_info in models {
+            self.models.insert(model_info.name.clone(), model_info);
         }
         
         Ok(())
     }
     
+    /// Build the models among `items`
+    ///
+    /// For expanded source, `implemented` maps the types implementing `SchemaSyncModel`, which
+    /// are models too, to the `#[schema_sync(...)]` attributes expansion consumed, and inline
+    /// modules are searched as well.
+    fn find_models(
+        &self,
+        file_path: &Path,
+        items: Vec<Item>,
+        attribute_patterns: &[Regex],
+        implemented: Option<&HashMap<String, Vec<Attribute>>>,
+        config: &Config,
+    ) -> Result<Vec<ModelInfo>> {
+        let mut models = Vec::new();
+        for item in items {
+            match item {
+                Item::Struct(mut item_struct) => {
+                    // Check if struct has one of the required attributes
+                    let consumed = implemented.and_then(|models| models.get(&item_struct.ident.to_string()));
+                    let is_model = self.has_schema_sync_attribute(&item_struct.attrs, attribute_patterns) || consumed.is_some();
+                    if is_model {
+                        item_struct.attrs.extend(consumed.into_iter().flatten().cloned());
+                        models.push(self.register_model(file_path, item_struct, config)?);
+                    }
+                }
+                Item::Enum(item_enum) if self.has_schema_sync_attribute(&item_enum.attrs, attribute_patterns) => {
+                    models.push(self.register_enum(file_path, item_enum, config)?);
+                }
+                Item::Mod(module) if implemented.is_some() => {
+                    if let Some((_, items)) = module.content {
+                        models.extend(self.find_models(file_path, items, attribute_patterns, implemented, config)?);
+                    }
+                }
+                _ => {}
+            }
+        }
+        
+        Ok(models)
+    }
+    
     /// Check if a struct has a SchemaSync attribute
     fn has_schema_sync_attribute(&self, attrs: &[Attribute], patterns: &[Regex]) -> bool {
         for attr in attrs {
@@ -138,145 +331,334 @@ impl ModelRegistry {
         false
     }
     
-    /// Register a model from a struct definition
+    /// Build the model information for a struct definition
     fn register_model(
-        &mut self,
+        &self,
         file_path: &Path,
         item_struct: ItemStruct,
         config: &Config,
-    ) -> Result<()> {
+    ) -> Result<ModelInfo> {
         let struct_name = item_struct.ident.to_string();
         
         // Extract table name from attribute or apply naming convention
-        let table_name = self.extract_table_name(&item_struct, &struct_name, &config.naming)?;
+        let table_name = self.extract_table_name(&item_struct, &struct_name, config)?;
+        let dialect = Dialect::from_driver(&config.database.driver);
+        let transliterate = config.naming.transliterate_identifiers == Some(true);
+        let struct_attributes = self.extract_struct_attributes(&item_struct, &table_name, config)?;
+        let serde_names = config.naming.serde_names == Some(true) && matches!(item_struct.fields, Fields::Named(_));
+        let rename_all = attribute_rename(&item_struct.attrs, "serde", "rename_all").filter(|_| serde_names);
+        
+        // Named fields are columns; a newtype's single field is one column, named by the
+        // `column` option (default "value")
+        let fields: Vec<(String, syn::Field)> = match item_struct.fields {
+            Fields::Named(named_fields) => named_fields
+                .named
+                .into_iter()
+                .filter_map(|field| Some((field.ident.as_ref()?.to_string(), field)))
+                .collect(),
+            Fields::Unnamed(unnamed_fields) if unnamed_fields.unnamed.len() == 1 => {
+                let column = struct_attributes.options.get("column").cloned().unwrap_or_else(|| "value".to_string());
+                unnamed_fields.unnamed.into_iter().map(|field| (column.clone(), field)).collect()
+            }
+            _ => {
+                return Err(Error::ModelRegistrationError(
+                    format!("Only named fields or a single unnamed field are supported in struct: {}", struct_name)
+                ));
+            }
+        };
+        if struct_attributes.options.get("transparent").is_some_and(|value| value == "true") && fields.len() != 1 {
+            return Err(Error::ModelRegistrationError(
+                format!("transparent model {} must have exactly one field", struct_name)
+            ));
+        }
         
         // Extract field definitions
-        let fields = match item_struct.fields {
-            Fields::Named(named_fields) => {
-                named_fields
-                    .named
-                    .into_iter()
-                    .filter_map(|field| {
-                        let field_name = field.ident?.to_string();
-                        let field_type = field.ty.to_token_stream().to_string();
+        let fields = fields
+            .into_iter()
+            .map(|(field_name, field)| {
+                let field_type = field.ty.to_token_stream().to_string();
+                
+                // Extract field attributes for additional properties
+                let mut attributes = HashMap::new();
+                let mut primary_key = false;
+                let mut nullable = false;
+                let mut unique = false;
+                let mut default = None;
+                let mut foreign_key = None;
+                let mut comment = None;
+                let mut db_type = None;
+                
+                for attr in &field.attrs {
+                    if attr.path().is_ident("schema_sync_field") {
+                        let attr_str = attr.to_token_stream().to_string();
                         
-                        // Extract field attributes for additional properties
-                        let mut attributes = HashMap::new();
-                        let mut primary_key = false;
-                        let mut nullable = false;
-                        let mut unique = false;
-                        let mut default = None;
-                        let mut foreign_key = None;
-                        let mut comment = None;
-                        let mut db_type = None;
+                        // Parse schema_sync_field attributes
+                        if attr_str.contains("primary_key") {
+                            primary_key = attr_str.contains("primary_key = true");
+                        }
                         
-                        for attr in &field.attrs {
-                            if attr.path().is_ident("schema_sync_field") {
-                                let attr_str = attr.to_token_stream().to_string();
-                                
-                                // Parse schema_sync_field attributes
-                                if attr_str.contains("primary_key") {
-                                    primary_key = attr_str.contains("primary_key = true");
-                                }
-                                
-                                if attr_str.contains("nullable") {
-                                    nullable = attr_str.contains("nullable = true");
-                                }
-                                
-                                if attr_str.contains("unique") {
-                                    unique = attr_str.contains("unique = true");
-                                }
-                                
-                                if attr_str.contains("default") {
-                                    // Extract default value between quotes
-                                    if let Some(start) = attr_str.find("default = \"") {
-                                        if let Some(end) = attr_str[start + 11..].find('"') {
-                                            default = Some(attr_str[start + 11..start + 11 + end].to_string());
-                                        }
-                                    }
+                        if attr_str.contains("nullable") {
+                            nullable = attr_str.contains("nullable = true");
+                        }
+                        
+                        if attr_str.contains("unique") {
+                            unique = attr_str.contains("unique = true");
+                        }
+                        
+                        if attr_str.contains("default") {
+                            // Extract default value between quotes
+                            if let Some(start) = attr_str.find("default = \"") {
+                                if let Some(end) = attr_str[start + 11..].find('"') {
+                                    default = Some(attr_str[start + 11..start + 11 + end].to_string());
                                 }
-                                
-                                if attr_str.contains("comment") {
-                                    // Extract comment value between quotes
-                                    if let Some(start) = attr_str.find("comment = \"") {
-                                        if let Some(end) = attr_str[start + 11..].find('"') {
-                                            comment = Some(attr_str[start + 11..start + 11 + end].to_string());
-                                        }
-                                    }
+                            }
+                        }
+                        
+                        if attr_str.contains("comment") {
+                            // Extract comment value between quotes
+                            if let Some(start) = attr_str.find("comment = \"") {
+                                if let Some(end) = attr_str[start + 11..].find('"') {
+                                    comment = Some(attr_str[start + 11..start + 11 + end].to_string());
                                 }
-                                
-                                if attr_str.contains("db_type") {
-                                    // Extract db_type value between quotes
-                                    if let Some(start) = attr_str.find("db_type = \"") {
-                                        if let Some(end) = attr_str[start + 11..].find('"') {
-                                            db_type = Some(attr_str[start + 11..start + 11 + end].to_string());
-                                        }
-                                    }
+                            }
+                        }
+                        
+                        if attr_str.contains("db_type") {
+                            // Extract db_type value between quotes
+                            if let Some(start) = attr_str.find("db_type = \"") {
+                                if let Some(end) = attr_str[start + 11..].find('"') {
+                                    db_type = Some(attr_str[start + 11..start + 11 + end].to_string());
                                 }
-                                
-                                if attr_str.contains("foreign_key") {
-                                    // Extract foreign_key value between quotes
-                                    if let Some(start) = attr_str.find("foreign_key = \"") {
-                                        if let Some(end) = attr_str[start + 15..].find('"') {
-                                            let fk_value = attr_str[start + 15..start + 15 + end].to_string();
-                                            
-                                            // Parse foreign key reference (table.column)
-                                            if let Some(dot_pos) = fk_value.find('.') {
-                                                let ref_table = fk_value[..dot_pos].to_string();
-                                                let ref_column = fk_value[dot_pos + 1..].to_string();
-                                                
-                                                foreign_key = Some(crate::schema::types::ForeignKeyDefinition {
-                                                    ref_table,
-                                                    ref_column,
-                                                    on_delete: None,
-                                                    on_update: None,
-                                                });
-                                            }
-                                        }
+                            }
+                        }
+                        
+                        if let Some(extra_sql) = string_attribute(&attr_str, "extra_sql") {
+                            attributes.insert("extra_sql".to_string(), extra_sql);
+                        }
+                        if let Some(charset) = string_attribute(&attr_str, "charset") {
+                            attributes.insert("charset".to_string(), charset);
+                        }
+                        if let Some(json) = string_attribute(&attr_str, "json") {
+                            attributes.insert("json".to_string(), json);
+                        }
+                        if let Some(storage) = string_attribute(&attr_str, "storage") {
+                            attributes.insert("storage".to_string(), storage);
+                        }
+                        // pgvector embeddings: `vector(dims = 1536)`
+                        if let Some(dims) = rule_arguments(&attr_str, "vector").and_then(|args| numeric_attribute(&args, "dims")) {
+                            attributes.insert("vector_dims".to_string(), dims.to_string());
+                        }
+                        if let Some(storage) = string_attribute(&attr_str, "bit_storage") {
+                            attributes.insert("bit_storage".to_string(), storage);
+                        }
+                        // `currency = true`, or a currency code the companion column defaults to
+                        match (bool_attribute(&attr_str, "currency"), string_attribute(&attr_str, "currency")) {
+                            (Some(true), _) => {
+                                attributes.insert("currency".to_string(), "true".to_string());
+                            }
+                            (_, Some(code)) => {
+                                attributes.insert("currency".to_string(), code);
+                            }
+                            _ => {}
+                        }
+                        if let Some(case_insensitive) = bool_attribute(&attr_str, "case_insensitive") {
+                            attributes.insert("case_insensitive".to_string(), case_insensitive.to_string());
+                        }
+                        if let Some(default_fn) = string_attribute(&attr_str, "default_fn") {
+                            attributes.insert("default_fn".to_string(), default_fn);
+                        }
+                        if let Some(variant) = string_attribute(&attr_str, "default_variant") {
+                            attributes.insert("default_variant".to_string(), variant);
+                        }
+                        
+                        // Type parameters: length for strings, precision/scale for decimals,
+                        // max_bytes for binary, bits for bitflags
+                        for key in ["length", "precision", "scale", "max_bytes", "bits"] {
+                            if let Some(value) = numeric_attribute(&attr_str, key) {
+                                attributes.insert(key.to_string(), value.to_string());
+                            }
+                        }
+                        
+                        // Validation rules, enforced as a CHECK constraint
+                        for key in ["min", "max"] {
+                            if let Some(value) = bound_attribute(&attr_str, key) {
+                                attributes.insert(key.to_string(), value);
+                            }
+                        }
+                        for key in ["min_length", "max_length"] {
+                            if let Some(value) = numeric_attribute(&attr_str, key) {
+                                attributes.insert(key.to_string(), value.to_string());
+                            }
+                        }
+                        if let Some(pattern) = string_attribute(&attr_str, "pattern") {
+                            // Unescape the literal as written, e.g. "^\\d+$"
+                            let pattern = syn::parse_str::<syn::LitStr>(&format!("\"{}\"", pattern))
+                                .map(|literal| literal.value())
+                                .unwrap_or(pattern);
+                            attributes.insert("pattern".to_string(), pattern);
+                        }
+                        
+                        if attr_str.contains("foreign_key") {
+                            // Extract foreign_key value between quotes
+                            if let Some(start) = attr_str.find("foreign_key = \"") {
+                                if let Some(end) = attr_str[start + 15..].find('"') {
+                                    let fk_value = attr_str[start + 15..start + 15 + end].to_string();
+                                    
+                                    // Parse foreign key reference ([schema.]table.column)
+                                    if let Some((ref_table, ref_column)) = fk_value.rsplit_once('.') {
+                                        let ref_table = ref_table.to_string();
+                                        let ref_column = ref_column.to_string();
+                                        
+                                        foreign_key = Some(crate::schema::types::ForeignKeyDefinition {
+                                            ref_table,
+                                            ref_column,
+                                            on_delete: referential_action_attribute(&attr_str, "on_delete", &field_name),
+                                            on_update: referential_action_attribute(&attr_str, "on_update", &field_name),
+                                            deferrable: string_attribute(&attr_str, "deferrable")
+                                                .and_then(|value| {
+                                                    let deferrable = normalize_deferrable(&value);
+                                                    if deferrable.is_none() {
+                                                        tracing::warn!("Ignoring unknown deferrable value '{}' on {}", value, field_name);
+                                                    }
+                                                    deferrable
+                                                }),
+                                            match_type: string_attribute(&attr_str, "match_type")
+                                                .map(|value| value.to_lowercase()),
+                                        });
                                     }
                                 }
                             }
                         }
+                    } else if attr.path().is_ident("validate") {
+                        // validator-style `#[validate(range(min = 0, max = 100))]` and
+                        // `#[validate(length(min = 3, max = 50))]`; schema_sync_field rules win
+                        let attr_str = attr.to_token_stream().to_string();
                         
-                        // Determine nullability from Option<T> type if not explicitly set
-                        if !nullable && field_type.starts_with("Option < ") {
-                            nullable = true;
+                        if let Some(range) = rule_arguments(&attr_str, "range") {
+                            for key in ["min", "max"] {
+                                if let Some(value) = bound_attribute(&range, key) {
+                                    attributes.entry(key.to_string()).or_insert(value);
+                                }
+                            }
                         }
-                        
-                        Some(FieldDefinition {
-                            name: field_name,
-                            rust_type: field_type,
-                            db_type,
-                            nullable,
-                            primary_key,
-                            unique,
-                            default,
-                            foreign_key,
-                            comment,
-                            attributes,
-                        })
-                    })
-                    .collect()
-            }
-            _ => {
-                return Err(Error::ModelRegistrationError(
-                    format!("Only named fields are supported in struct: {}", struct_name)
-                ));
-            }
-        };
+                        if let Some(length) = rule_arguments(&attr_str, "length") {
+                            let equal = numeric_attribute(&length, "equal");
+                            for (key, attribute) in [("min", "min_length"), ("max", "max_length")] {
+                                if let Some(value) = numeric_attribute(&length, key).or(equal) {
+                                    attributes.entry(attribute.to_string()).or_insert(value.to_string());
+                                }
+                            }
+                        }
+                    }
+                }
+                
+                // Determine nullability from Option<T> type if not explicitly set
+                if !nullable && field_type.starts_with("Option < ") {
+                    nullable = true;
+                }
+                
+                // With `serde_names`, the column is named as serde serializes the field
+                let column_name = attribute_rename(&field.attrs, "serde", "rename")
+                    .or_else(|| rename_all.as_deref().and_then(|rule| serde_case(&field_name, rule)))
+                    .filter(|_| serde_names)
+                    .unwrap_or_else(|| field_name.clone());
+                
+                FieldDefinition {
+                    name: config.naming.column_override(&struct_name, &field_name)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| sanitize_identifier_for(&column_name, dialect, transliterate)),
+                    rust_type: field_type,
+                    db_type,
+                    nullable,
+                    primary_key,
+                    unique,
+                    default,
+                    foreign_key,
+                    comment,
+                    attributes,
+                }
+            })
+            .collect();
         
-        // Register the model
-        let model_info = ModelInfo {
+        Ok(ModelInfo {
             name: struct_name.clone(),
             file_path: file_path.to_owned(),
             table_name,
             fields,
-            attributes: HashMap::new(),
+            attributes: struct_attributes.options,
+            foreign_keys: struct_attributes.foreign_keys,
+            constraints: struct_attributes.constraints,
+            indexes: struct_attributes.indexes,
+            variants: Vec::new(),
+        })
+    }
+    
+    /// Build the model information for an enum definition, a column type
+    ///
+    /// Variants are stored as labels, named by `rename` / `rename_all` in `#[schema_sync(...)]`
+    /// or `#[sqlx(...)]` (and `#[serde(...)]` with `naming.serde_names`), or as discriminants.
+    fn register_enum(&self, file_path: &Path, item_enum: ItemEnum, config: &Config) -> Result<ModelInfo> {
+        let enum_name = item_enum.ident.to_string();
+        let mut paths = vec!["schema_sync", "sqlx"];
+        if config.naming.serde_names == Some(true) {
+            paths.push("serde");
+        }
+        let rename = |attrs: &[Attribute], key: &str| {
+            paths.iter().find_map(|path| attribute_rename(attrs, path, key))
         };
+        let rename_all = rename(&item_enum.attrs, "rename_all");
         
-        self.models.insert(struct_name, model_info);
+        let mut attributes = HashMap::new();
+        attributes.insert("enum".to_string(), "true".to_string());
+        for attr in item_enum.attrs.iter().filter(|attr| attr.path().is_ident("schema_sync")) {
+            let attr_str = attr.to_token_stream().to_string();
+            for key in ["storage", "type_name"] {
+                if let Some(value) = string_attribute(&attr_str, key) {
+                    attributes.insert(key.to_string(), value);
+                }
+            }
+        }
         
-        Ok(())
+        let mut variants = Vec::new();
+        let mut next = 0;
+        for variant in &item_enum.variants {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::ModelRegistrationError(format!(
+                    "Enum {} has a variant with fields ({}); only unit variants can be stored",
+                    enum_name, variant.ident
+                )));
+            }
+            
+            let name = variant.ident.to_string();
+            let discriminant = match &variant.discriminant {
+                Some((_, expr)) => integer_literal(expr).ok_or_else(|| {
+                    Error::ModelRegistrationError(format!(
+                        "Discriminant of {}::{} must be an integer literal",
+                        enum_name, name
+                    ))
+                })?,
+                None => next,
+            };
+            next = discriminant + 1;
+            
+            let label = rename(&variant.attrs, "rename")
+                .or_else(|| rename_all.as_deref().and_then(|rule| variant_case(&name, rule)))
+                .unwrap_or_else(|| name.clone());
+            variants.push(EnumVariant { name, label, discriminant });
+        }
+        
+        Ok(ModelInfo {
+            table_name: attributes.get("type_name").cloned()
+                .unwrap_or_else(|| apply_naming_convention(&enum_name, "snake_case")),
+            name: enum_name,
+            file_path: file_path.to_owned(),
+            fields: Vec::new(),
+            attributes,
+            foreign_keys: Vec::new(),
+            constraints: Vec::new(),
+            indexes: Vec::new(),
+            variants,
+        })
     }
     
     /// Extract table name from struct or attributes
@@ -284,8 +666,13 @@ impl ModelRegistry {
         &self,
         item_struct: &ItemStruct,
         struct_name: &str,
-        naming_config: &crate::config::NamingConfig,
+        config: &Config,
     ) -> Result<String> {
+        let naming_config = &config.naming;
+        let rules = naming_config.pluralization.as_ref();
+        let mut pluralize = naming_config.pluralize_tables
+            && !rules.is_some_and(|rules| rules.skip_models.iter().any(|model| model == struct_name));
+        
         // Check for explicit table name in attributes
         for attr in &item_struct.attrs {
             if attr.path().is_ident("schema_sync") {
@@ -299,16 +686,29 @@ impl ModelRegistry {
                         }
                     }
                 }
+                
+                // Per-model override of `naming.pluralize_tables`
+                if let Some(value) = bool_attribute(&attr_str, "pluralize") {
+                    pluralize = value;
+                }
             }
         }
         
-        // Apply naming convention
-        let table_name = apply_naming_convention(struct_name, &naming_config.table_style);
+        // Names from `[naming.overrides]` are used as they are
+        if let Some(table_name) = naming_config.table_override(struct_name) {
+            return Ok(table_name.to_string());
+        }
+        
+        // Apply naming convention, keeping (or transliterating) letters from any script
+        let table_name = sanitize_identifier_for(
+            &apply_naming_convention(struct_name, &naming_config.table_style),
+            Dialect::from_driver(&config.database.driver),
+            naming_config.transliterate_identifiers == Some(true),
+        );
         
         // Apply pluralization if configured
-        let final_name = if naming_config.pluralize_tables {
-            use inflector::Inflector;
-            table_name.to_plural()
+        let final_name = if pluralize {
+            crate::utils::naming::pluralize_with(&table_name, rules)
         } else {
             table_name
         };
@@ -316,11 +716,170 @@ impl ModelRegistry {
         Ok(final_name)
     }
     
+    /// Extract struct-level foreign keys, constraints, and indexes such as
+    /// `#[schema_sync(foreign_key(columns = ["org_id", "user_id"], references = "memberships(org_id, user_id)"))]`,
+    /// `#[schema_sync(exclude(using = "gist", elements = "room_id WITH =, during WITH &&"))]`, and
+    /// `#[schema_sync(index(columns = ["tenant_id"], include = ["email", "name"]))]` or
+    /// `#[schema_sync(index(expression = "lower(email)", unique = true, fillfactor = 70))]`,
+    /// along with plain options like `tablespace = "fast_ssd"` or `timestamps = false`
+    fn extract_struct_attributes(
+        &self,
+        item_struct: &ItemStruct,
+        table_name: &str,
+        config: &Config,
+    ) -> Result<StructAttributes> {
+        let mut foreign_keys = Vec::new();
+        let mut constraints = Vec::new();
+        let mut indexes = Vec::new();
+        let mut plain_options = HashMap::new();
+        
+        for attr in &item_struct.attrs {
+            if !attr.path().is_ident("schema_sync") || !matches!(attr.meta, syn::Meta::List(_)) {
+                continue;
+            }
+            
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("index") {
+                    let options = parse_nested_options(&meta)?;
+                    let columns = options.lists.get("columns").cloned().unwrap_or_default();
+                    let expression = options.values.get("expression").cloned();
+                    if columns.is_empty() && expression.is_none() {
+                        return Err(meta.error("index needs `columns = [\"...\"]` or `expression = \"...\"`"));
+                    }
+                    
+                    // Expression indexes are named after the expression, e.g. ix_users_lower_email
+                    let name = options.values.get("name").cloned().unwrap_or_else(|| {
+                        let mut parts = columns.clone();
+                        if let Some(expression) = &expression {
+                            parts.push(crate::utils::naming::sanitize_identifier(expression).trim_matches('_').to_string());
+                        }
+                        let kind = if options.values.get("unique").is_some_and(|v| v == "true") { "uq" } else { "ix" };
+                        object_name(config, &config.naming.index_pattern, table_name, &parts, kind)
+                    });
+                    
+                    indexes.push(Index {
+                        name,
+                        columns,
+                        is_unique: options.values.get("unique").is_some_and(|v| v == "true"),
+                        // Filled in once column types are known: GiST for ranges, else btree
+                        method: options.values.get("method").cloned(),
+                        include: options.lists.get("include").cloned().unwrap_or_default(),
+                        expression,
+                        options: INDEX_STORAGE_OPTIONS
+                            .iter()
+                            .filter_map(|key| options.values.get(*key).map(|value| (key.to_string(), value.clone())))
+                            .collect(),
+                        tablespace: options.values.get("tablespace").cloned(),
+                        operator_class: options.values.get("opclass").cloned(),
+                    });
+                    return Ok(());
+                }
+                
+                if meta.path.is_ident("exclude") {
+                    let options = parse_nested_options(&meta)?.values;
+                    let elements = options
+                        .get("elements")
+                        .ok_or_else(|| meta.error("exclude needs `elements = \"column WITH operator, ...\"`"))?;
+                    let using = options.get("using").map(String::as_str).unwrap_or("gist");
+                    
+                    // Postgres names unnamed exclusion constraints <table>_<columns>_excl
+                    let name = options.get("name").cloned().unwrap_or_else(|| {
+                        let columns: Vec<String> = elements
+                            .split(',')
+                            .filter_map(|element| element.split_whitespace().next())
+                            .map(|column| column.trim_matches('"').to_string())
+                            .collect();
+                        object_name(config, "{table}_{columns}_{type}", table_name, &columns, "excl")
+                    });
+                    
+                    constraints.push(Constraint::exclusion(name, using, elements));
+                    return Ok(());
+                }
+                
+                if !meta.path.is_ident("foreign_key") {
+                    // Plain options are kept as model attributes
+                    if meta.input.peek(syn::Token![=]) {
+                        let key = meta.path.to_token_stream().to_string();
+                        if let syn::Expr::Lit(syn::ExprLit { lit, .. }) = meta.value()?.parse::<syn::Expr>()? {
+                            match lit {
+                                syn::Lit::Str(value) => plain_options.insert(key, value.value()),
+                                syn::Lit::Bool(value) => plain_options.insert(key, value.value.to_string()),
+                                syn::Lit::Int(value) => plain_options.insert(key, value.base10_digits().to_string()),
+                                _ => None,
+                            };
+                        }
+                    } else if meta.input.peek(syn::token::Paren) {
+                        meta.parse_nested_meta(|inner| {
+                            if inner.input.peek(syn::Token![=]) {
+                                inner.value()?.parse::<syn::Expr>()?;
+                            }
+                            Ok(())
+                        })?;
+                    } else {
+                        // A bare flag such as `transparent`
+                        plain_options.insert(meta.path.to_token_stream().to_string(), "true".to_string());
+                    }
+                    return Ok(());
+                }
+                
+                let NestedOptions { values: options, lists } = parse_nested_options(&meta)?;
+                let columns = lists.get("columns").cloned().unwrap_or_default();
+                
+                let references = options
+                    .get("references")
+                    .ok_or_else(|| meta.error("foreign_key needs `references = \"table(columns)\"`"))?;
+                let (ref_table, ref_columns) = parse_references(references)
+                    .ok_or_else(|| meta.error(format!("invalid foreign key reference '{}'", references)))?;
+                
+                if columns.is_empty() || columns.len() != ref_columns.len() {
+                    return Err(meta.error("foreign key columns and referenced columns must have the same length"));
+                }
+                
+                let name = options.get("name").cloned().unwrap_or_else(|| {
+                    object_name(config, &config.naming.constraint_pattern, table_name, &columns, "fk")
+                });
+                
+                foreign_keys.push(ForeignKey {
+                    name,
+                    columns,
+                    ref_table,
+                    ref_columns,
+                    on_delete: options.get("on_delete").and_then(|v| normalize_referential_action(v)),
+                    on_update: options.get("on_update").and_then(|v| normalize_referential_action(v)),
+                    deferrable: options.get("deferrable").and_then(|v| normalize_deferrable(v)),
+                    match_type: options.get("match_type").map(|v| v.to_lowercase()),
+                });
+                
+                Ok(())
+            })
+            .map_err(|e| Error::SyntaxError(format!("Invalid schema_sync attribute on {}: {}", item_struct.ident, e)))?;
+        }
+        
+        Ok(StructAttributes {
+            foreign_keys,
+            constraints,
+            indexes,
+            options: plain_options,
+        })
+    }
+    
     /// Convert registered models to database schema
     pub fn to_database_schema(&self, config: &Config) -> Result<DatabaseSchema> {
         let mut schema = DatabaseSchema::new(config.database.schema.clone());
         
         for (_, model_info) in &self.models {
+            // Transparent newtypes and enums are column types, not tables
+            if model_info.flag("transparent") == Some(true) {
+                continue;
+            }
+            if model_info.flag("enum") == Some(true) {
+                if enum_storage(model_info, config)? == "native" {
+                    let labels = model_info.variants.iter().map(|variant| variant.label.clone()).collect();
+                    schema.enum_types.insert(model_info.table_name.clone(), labels);
+                }
+                continue;
+            }
+            
             let mut table = Table::new(&model_info.table_name);
             
             // Convert fields to columns
@@ -328,21 +887,87 @@ impl ModelRegistry {
                 // Map Rust type to database type
                 let db_type = match &field.db_type {
                     Some(t) => t.clone(),
-                    None => self.map_type_to_db_type(&field.rust_type, config)?,
+                    // Bitflags and embedding fields map by their width, whatever their Rust type
+                    None if field.attributes.contains_key("bits") => bitflags_db_type(field, config)?,
+                    None if field.attributes.contains_key("vector_dims") => {
+                        vector_db_type(field.attributes.get("vector_dims").map(String::as_str), &field.rust_type, config)?
+                    }
+                    None => apply_type_parameters(
+                        self.map_type_to_db_type(&field.rust_type, config)?,
+                        field,
+                        config,
+                    )?,
+                };
+                
+                // UUID-generating defaults are spelled differently by each database
+                let default = match (&field.default, Dialect::from_driver(&config.database.driver)) {
+                    (Some(default), Some(dialect)) if is_uuid_generator(default) => {
+                        dialect.uuid_default(default)
+                    }
+                    (default, _) => default.clone(),
+                };
+                let default = match field.attributes.get("default_variant") {
+                    Some(_) if default.is_some() => {
+                        return Err(Error::TypeMappingError(format!(
+                            "field '{}' sets both default and default_variant",
+                            field.name
+                        )));
+                    }
+                    Some(variant) => Some(self.variant_default(field, variant, config)?),
+                    None => default,
+                };
+                
+                let storage = match field.attributes.get("storage").map(|s| s.to_lowercase()) {
+                    Some(storage) if !matches!(storage.as_str(), "plain" | "main" | "external" | "extended") => {
+                        return Err(Error::TypeMappingError(format!(
+                            "storage must be plain, main, external or extended, but field '{}' sets '{}'",
+                            field.name, storage
+                        )));
+                    }
+                    storage => storage,
+                };
+                
+                // Case-insensitive text is CITEXT on Postgres and a case-insensitive collation elsewhere
+                let collation = match field.attributes.get("case_insensitive").map(String::as_str) {
+                    Some("true") => match config.database.driver.as_str() {
+                        "mysql" => Some("utf8mb4_unicode_ci".to_string()),
+                        "sqlite" => Some("NOCASE".to_string()),
+                        // Column collations arrived in Oracle 12.2
+                        "oracle" if config.database.oracle_release()? >= (12, 2) => Some("BINARY_CI".to_string()),
+                        _ => None,
+                    },
+                    _ => None,
                 };
                 
                 let column = crate::schema::types::Column {
                     name: field.name.clone(),
                     data_type: db_type,
                     nullable: field.nullable,
-                    default: field.default.clone(),
+                    default,
                     comment: field.comment.clone(),
                     is_unique: field.unique,
                     is_generated: false,
                     generation_expression: None,
+                    charset: field.attributes.get("charset").cloned(),
+                    default_fn: field.attributes.get("default_fn").cloned(),
+                    storage,
+                    collation,
                 };
                 
                 table.add_column(column);
+                
+                // Money amounts get a companion currency-code column, unless the model declares one
+                let currency = field.attributes.get("currency");
+                if currency.is_some() || is_money_type(&field.rust_type) {
+                    let name = format!("{}_currency", field.name);
+                    if !model_info.fields.iter().any(|f| f.name == name) {
+                        let mut column = Column::new(&name, "CHAR(3)").nullable(field.nullable);
+                        if let Some(code) = currency.filter(|code| *code != "true") {
+                            column = column.default(&format!("'{}'", code.replace('\'', "''")));
+                        }
+                        table.add_column(column);
+                    }
+                }
             }
             
             // Set primary key if defined
@@ -352,55 +977,86 @@ impl ModelRegistry {
                 .collect();
                 
             if !pk_fields.is_empty() {
-                let pk_columns = pk_fields.iter().map(|f| f.name.clone()).collect();
+                let pk_columns: Vec<String> = pk_fields.iter().map(|f| f.name.clone()).collect();
+                table.set_primary_key(crate::schema::types::PrimaryKey {
+                    name: Some(primary_key_name(config, &model_info.table_name, &pk_columns)),
+                    columns: pk_columns,
+                });
+            } else if let (Some(auto_pk), true) = (
+                &config.schema.auto_primary_key,
+                model_info.flag("auto_primary_key").unwrap_or(true),
+            ) {
+                let column_exists = table.columns.iter().any(|c| c.name == auto_pk.name);
+                
+                if !column_exists {
+                    table.columns.insert(0, auto_primary_key_column(auto_pk, config)?);
+                }
+                
+                let pk_columns = vec![auto_pk.name.clone()];
                 table.set_primary_key(crate::schema::types::PrimaryKey {
-                    name: Some(format!("pk_{}", model_info.table_name)),
+                    name: Some(primary_key_name(config, &model_info.table_name, &pk_columns)),
                     columns: pk_columns,
                 });
             }
             
-            // Add created_at and updated_at columns if configured
-            if config.schema.add_created_at_column {
+            // Add created_at and updated_at columns if configured; `timestamps = false` opts a model out
+            let timestamps = model_info.flag("timestamps");
+            if timestamps.unwrap_or(config.schema.add_created_at_column) {
                 let column_exists = table.columns.iter().any(|c| c.name == "created_at");
                 
                 if !column_exists {
                     table.add_column(crate::schema::types::Column {
                         name: "created_at".to_string(),
-                        data_type: "TIMESTAMP WITH TIME ZONE".to_string(),
+                        data_type: timestamp_db_type(config, None),
                         nullable: false,
-                        default: Some("CURRENT_TIMESTAMP".to_string()),
+                        default: Some(current_timestamp_default(config)),
                         comment: Some("Record creation timestamp".to_string()),
                         is_unique: false,
                         is_generated: false,
                         generation_expression: None,
+                        charset: None,
+                        default_fn: None,
+                        storage: None,
+                        collation: None,
                     });
                 }
             }
             
-            if config.schema.add_updated_at_column {
+            if timestamps.unwrap_or(config.schema.add_updated_at_column) {
                 let column_exists = table.columns.iter().any(|c| c.name == "updated_at");
                 
                 if !column_exists {
                     table.add_column(crate::schema::types::Column {
                         name: "updated_at".to_string(),
-                        data_type: "TIMESTAMP WITH TIME ZONE".to_string(),
+                        data_type: timestamp_db_type(config, None),
                         nullable: false,
-                        default: Some("CURRENT_TIMESTAMP".to_string()),
+                        default: Some(current_timestamp_default(config)),
                         comment: Some("Record last update timestamp".to_string()),
                         is_unique: false,
                         is_generated: false,
                         generation_expression: None,
+                        charset: None,
+                        default_fn: None,
+                        storage: None,
+                        collation: None,
                     });
                 }
             }
             
+            if model_info.flag("audit_columns").unwrap_or(config.schema.add_audit_user_columns == Some(true)) {
+                add_audit_user_columns(&mut table, config)?;
+            }
+            
             // Add indexes for unique and foreign key columns
             for field in &model_info.fields {
                 // Add unique constraints
                 if field.unique {
-                    let index_name = format!("ix_{}_{}",
-                        model_info.table_name,
-                        field.name
+                    let index_name = object_name(
+                        config,
+                        config.naming.unique_pattern.as_deref().unwrap_or("ix_{table}_{columns}"),
+                        &model_info.table_name,
+                        std::slice::from_ref(&field.name),
+                        "uq",
                     );
                     
                     table.add_index(crate::schema::types::Index {
@@ -408,32 +1064,44 @@ impl ModelRegistry {
                         columns: vec![field.name.clone()],
                         is_unique: true,
                         method: Some("btree".to_string()),
+                        include: Vec::new(),
+                        expression: None,
+                        options: Default::default(),
+                        tablespace: None,
+                        operator_class: None,
                     });
                 }
                 
                 // Add foreign key constraints
                 if let Some(fk) = &field.foreign_key {
                     // Generate foreign key name
-                    let fk_name = crate::utils::get_foreign_key_name(
+                    let fk_name = object_name(
+                        config,
                         &config.naming.constraint_pattern,
                         &model_info.table_name,
-                        &field.name,
+                        std::slice::from_ref(&field.name),
+                        "fk",
                     );
                     
                     table.foreign_keys.push(crate::schema::types::ForeignKey {
                         name: fk_name,
                         columns: vec![field.name.clone()],
-                        ref_table: fk.ref_table.clone(),
+                        ref_table: resolve_reference(&fk.ref_table, config)?,
                         ref_columns: vec![fk.ref_column.clone()],
                         on_delete: fk.on_delete.clone(),
                         on_update: fk.on_update.clone(),
+                        deferrable: fk.deferrable.clone(),
+                        match_type: fk.match_type.clone(),
                     });
                     
                     // Add index for foreign key if configured
-                    if config.schema.index_foreign_keys {
-                        let index_name = format!("ix_{}_{}",
-                            model_info.table_name,
-                            field.name
+                    if model_info.flag("index_foreign_keys").unwrap_or(config.schema.index_foreign_keys) {
+                        let index_name = object_name(
+                            config,
+                            &config.naming.index_pattern,
+                            &model_info.table_name,
+                            std::slice::from_ref(&field.name),
+                            "ix",
                         );
                         
                         table.add_index(crate::schema::types::Index {
@@ -441,9 +1109,83 @@ impl ModelRegistry {
                             columns: vec![field.name.clone()],
                             is_unique: false,
                             method: Some("btree".to_string()),
+                            include: Vec::new(),
+                            expression: None,
+                            options: Default::default(),
+                            tablespace: None,
+                            operator_class: None,
                         });
                     }
                 }
+                
+                // Add a CHECK constraint for validation rules such as `min = 0` or `pattern = "..."`
+                if let Some(expression) = Dialect::from_driver(&config.database.driver)
+                    .and_then(|dialect| check_expression(field, dialect))
+                {
+                    let name = object_name(
+                        config,
+                        config.naming.check_pattern.as_deref().unwrap_or("ck_{table}_{column}"),
+                        &model_info.table_name,
+                        std::slice::from_ref(&field.name),
+                        "ck",
+                    );
+                    table.constraints.push(Constraint::check(name, &expression));
+                }
+            }
+            
+            for fk in &model_info.foreign_keys {
+                table.foreign_keys.push(crate::schema::types::ForeignKey {
+                    ref_table: resolve_reference(&fk.ref_table, config)?,
+                    ..fk.clone()
+                });
+            }
+            table.constraints.extend(model_info.constraints.iter().cloned());
+            warn_non_range_overlaps(&table);
+            for index in &model_info.indexes {
+                let mut index = index.clone();
+                if index.method.is_none() {
+                    let ranges = !index.columns.is_empty() && index.columns.iter().all(|name| {
+                        table.columns.iter().any(|column| column.name == *name && is_range_type(&column.data_type))
+                    });
+                    index.method = Some(if ranges { "gist" } else { "btree" }.to_string());
+                }
+                table.add_index(index);
+            }
+            
+            table.comment = model_info.attributes.get("comment").cloned();
+            table.options = TABLE_OPTIONS
+                .iter()
+                .filter_map(|key| model_info.attributes.get(*key).map(|value| (key.to_string(), value.clone())))
+                .collect();
+            table.tablespace = model_info.attributes.get("tablespace").cloned()
+                .or_else(|| config.schema.tablespace.clone());
+            if let Some(index_tablespace) = &config.schema.index_tablespace {
+                for index in table.indexes.iter_mut().filter(|index| index.tablespace.is_none()) {
+                    index.tablespace = Some(index_tablespace.clone());
+                }
+            }
+            
+            // Escape hatch for what SchemaSync does not model: the struct's statements, then
+            // each field's, with `{table}` and `{column}` filled in
+            let struct_sql = model_info.attributes.get("extra_sql_after_create").map(|sql| (sql, None));
+            let field_sql = model_info.fields.iter()
+                .filter_map(|field| field.attributes.get("extra_sql").map(|sql| (sql, Some(field.name.as_str()))));
+            table.extra_sql = struct_sql.into_iter().chain(field_sql)
+                .map(|(sql, column)| {
+                    sql.replace("{table}", &model_info.table_name)
+                        .replace("{column}", column.unwrap_or_default())
+                        .trim()
+                        .trim_end_matches(';')
+                        .to_string()
+                })
+                .filter(|sql| !sql.is_empty())
+                .collect();
+            
+            if config.database.driver == "clickhouse" {
+                clickhouse_table(&mut table);
+            }
+            if config.database.driver == "oracle" {
+                check_oracle_identifiers(&table, config)?;
             }
             
             schema.add_table(table);
@@ -470,31 +1212,135 @@ impl ModelRegistry {
             }
         }
         
-        // Default mappings
-        match rust_type {
-            "String" | "&str" => Ok("VARCHAR(255)".to_string()),
-            "i8" => Ok("SMALLINT".to_string()),
-            "i16" => Ok("SMALLINT".to_string()),
-            "i32" => Ok("INTEGER".to_string()),
-            "i64" => Ok("BIGINT".to_string()),
-            "u8" | "u16" | "u32" => Ok("INTEGER".to_string()),
-            "u64" => Ok("BIGINT".to_string()),
-            "f32" => Ok("REAL".to_string()),
-            "f64" => Ok("DOUBLE PRECISION".to_string()),
-            "bool" => Ok("BOOLEAN".to_string()),
-            t if t.contains("Vec<u8>") => Ok("BYTEA".to_string()),
-            t if t.contains("DateTime") => Ok("TIMESTAMP WITH TIME ZONE".to_string()),
-            t if t.contains("NaiveDateTime") => Ok("TIMESTAMP".to_string()),
-            t if t.contains("NaiveDate") => Ok("DATE".to_string()),
-            t if t.contains("Uuid") => Ok("UUID".to_string()),
-            t if t.contains("Decimal") => Ok("NUMERIC(20,6)".to_string()),
-            t if t.contains("Json") || t.contains("Value") => Ok("JSONB".to_string()),
+        // Default mappings, matched on the type's path rather than substrings so that
+        // e.g. `NaiveDateTime` or a user type named `MyValue` can't hit the wrong arm
+        let (path, inner) = split_type(rust_type);
+        
+        // Nullability is handled separately; map the wrapped type
+        if path == "Option" || path == "std::option::Option" {
+            if let Some(inner) = inner {
+                return self.map_type_to_db_type(&inner, config);
+            }
+        }
+        
+        let name = path.rsplit("::").next().unwrap_or(&path);
+        
+        // A transparent newtype (`#[schema_sync(transparent)]`) maps as the field it wraps
+        if let Some(model) = self.models.get(name).filter(|model| model.flag("transparent") == Some(true)) {
+            let field = &model.fields[0];
+            return match &field.db_type {
+                Some(db_type) => Ok(db_type.clone()),
+                None => apply_type_parameters(self.map_type_to_db_type(&field.rust_type, config)?, field, config),
+            };
+        }
+        
+        // A `#[schema_sync]` enum maps following its storage mode
+        if let Some(model) = self.models.get(name).filter(|model| model.flag("enum") == Some(true)) {
+            return Ok(match enum_storage(model, config)? {
+                "int" => "INTEGER".to_string(),
+                "native" => model.table_name.clone(),
+                "native_mysql" => {
+                    let labels: Vec<String> = model.variants.iter()
+                        .map(|variant| format!("'{}'", variant.label.replace('\'', "''")))
+                        .collect();
+                    format!("ENUM({})", labels.join(","))
+                }
+                _ => "VARCHAR(255)".to_string(),
+            });
+        }
+        
+        match (name, path.as_str()) {
+            ("String", _) | ("&str", _) | ("str", _) => Ok("VARCHAR(255)".to_string()),
+            ("i8", _) => Ok("SMALLINT".to_string()),
+            ("i16", _) => Ok("SMALLINT".to_string()),
+            ("i32", _) => Ok("INTEGER".to_string()),
+            ("i64", _) => Ok("BIGINT".to_string()),
+            ("u8", _) | ("u16", _) | ("u32", _) => Ok("INTEGER".to_string()),
+            ("u64", _) => Ok("BIGINT".to_string()),
+            ("f32", _) => Ok("REAL".to_string()),
+            ("f64", _) => Ok("DOUBLE PRECISION".to_string()),
+            ("bool", _) => Ok("BOOLEAN".to_string()),
+            ("Vec", _) if inner.as_deref() == Some("u8") => Ok("BYTEA".to_string()),
+            
+            // chrono
+            ("NaiveDateTime", _) => Ok(timestamp_db_type(config, Some(false))),
+            ("DateTime", _) => Ok(timestamp_db_type(config, None)),
+            ("NaiveDate", _) => Ok("DATE".to_string()),
+            ("NaiveTime", _) => Ok("TIME".to_string()),
+            
+            // time
+            ("OffsetDateTime", _) => Ok(timestamp_db_type(config, None)),
+            ("PrimitiveDateTime", _) => Ok(timestamp_db_type(config, Some(false))),
+            ("Date", _) => Ok("DATE".to_string()),
+            ("Time", _) => Ok("TIME".to_string()),
+            
+            ("Money", _) => Ok(money_db_type(config)),
+            ("Vector", _) => vector_db_type(None, rust_type, config),
+            
+            // sqlx's PgRange and std::ops::Range
+            ("PgRange", _) | ("Range", _) if inner.is_some() => {
+                range_db_type(inner.as_deref().unwrap_or_default(), rust_type, config)
+            }
+            
+            // std::time, chrono, and time durations
+            ("Duration", _) | ("TimeDelta", _) => duration_db_type(config),
+            
+            ("Uuid", _) => Ok("UUID".to_string()),
+            ("Ulid", _) => Ok(ulid_db_type(config)),
+            ("Snowflake", _) | ("SnowflakeId", _) => Ok("BIGINT".to_string()),
+            
+            // rust_decimal has at most 28 significant digits; bigdecimal is unbounded
+            ("Decimal", _) => Ok("NUMERIC(20,6)".to_string()),
+            ("BigDecimal", _) => Ok("NUMERIC".to_string()),
+            
+            // String-to-string maps are hstore on Postgres; other maps, and other databases, use JSON
+            ("HashMap", _) | ("BTreeMap", _) if inner.is_some() => {
+                let compact: String = rust_type.chars().filter(|c| !c.is_whitespace()).collect();
+                let text_map = compact.ends_with("<String,String>") || compact.ends_with("<String,Option<String>>");
+                if text_map && config.database.driver == "postgres" {
+                    Ok("HSTORE".to_string())
+                } else {
+                    Ok(json_db_type(config))
+                }
+            }
+            ("Json", _) | ("JsonValue", _) => Ok(json_db_type(config)),
+            ("Value", "serde_json::Value") | ("Value", "Value") => Ok(json_db_type(config)),
+            
             _ => Err(Error::TypeMappingError(format!(
                 "No mapping found for Rust type: {}", rust_type
             ))),
         }
     }
     
+    /// Column default for `default_variant = "..."` on an enum field, stored as the enum is
+    fn variant_default(&self, field: &FieldDefinition, variant_name: &str, config: &Config) -> Result<String> {
+        let path = match split_type(&field.rust_type) {
+            (path, Some(wrapped)) if path == "Option" || path == "std::option::Option" => split_type(&wrapped).0,
+            (path, _) => path,
+        };
+        let name = path.rsplit("::").next().unwrap_or(&path);
+        
+        let model = self.models.get(name).filter(|model| model.flag("enum") == Some(true)).ok_or_else(|| {
+            Error::TypeMappingError(format!(
+                "default_variant applies to #[schema_sync] enum fields, but field '{}' is {}",
+                field.name, field.rust_type
+            ))
+        })?;
+        let variant = model.variants.iter()
+            .find(|variant| variant.name == variant_name || variant.label == variant_name)
+            .ok_or_else(|| {
+                Error::TypeMappingError(format!(
+                    "default_variant '{}' on field '{}' is not a variant of {}",
+                    variant_name, field.name, model.name
+                ))
+            })?;
+        
+        Ok(match enum_storage(model, config)? {
+            "int" => variant.discriminant.to_string(),
+            _ => format!("'{}'", variant.label.replace('\'', "''")),
+        })
+    }
+    
     /// Get all registered models
     pub fn get_models(&self) -> &HashMap<String, ModelInfo> {
         &self.models
@@ -504,4 +1350,967 @@ impl ModelRegistry {
     pub fn get_model(&self, name: &str) -> Option<&ModelInfo> {
         self.models.get(name)
     }
-}
\ No newline at end of file
+}
+
+/// The types `items` (and their inline modules) implement `SchemaSyncModel` for, each with the
+/// `#[schema_sync(...)]` attribute rebuilt from its `SCHEMA_SYNC_OPTIONS` constant, if any
+fn model_impls(items: &[Item]) -> HashMap<String, Vec<Attribute>> {
+    let mut names = HashSet::new();
+    let mut options = HashMap::new();
+    collect_model_impls(items, &mut names, &mut options);
+    
+    names
+        .into_iter()
+        .map(|name| {
+            let attrs = options
+                .remove(&name)
+                .and_then(|options: String| {
+                    let attr = format!("#[schema_sync({})]", options);
+                    syn::parse::Parser::parse_str(Attribute::parse_outer, &attr).ok()
+                })
+                .unwrap_or_default();
+            (name, attrs)
+        })
+        .collect()
+}
+
+fn collect_model_impls(items: &[Item], names: &mut HashSet<String>, options: &mut HashMap<String, String>) {
+    for item in items {
+        match item {
+            Item::Impl(item_impl) => {
+                let syn::Type::Path(self_ty) = item_impl.self_ty.as_ref() else {
+                    continue;
+                };
+                let Some(segment) = self_ty.path.segments.last() else {
+                    continue;
+                };
+                
+                let is_model_impl = item_impl
+                    .trait_
+                    .as_ref()
+                    .and_then(|(_, path, _)| path.segments.last())
+                    .is_some_and(|segment| segment.ident == "SchemaSyncModel");
+                if is_model_impl {
+                    names.insert(segment.ident.to_string());
+                    continue;
+                }
+                
+                // The inherent impl `#[schema_sync]` leaves the options it was given in
+                for impl_item in &item_impl.items {
+                    if let syn::ImplItem::Const(item_const) = impl_item {
+                        if let (true, syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(value), .. })) =
+                            (item_const.ident == "SCHEMA_SYNC_OPTIONS", &item_const.expr)
+                        {
+                            options.insert(segment.ident.to_string(), value.value());
+                        }
+                    }
+                }
+            }
+            Item::Mod(module) => {
+                if let Some((_, items)) = &module.content {
+                    collect_model_impls(items, names, options);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Limits on one scan, so a misconfigured path fails quickly instead of running on
+struct ScanBudget {
+    started: Instant,
+    timeout: Duration,
+    max_files: usize,
+    files: usize,
+}
+
+impl ScanBudget {
+    fn new(config: &ModelsConfig) -> Self {
+        Self {
+            started: Instant::now(),
+            timeout: Duration::from_secs(config.scan_timeout_seconds.unwrap_or(120)),
+            max_files: config.max_files.unwrap_or(100_000),
+            files: 0,
+        }
+    }
+    
+    /// Count a visited path, failing once either limit is exceeded
+    fn visit(&mut self, path: &Path) -> Result<()> {
+        if path.is_file() {
+            self.files += 1;
+            if self.files > self.max_files {
+                return Err(Error::ModelRegistrationError(format!(
+                    "scan stopped at {} after visiting more than {} files; narrow models.paths or raise models.max_files",
+                    path.display(),
+                    self.max_files
+                )));
+            }
+        }
+        if self.started.elapsed() >= self.timeout {
+            return Err(Error::ModelRegistrationError(format!(
+                "scan stopped at {} after {} seconds; narrow models.paths or raise models.scan_timeout_seconds",
+                path.display(),
+                self.timeout.as_secs()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The child and ancestor of a symbolic link loop reported while walking
+fn find_loop(error: &ignore::Error) -> Option<(&Path, &Path)> {
+    match error {
+        ignore::Error::Loop { ancestor, child } => Some((child, ancestor)),
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => find_loop(err),
+        ignore::Error::Partial(errors) => errors.iter().find_map(find_loop),
+        _ => None,
+    }
+}
+
+fn symlink_loop(child: &Path, ancestor: &Path) -> Error {
+    Error::ModelRegistrationError(format!(
+        "symbolic link loop: {} leads back to {}; exclude it or remove the link",
+        child.display(),
+        ancestor.display()
+    ))
+}
+
+/// Build the column injected by `schema.auto_primary_key`
+fn auto_primary_key_column(auto_pk: &AutoPrimaryKeyConfig, config: &Config) -> Result<Column> {
+    let ulid_type = ulid_db_type(config);
+    let (data_type, default) = match (auto_pk.key_type.as_str(), config.database.driver.as_str()) {
+        ("Uuid", "postgres") => ("UUID", Some("gen_random_uuid()")),
+        ("Uuid", "mysql") => ("CHAR(36)", Some("(UUID())")),
+        ("Uuid", "oracle") => ("UUID", Some("SYS_GUID()")),
+        // SQLite has no UUID function; the application supplies the value
+        ("Uuid", _) => ("TEXT", None),
+        ("i64", "postgres" | "oracle") => ("BIGINT GENERATED BY DEFAULT AS IDENTITY", None),
+        ("i64", "mysql") => ("BIGINT AUTO_INCREMENT", None),
+        // An INTEGER PRIMARY KEY aliases the rowid, which SQLite assigns automatically
+        ("i64", _) => ("INTEGER", None),
+        // Distributed IDs are generated by the application
+        ("Ulid", _) => (ulid_type.as_str(), None),
+        ("Snowflake", _) => ("BIGINT", None),
+        (other, _) => {
+            return Err(Error::ConfigError(format!(
+                "Unsupported auto_primary_key type '{}' (expected \"Uuid\", \"i64\", \"Ulid\", or \"Snowflake\")",
+                other
+            )));
+        }
+    };
+    
+    Ok(Column {
+        name: auto_pk.name.clone(),
+        data_type: data_type.to_string(),
+        nullable: false,
+        default: default.map(str::to_string),
+        comment: None,
+        is_unique: false,
+        is_generated: false,
+        generation_expression: None,
+        charset: None,
+        default_fn: None,
+        storage: None,
+        collation: None,
+    })
+}
+
+/// Adapt a table for ClickHouse, which reports its own type names and has no keys to sync
+///
+/// Columns are declared in ClickHouse types up front so they compare equal to what
+/// `system.columns` reports. Foreign keys, secondary indexes, and uniqueness can't be
+/// enforced there, so they are dropped with a warning.
+fn clickhouse_table(table: &mut Table) {
+    for column in &mut table.columns {
+        column.data_type = clickhouse_type(&column.data_type);
+        // Arrays and maps can't be Nullable; ClickHouse stores them empty instead
+        if column.data_type.starts_with("Array(") || column.data_type.starts_with("Map(") {
+            column.nullable = false;
+        }
+        if column.is_unique {
+            tracing::warn!("ClickHouse cannot enforce uniqueness; '{}.{}' is not unique there", table.name, column.name);
+            column.is_unique = false;
+        }
+    }
+    
+    for index in table.indexes.drain(..) {
+        tracing::warn!("ClickHouse has no secondary B-tree indexes; {} on '{}' was not created", index.name, table.name);
+    }
+    for fk in table.foreign_keys.drain(..) {
+        tracing::warn!("ClickHouse has no foreign keys; {} on '{}' was not created", fk.name, table.name);
+    }
+}
+
+/// Check that a table's names fit Oracle's identifier limit
+///
+/// Generated index and constraint names are shortened to fit, but table, column, and
+/// explicitly given names are used as written, so a name that is too long is an error.
+fn check_oracle_identifiers(table: &Table, config: &Config) -> Result<()> {
+    // A malformed oracle.version is reported here, before any SQL is generated
+    config.database.oracle_release()?;
+    let limit = max_identifier_length(config);
+    let names = std::iter::once(&table.name)
+        .chain(table.columns.iter().map(|column| &column.name))
+        .chain(table.primary_key.iter().filter_map(|pk| pk.name.as_ref()))
+        .chain(table.indexes.iter().map(|index| &index.name))
+        .chain(table.foreign_keys.iter().map(|fk| &fk.name))
+        .chain(table.constraints.iter().map(|constraint| &constraint.name));
+    
+    for name in names {
+        if name.len() > limit {
+            let hint = if limit < get_max_identifier_length("oracle_12c") { " or raise oracle.version" } else { "" };
+            let subject = if *name == table.name {
+                format!("table name '{}'", name)
+            } else {
+                format!("'{}' in table '{}'", name, table.name)
+            };
+            return Err(Error::ValidationError(format!(
+                "{} is {} characters, but Oracle {} allows {}; rename it{}",
+                subject,
+                name.len(),
+                config.database.oracle.as_ref().and_then(|oracle| oracle.version.as_deref()).unwrap_or("19"),
+                limit,
+                hint
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// How an enum is stored: its `#[schema_sync(storage = "...")]`, else `type_mapping.enum_storage`
+///
+/// "native" is a Postgres enum type, reported as "native_mysql" for a MySQL `ENUM` column;
+/// SQLite, ClickHouse, and Oracle have neither, so they store labels as strings.
+fn enum_storage(model: &ModelInfo, config: &Config) -> Result<&'static str> {
+    let storage = model.attributes.get("storage").or(config.type_mapping.enum_storage.as_ref());
+    match (storage.map(String::as_str).unwrap_or("string"), config.database.driver.as_str()) {
+        ("string", _) | ("native", "sqlite" | "clickhouse" | "oracle") => Ok("string"),
+        ("int", _) => Ok("int"),
+        ("native", "mysql") => Ok("native_mysql"),
+        ("native", _) => Ok("native"),
+        (other, _) => Err(Error::ConfigError(format!(
+            "unknown enum storage '{}' for {}; expected \"string\", \"int\", or \"native\"",
+            other, model.name
+        ))),
+    }
+}
+
+/// Column type for JSON values, following `type_mapping.json_format`
+fn json_db_type(config: &Config) -> String {
+    match config.type_mapping.json_format.as_deref() {
+        Some("text") => "JSON".to_string(),
+        _ => "JSONB".to_string(),
+    }
+}
+
+/// Postgres range type for a range of `element` values
+fn range_db_type(element: &str, rust_type: &str, config: &Config) -> Result<String> {
+    if config.database.driver != "postgres" {
+        return Err(Error::TypeMappingError(format!(
+            "range types are PostgreSQL-only; {} has no {} mapping",
+            rust_type, config.database.driver
+        )));
+    }
+    
+    let element = split_type(element).0;
+    let range = match element.rsplit("::").next().unwrap_or(&element) {
+        "i32" => "INT4RANGE",
+        "i64" => "INT8RANGE",
+        "Decimal" | "BigDecimal" => "NUMRANGE",
+        "NaiveDateTime" | "PrimitiveDateTime" => "TSRANGE",
+        "DateTime" | "OffsetDateTime" => "TSTZRANGE",
+        "NaiveDate" | "Date" => "DATERANGE",
+        _ => {
+            return Err(Error::TypeMappingError(format!(
+                "No range type found for Rust type: {}",
+                rust_type
+            )))
+        }
+    };
+    Ok(range.to_string())
+}
+
+/// Whether a column type is a Postgres range or multirange, such as `tstzrange`
+fn is_range_type(data_type: &str) -> bool {
+    data_type.trim().to_uppercase().ends_with("RANGE")
+}
+
+/// Warn about exclusion constraint elements that test overlap (`&&`) on columns that can't overlap
+///
+/// Overlap is defined for ranges, arrays, and geometric types; on anything else Postgres
+/// rejects the constraint when the migration runs.
+fn warn_non_range_overlaps(table: &Table) {
+    for constraint in table.constraints.iter().filter(|c| c.constraint_type == "EXCLUDE") {
+        let elements = constraint.definition.find('(').map(|start| &constraint.definition[start + 1..]).unwrap_or_default();
+        for element in elements.trim_end_matches(')').split(',') {
+            let mut parts = element.split_whitespace();
+            let (Some(name), Some(operator)) = (parts.next(), parts.last()) else {
+                continue;
+            };
+            let Some(column) = table.columns.iter().find(|column| column.name == name.trim_matches('"')) else {
+                continue;
+            };
+            
+            let data_type = column.data_type.to_uppercase();
+            let overlaps = is_range_type(&data_type)
+                || data_type.ends_with("[]")
+                || matches!(data_type.as_str(), "BOX" | "CIRCLE" | "POLYGON" | "INET" | "CIDR");
+            if operator == "&&" && !overlaps {
+                tracing::warn!(
+                    "exclusion constraint {} tests {} WITH && but {}.{} is {}, not a range",
+                    constraint.name, column.name, table.name, column.name, column.data_type
+                );
+            }
+        }
+    }
+}
+
+/// pgvector column type, with `dims` dimensions when given
+fn vector_db_type(dims: Option<&str>, rust_type: &str, config: &Config) -> Result<String> {
+    if config.database.driver != "postgres" {
+        return Err(Error::TypeMappingError(format!(
+            "vector columns need PostgreSQL with pgvector; {} has no {} mapping",
+            rust_type, config.database.driver
+        )));
+    }
+    
+    Ok(match dims {
+        Some(dims) => format!("VECTOR({})", dims),
+        None => "VECTOR".to_string(),
+    })
+}
+
+/// Column type for money amounts, following `type_mapping.money_precision` / `money_scale`
+fn money_db_type(config: &Config) -> String {
+    format!(
+        "NUMERIC({},{})",
+        config.type_mapping.money_precision.unwrap_or(19),
+        config.type_mapping.money_scale.unwrap_or(4)
+    )
+}
+
+/// Whether a field is typed `Money` (optionally wrapped in `Option`)
+fn is_money_type(rust_type: &str) -> bool {
+    let path = match split_type(rust_type) {
+        (path, Some(wrapped)) if path == "Option" || path == "std::option::Option" => split_type(&wrapped).0,
+        (path, _) => path,
+    };
+    path.rsplit("::").next() == Some("Money")
+}
+
+/// Column type for durations, following `type_mapping.duration_format`
+fn duration_db_type(config: &Config) -> Result<String> {
+    let format = config.type_mapping.duration_format.as_deref().unwrap_or("native");
+    match (format, config.database.driver.as_str()) {
+        ("seconds", _) | ("native", "sqlite") => Ok("BIGINT".to_string()),
+        ("native", "mysql") => Ok("TIME".to_string()),
+        ("native", _) => Ok("INTERVAL".to_string()),
+        (other, _) => Err(Error::ConfigError(format!(
+            "unknown duration format '{}'; expected \"native\" or \"seconds\"",
+            other
+        ))),
+    }
+}
+
+/// Column type for `ulid::Ulid`, following `type_mapping.ulid_format`
+fn ulid_db_type(config: &Config) -> String {
+    if config.type_mapping.ulid_format.as_deref() != Some("binary") {
+        return "CHAR(26)".to_string();
+    }
+    
+    match config.database.driver.as_str() {
+        "mysql" => "BINARY(16)".to_string(),
+        "sqlite" => "BLOB".to_string(),
+        "oracle" => "RAW(16)".to_string(),
+        _ => "BYTEA".to_string(),
+    }
+}
+
+/// Add created_by/updated_by columns, with foreign keys to the configured users table
+fn add_audit_user_columns(table: &mut Table, config: &Config) -> Result<()> {
+    let data_type = config.schema.audit_user_type.clone().unwrap_or_else(|| "BIGINT".to_string());
+    let references = config.schema.audit_user_references.as_deref().unwrap_or("users.id");
+    let (ref_table, ref_column) = references.rsplit_once('.').ok_or_else(|| {
+        Error::ConfigError(format!(
+            "audit_user_references must be \"table.column\", got '{}'",
+            references
+        ))
+    })?;
+    
+    let audit_columns = [
+        ("created_by", "User who created the record"),
+        ("updated_by", "User who last updated the record"),
+    ];
+    
+    for (column_name, comment) in audit_columns {
+        if table.columns.iter().any(|c| c.name == column_name) {
+            continue;
+        }
+        
+        // Nullable, since changes can come from outside any user session
+        table.add_column(Column {
+            name: column_name.to_string(),
+            data_type: data_type.clone(),
+            nullable: true,
+            default: None,
+            comment: Some(comment.to_string()),
+            is_unique: false,
+            is_generated: false,
+            generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
+        });
+        
+        table.foreign_keys.push(crate::schema::types::ForeignKey {
+            name: object_name(config, &config.naming.constraint_pattern, &table.name, &[column_name.to_string()], "fk"),
+            columns: vec![column_name.to_string()],
+            ref_table: resolve_reference(ref_table, config)?,
+            ref_columns: vec![ref_column.to_string()],
+            on_delete: Some("SET NULL".to_string()),
+            on_update: None,
+            deferrable: None,
+            match_type: None,
+        });
+        
+        if config.schema.index_foreign_keys {
+            table.add_index(crate::schema::types::Index {
+                name: object_name(config, &config.naming.index_pattern, &table.name, &[column_name.to_string()], "ix"),
+                columns: vec![column_name.to_string()],
+                is_unique: false,
+                method: Some("btree".to_string()),
+                include: Vec::new(),
+                expression: None,
+                options: Default::default(),
+                tablespace: None,
+                operator_class: None,
+            });
+        }
+    }
+    
+    Ok(())
+}
+
+/// Timestamp column type for the configured driver, precision, and time zone handling
+///
+/// `with_time_zone` of `None` follows `type_mapping.timestamp_type`.
+fn timestamp_db_type(config: &Config, with_time_zone: Option<bool>) -> String {
+    let with_time_zone = with_time_zone
+        .unwrap_or(config.type_mapping.timestamp_type.as_deref() != Some("timestamp"));
+    let precision = config.type_mapping.timestamp_precision;
+    
+    match config.database.driver.as_str() {
+        // MySQL's TIMESTAMP ends in 2038, so both kinds are stored as DATETIME in UTC
+        "mysql" => format!("DATETIME({})", precision.unwrap_or(6)),
+        "sqlite" => "TIMESTAMP".to_string(),
+        "clickhouse" if with_time_zone => format!("DateTime64({}, 'UTC')", precision.unwrap_or(6)),
+        "clickhouse" => format!("DateTime64({})", precision.unwrap_or(6)),
+        _ => {
+            let base = match precision {
+                Some(p) => format!("TIMESTAMP({})", p),
+                None => "TIMESTAMP".to_string(),
+            };
+            if with_time_zone {
+                format!("{} WITH TIME ZONE", base)
+            } else {
+                base
+            }
+        }
+    }
+}
+
+/// Default for created_at/updated_at; MySQL needs the column's precision repeated
+fn current_timestamp_default(config: &Config) -> String {
+    let precision = config.type_mapping.timestamp_precision.unwrap_or(6);
+    match config.database.driver.as_str() {
+        "mysql" => format!("CURRENT_TIMESTAMP({})", precision),
+        "clickhouse" => format!("now64({})", precision),
+        _ => "CURRENT_TIMESTAMP".to_string(),
+    }
+}
+
+/// Split a Rust type into its path and first generic argument, ignoring whitespace
+///
+/// `"Option < chrono :: NaiveDate >"` becomes `("Option", Some("chrono::NaiveDate"))`.
+fn split_type(rust_type: &str) -> (String, Option<String>) {
+    let compact: String = rust_type.chars().filter(|c| !c.is_whitespace()).collect();
+    
+    match (compact.find('<'), compact.rfind('>')) {
+        (Some(open), Some(close)) if open < close => {
+            let args = &compact[open + 1..close];
+            // Only the first argument matters for the types mapped here
+            let mut depth = 0;
+            let first_end = args
+                .char_indices()
+                .find(|&(_, c)| {
+                    match c {
+                        '<' => depth += 1,
+                        '>' => depth -= 1,
+                        ',' if depth == 0 => return true,
+                        _ => {}
+                    }
+                    false
+                })
+                .map(|(i, _)| i)
+                .unwrap_or(args.len());
+            (compact[..open].to_string(), Some(args[..first_end].to_string()))
+        }
+        _ => (compact, None),
+    }
+}
+
+/// Check a foreign key's target table and drop a redundant qualification
+///
+/// Postgres targets may be `schema.table` and MySQL targets `database.table`; anything
+/// further qualified (or any qualified target on SQLite) would cross databases, which
+/// foreign keys cannot do. A qualifier naming the configured schema is removed so the
+/// reference compares equal to what the analyzer reports.
+fn resolve_reference(ref_table: &str, config: &Config) -> Result<String> {
+    let parts: Vec<&str> = ref_table.split('.').collect();
+    let max_parts = match config.database.driver.as_str() {
+        "sqlite" => 1,
+        _ => 2,
+    };
+    
+    if parts.len() > max_parts {
+        let target_database = parts[..parts.len() - max_parts].join(".");
+        return Err(Error::ValidationError(format!(
+            "foreign key to '{}' targets database '{}'; {} foreign keys cannot reference another database",
+            ref_table, target_database, config.database.driver
+        )));
+    }
+    
+    match (parts.as_slice(), config.database.schema.as_deref()) {
+        ([schema, table], Some(current)) if *schema == current => Ok(table.to_string()),
+        _ => Ok(ref_table.to_string()),
+    }
+}
+
+/// Name an index or constraint from a `[naming]` pattern, within the dialect's identifier limit
+fn object_name(config: &Config, pattern: &str, table: &str, columns: &[String], kind: &str) -> String {
+    let parts = NameParts {
+        table,
+        columns,
+        kind,
+        schema: config.database.schema.as_deref(),
+    };
+    render_name(pattern, &parts, max_identifier_length(config))
+}
+
+/// Longest identifier the configured database accepts
+///
+/// Oracle allows 128 characters from 12.2 and 30 before that.
+fn max_identifier_length(config: &Config) -> usize {
+    match config.database.driver.as_str() {
+        "oracle" if config.database.oracle_release().map_or(true, |release| release >= (12, 2)) => {
+            get_max_identifier_length("oracle_12c")
+        }
+        driver => get_max_identifier_length(driver),
+    }
+}
+
+fn primary_key_name(config: &Config, table: &str, columns: &[String]) -> String {
+    let pattern = config.naming.primary_key_pattern.as_deref().unwrap_or("pk_{table}");
+    object_name(config, pattern, table, columns, "pk")
+}
+
+/// Struct-level `#[schema_sync(...)]` options of a model
+struct StructAttributes {
+    foreign_keys: Vec<ForeignKey>,
+    constraints: Vec<Constraint>,
+    indexes: Vec<Index>,
+    /// Plain `key = value` options such as `table`, `tablespace`, or `timestamps = false`
+    options: HashMap<String, String>,
+}
+
+/// Struct options passed through to the database as table options, for the dialects
+/// that have them
+const TABLE_OPTIONS: [&str; 13] = [
+    "unlogged",
+    "access_method",
+    "fillfactor",
+    "autovacuum_enabled",
+    "parallel_workers",
+    "engine",
+    "compression",
+    "row_format",
+    "key_block_size",
+    "strict",
+    "without_rowid",
+    "order_by",
+    "partition_by",
+];
+
+/// Index options passed through to the database (`WITH (...)` on Postgres, table-option
+/// style on MySQL)
+const INDEX_STORAGE_OPTIONS: [&str; 11] = [
+    "fillfactor",
+    "fastupdate",
+    "gin_pending_list_limit",
+    "deduplicate_items",
+    "buffering",
+    "pages_per_range",
+    "autosummarize",
+    "key_block_size",
+    // pgvector: IVFFlat lists, HNSW graph parameters
+    "lists",
+    "m",
+    "ef_construction",
+];
+
+/// Options inside a struct-level `name(key = "value", flag = true, list = ["a", "b"])` attribute
+#[derive(Default)]
+struct NestedOptions {
+    values: HashMap<String, String>,
+    lists: HashMap<String, Vec<String>>,
+}
+
+fn parse_nested_options(meta: &syn::meta::ParseNestedMeta) -> syn::Result<NestedOptions> {
+    let mut options = NestedOptions::default();
+    
+    meta.parse_nested_meta(|inner| {
+        let key = inner.path.to_token_stream().to_string();
+        match inner.value()?.parse::<syn::Expr>()? {
+            syn::Expr::Lit(syn::ExprLit { lit, .. }) => {
+                let value = match lit {
+                    syn::Lit::Str(value) => value.value(),
+                    syn::Lit::Bool(value) => value.value.to_string(),
+                    syn::Lit::Int(value) => value.base10_digits().to_string(),
+                    _ => return Err(inner.error(format!("unsupported value for `{}`", key))),
+                };
+                options.values.insert(key, value);
+            }
+            syn::Expr::Array(array) => {
+                let mut list = Vec::new();
+                for element in array.elems {
+                    match element {
+                        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(value), .. }) => list.push(value.value()),
+                        _ => return Err(inner.error(format!("`{}` must list string literals", key))),
+                    }
+                }
+                options.lists.insert(key, list);
+            }
+            _ => return Err(inner.error(format!("unsupported value for `{}`", key))),
+        }
+        Ok(())
+    })?;
+    
+    Ok(options)
+}
+
+/// Parse a foreign key target: `"table(col_a, col_b)"` or `"table.column"`
+fn parse_references(references: &str) -> Option<(String, Vec<String>)> {
+    let references = references.trim();
+    
+    if let Some((table, rest)) = references.split_once('(') {
+        let columns: Vec<String> = rest
+            .strip_suffix(')')?
+            .split(',')
+            .map(|column| column.trim().to_string())
+            .filter(|column| !column.is_empty())
+            .collect();
+        return Some((table.trim().to_string(), columns));
+    }
+    
+    let (table, column) = references.rsplit_once('.')?;
+    Some((table.to_string(), vec![column.to_string()]))
+}
+
+/// The name given by an option such as `rename = "userId"` in `#[<path>(...)]`, e.g. `#[serde(...)]`
+///
+/// For `rename(serialize = "...", deserialize = "...")`, the serialize name is used.
+fn attribute_rename(attrs: &[Attribute], path: &str, key: &str) -> Option<String> {
+    let mut name = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(path)) {
+        // Options this doesn't read (`default`, `with = "..."`, ...) are skipped over
+        let _ = attr.parse_nested_meta(|meta| {
+            let wanted = meta.path.is_ident(key);
+            if meta.input.peek(syn::Token![=]) {
+                let value = meta.value()?.parse::<syn::Expr>()?;
+                if let (true, syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(value), .. })) = (wanted, value) {
+                    name = Some(value.value());
+                }
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|inner| {
+                    let value = inner.value()?.parse::<syn::LitStr>()?;
+                    if wanted && inner.path.is_ident("serialize") {
+                        name = Some(value.value());
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        });
+    }
+    name
+}
+
+/// A snake_case field name as serde's `rename_all = rule` spells it; `None` for unknown rules
+fn serde_case(field_name: &str, rule: &str) -> Option<String> {
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        chars.next().map(|first| first.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+    };
+    let pascal: String = field_name.split('_').map(capitalize).collect();
+    
+    Some(match rule {
+        "lowercase" | "snake_case" => field_name.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field_name.to_uppercase(),
+        "PascalCase" => pascal,
+        "camelCase" => {
+            let mut chars = pascal.chars();
+            chars.next().map(|first| first.to_lowercase().chain(chars).collect()).unwrap_or_default()
+        }
+        "kebab-case" => field_name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field_name.replace('_', "-").to_uppercase(),
+        _ => return None,
+    })
+}
+
+/// An enum variant name as a `rename_all = rule` spells it; `None` for unknown rules
+///
+/// serde and sqlx apply `lowercase` and `UPPERCASE` to the whole name, so `InProgress`
+/// becomes `inprogress`, while the other rules split it into words.
+fn variant_case(variant: &str, rule: &str) -> Option<String> {
+    match rule {
+        "lowercase" => Some(variant.to_lowercase()),
+        "UPPERCASE" => Some(variant.to_uppercase()),
+        "PascalCase" => Some(variant.to_string()),
+        _ => serde_case(&apply_naming_convention(variant, "snake_case"), rule),
+    }
+}
+
+/// Value of an integer literal expression such as `3` or `-1`
+fn integer_literal(expr: &syn::Expr) -> Option<i64> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(value), .. }) => value.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => integer_literal(expr).map(|value| -value),
+        _ => None,
+    }
+}
+
+/// Read a string field attribute such as `deferrable = "initially_deferred"`
+fn string_attribute(attr_str: &str, key: &str) -> Option<String> {
+    let pattern = Regex::new(&format!(r#"\b{}\s*=\s*"([^"]*)""#, key)).ok()?;
+    Some(pattern.captures(attr_str)?.get(1)?.as_str().to_string())
+}
+
+/// Read an `on_delete` / `on_update` attribute as a SQL referential action
+fn referential_action_attribute(attr_str: &str, key: &str, field_name: &str) -> Option<String> {
+    let value = string_attribute(attr_str, key)?;
+    let action = normalize_referential_action(&value);
+    if action.is_none() {
+        tracing::warn!("Ignoring unknown {} action '{}' on {}", key, value, field_name);
+    }
+    action
+}
+
+/// Read a boolean attribute such as `pluralize = false`
+fn bool_attribute(attr_str: &str, key: &str) -> Option<bool> {
+    let pattern = Regex::new(&format!(r"\b{}\s*=\s*(true|false)\b", key)).ok()?;
+    pattern.captures(attr_str)?.get(1)?.as_str().parse().ok()
+}
+
+/// Read an integer field attribute such as `length = 320`
+fn numeric_attribute(attr_str: &str, key: &str) -> Option<u32> {
+    let pattern = Regex::new(&format!(r"\b{}\s*=\s*(\d+)", key)).ok()?;
+    pattern.captures(attr_str)?.get(1)?.as_str().parse().ok()
+}
+
+/// Read a numeric bound such as `min = -5` or `max = 99.5`, as written
+fn bound_attribute(attr_str: &str, key: &str) -> Option<String> {
+    let pattern = Regex::new(&format!(r"\b{}\s*=\s*(-?)\s*(\d+(?:\.\d+)?)", key)).ok()?;
+    let captures = pattern.captures(attr_str)?;
+    Some(format!("{}{}", captures.get(1)?.as_str(), captures.get(2)?.as_str()))
+}
+
+/// Arguments of a validator rule such as `range(min = 0, max = 100)`
+fn rule_arguments(attr_str: &str, rule: &str) -> Option<String> {
+    let pattern = Regex::new(&format!(r"\b{}\s*\(([^)]*)\)", rule)).ok()?;
+    Some(pattern.captures(attr_str)?.get(1)?.as_str().to_string())
+}
+
+/// CHECK expression enforcing a field's `min` / `max` / `min_length` / `max_length` / `pattern` rules
+///
+/// SQLite has no built-in regular expressions, so patterns are left out there with a warning.
+/// Oracle column names are quoted, as the generator declares them.
+fn check_expression(field: &FieldDefinition, dialect: Dialect) -> Option<String> {
+    let column = match dialect {
+        Dialect::Oracle => format!("\"{}\"", field.name),
+        _ => field.name.clone(),
+    };
+    let mut conditions = Vec::new();
+    
+    for (key, operator) in [("min", ">="), ("max", "<=")] {
+        if let Some(value) = field.attributes.get(key) {
+            // Postgres reports negative constants quoted ('-5'::integer), so write them that way
+            let value = match dialect {
+                Dialect::Postgres if value.starts_with('-') => format!("'{}'", value),
+                _ => value.clone(),
+            };
+            conditions.push(format!("{} {} {}", column, operator, value));
+        }
+    }
+    for (key, operator) in [("min_length", ">="), ("max_length", "<=")] {
+        if let Some(value) = field.attributes.get(key) {
+            conditions.push(format!("{}({}) {} {}", dialect.char_length(), column, operator, value));
+        }
+    }
+    // Integer-stored bitflags can't have bits beyond their width set
+    if let Some(bits) = field.attributes.get("bits").and_then(|bits| bits.parse::<u32>().ok()) {
+        let integer = dialect == Dialect::Sqlite || field.attributes.get("bit_storage").is_none_or(|s| s == "int");
+        if integer && (1..64).contains(&bits) {
+            conditions.push(format!("{} >= 0 AND {} <= {}", column, column, (1u64 << bits) - 1));
+        }
+    }
+    if let Some(pattern) = field.attributes.get("pattern") {
+        match dialect.regex_match(&column, pattern) {
+            Some(condition) => conditions.push(condition),
+            None => tracing::warn!(
+                "{} has no regular expressions; pattern on '{}' is not enforced by the database",
+                dialect.name(),
+                column
+            ),
+        }
+    }
+    
+    if conditions.is_empty() {
+        None
+    } else {
+        Some(conditions.join(" AND "))
+    }
+}
+
+/// Column type for a bitflags field with `bits = n`, following its `bit_storage`
+///
+/// "int" (the default) is the smallest integer type that holds `n` bits, with a CHECK constraint
+/// from `check_expression`; "bit" and "varbit" are bit strings, which SQLite stores as integers.
+fn bitflags_db_type(field: &FieldDefinition, config: &Config) -> Result<String> {
+    let bits = field.attributes.get("bits").and_then(|bits| bits.parse::<u32>().ok()).unwrap_or(0);
+    let storage = field.attributes.get("bit_storage").map(String::as_str).unwrap_or("int");
+    if bits == 0 {
+        return Err(Error::TypeMappingError(format!("bits on field '{}' must be at least 1", field.name)));
+    }
+    
+    match (storage, config.database.driver.as_str()) {
+        ("bit" | "varbit", "mysql") if bits <= 64 => Ok(format!("BIT({})", bits)),
+        ("bit", "postgres") => Ok(format!("BIT({})", bits)),
+        ("varbit", "postgres") => Ok(format!("BIT VARYING({})", bits)),
+        ("int" | "bit" | "varbit", _) => match bits {
+            1..=15 => Ok("SMALLINT".to_string()),
+            16..=31 => Ok("INTEGER".to_string()),
+            32..=63 => Ok("BIGINT".to_string()),
+            _ => Err(Error::TypeMappingError(format!(
+                "field '{}' has {} bits, more than an integer column holds; use bit_storage = \"bit\"",
+                field.name, bits
+            ))),
+        },
+        (other, _) => Err(Error::TypeMappingError(format!(
+            "bit_storage must be \"int\", \"bit\", or \"varbit\", but field '{}' sets '{}'",
+            field.name, other
+        ))),
+    }
+}
+
+/// Apply `case_insensitive` / `currency` / `length` / `precision` / `scale` / `max_bytes` field
+/// attributes to a mapped type
+fn apply_type_parameters(db_type: String, field: &FieldDefinition, config: &Config) -> Result<String> {
+    let attribute = |key: &str| field.attributes.get(key).and_then(|v| v.parse::<u32>().ok());
+    let base = db_type.split('(').next().unwrap_or(&db_type).trim().to_uppercase();
+    
+    // `json = "binary"` (JSONB) or `json = "text"` (JSON, which keeps key order and whitespace),
+    // which also stores a string map as JSON rather than hstore
+    if let Some(json) = field.attributes.get("json") {
+        return match (base.as_str(), json.as_str()) {
+            ("JSON" | "JSONB" | "HSTORE", "binary") => Ok("JSONB".to_string()),
+            ("JSON" | "JSONB" | "HSTORE", "text") => Ok("JSON".to_string()),
+            ("JSON" | "JSONB" | "HSTORE", other) => Err(Error::TypeMappingError(format!(
+                "json must be \"binary\" or \"text\", but field '{}' sets '{}'",
+                field.name, other
+            ))),
+            _ => Err(Error::TypeMappingError(format!(
+                "json applies to JSON columns, but field '{}' maps to {}",
+                field.name, db_type
+            ))),
+        };
+    }
+    
+    if field.attributes.get("case_insensitive").is_some_and(|value| value == "true") {
+        if !matches!(base.as_str(), "VARCHAR" | "CHAR" | "CHARACTER VARYING" | "TEXT" | "CITEXT") {
+            return Err(Error::TypeMappingError(format!(
+                "case_insensitive applies to string columns, but field '{}' maps to {}",
+                field.name, db_type
+            )));
+        }
+        if config.database.driver == "postgres" {
+            return Ok("CITEXT".to_string());
+        }
+    }
+    
+    // A decimal with a currency is a money amount, unless it sets its own precision
+    if field.attributes.contains_key("currency") && attribute("precision").is_none() {
+        return match base.as_str() {
+            "NUMERIC" | "DECIMAL" => Ok(money_db_type(config)),
+            _ => Err(Error::TypeMappingError(format!(
+                "currency applies to decimal columns, but field '{}' maps to {}",
+                field.name, db_type
+            ))),
+        };
+    }
+    
+    if let Some(length) = attribute("length") {
+        return match base.as_str() {
+            "VARCHAR" | "CHAR" | "CHARACTER VARYING" => Ok(format!("{}({})", base, length)),
+            _ => Err(Error::TypeMappingError(format!(
+                "length applies to string columns, but field '{}' maps to {}",
+                field.name, db_type
+            ))),
+        };
+    }
+    
+    // `max_bytes` picks the smallest MySQL BLOB type that holds the value; other databases
+    // have a single binary type
+    if let Some(max_bytes) = attribute("max_bytes") {
+        if !matches!(base.as_str(), "BYTEA" | "BLOB") {
+            return Err(Error::TypeMappingError(format!(
+                "max_bytes applies to binary columns, but field '{}' maps to {}",
+                field.name, db_type
+            )));
+        }
+        
+        if config.database.driver != "mysql" {
+            return Ok(db_type);
+        }
+        
+        return Ok(match max_bytes {
+            0..=255 => "TINYBLOB",
+            256..=65_535 => "BLOB",
+            65_536..=16_777_215 => "MEDIUMBLOB",
+            _ => "LONGBLOB",
+        }
+        .to_string());
+    }
+    
+    if let Some(precision) = attribute("precision") {
+        let scale = attribute("scale").unwrap_or(0);
+        if scale > precision {
+            return Err(Error::TypeMappingError(format!(
+                "scale {} exceeds precision {} on field '{}'",
+                scale, precision, field.name
+            )));
+        }
+        
+        return match base.as_str() {
+            "NUMERIC" | "DECIMAL" => Ok(format!("{}({},{})", base, precision, scale)),
+            _ => Err(Error::TypeMappingError(format!(
+                "precision applies to decimal columns, but field '{}' maps to {}",
+                field.name, db_type
+            ))),
+        };
+    }
+    
+    Ok(db_type)
+}
@@ -0,0 +1,283 @@
+//! Index advisor
+//!
+//! This module suggests indexes to add or drop. Suggestions come from the schema itself
+//! (unindexed foreign keys, declared query patterns) and, on Postgres, from the usage
+//! statistics the server keeps. Nothing here changes the database; the output is a report.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[cfg(feature = "postgres")]
+use sqlparser::ast::{BinaryOperator, Expr, SetExpr, Statement, TableFactor};
+#[cfg(feature = "postgres")]
+use sqlparser::dialect::PostgreSqlDialect;
+#[cfg(feature = "postgres")]
+use sqlparser::parser::Parser;
+
+use crate::config::QueryPattern;
+use crate::db::connection::DatabaseConnection;
+use crate::error::Result;
+use crate::schema::types::{DatabaseSchema, Table};
+
+/// Whether an index should be added or dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexSuggestionKind {
+    Missing,
+    Unused,
+}
+
+/// A suggested index change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexSuggestion {
+    pub kind: IndexSuggestionKind,
+    pub table: String,
+    pub columns: Vec<String>,
+    /// Existing index, for `Unused` suggestions
+    pub index_name: Option<String>,
+    pub reason: String,
+}
+
+impl fmt::Display for IndexSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            IndexSuggestionKind::Missing => write!(
+                f,
+                "add index on {}({}): {}",
+                self.table,
+                self.columns.join(", "),
+                self.reason
+            ),
+            IndexSuggestionKind::Unused => write!(
+                f,
+                "drop index {} on {}: {}",
+                self.index_name.as_deref().unwrap_or("?"),
+                self.table,
+                self.reason
+            ),
+        }
+    }
+}
+
+/// Suggest indexes for foreign keys and declared query patterns the schema does not cover
+pub fn advise(schema: &DatabaseSchema, patterns: &[QueryPattern]) -> Vec<IndexSuggestion> {
+    let mut suggestions = Vec::new();
+
+    let mut table_names: Vec<&String> = schema.tables.keys().collect();
+    table_names.sort();
+
+    for table_name in table_names {
+        let table = &schema.tables[table_name];
+
+        for fk in &table.foreign_keys {
+            if !is_covered(table, &fk.columns) {
+                push_missing(
+                    &mut suggestions,
+                    &table.name,
+                    &fk.columns,
+                    format!("foreign key to {} is not indexed", fk.ref_table),
+                );
+            }
+        }
+    }
+
+    for pattern in patterns {
+        let covered = schema
+            .tables
+            .get(&pattern.table)
+            .is_some_and(|table| is_covered(table, &pattern.columns));
+
+        if !covered {
+            push_missing(
+                &mut suggestions,
+                &pattern.table,
+                &pattern.columns,
+                "declared query pattern is not indexed".to_string(),
+            );
+        }
+    }
+
+    suggestions
+}
+
+/// Suggest index changes from the database's usage statistics
+///
+/// On Postgres this reports non-unique indexes that have never been scanned
+/// (`pg_stat_user_indexes`) and, when the `pg_stat_statements` extension is installed,
+/// equality filters in the slowest statements that no index covers. Other drivers keep
+/// no comparable statistics and return no suggestions.
+pub async fn advise_from_statistics(
+    connection: &DatabaseConnection,
+    schema: &DatabaseSchema,
+) -> Result<Vec<IndexSuggestion>> {
+    match connection {
+        #[cfg(feature = "postgres")]
+        DatabaseConnection::Postgres(pool) => postgres_statistics(pool, schema).await,
+        #[allow(unreachable_patterns)]
+        _ => {
+            let _ = schema;
+            Ok(Vec::new())
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+async fn postgres_statistics(
+    pool: &sqlx::Pool<sqlx::Postgres>,
+    schema: &DatabaseSchema,
+) -> Result<Vec<IndexSuggestion>> {
+    let mut suggestions = Vec::new();
+
+    let unused: Vec<(String, String, i64)> = sqlx::query_as(
+        "SELECT s.relname::text, s.indexrelname::text, pg_relation_size(s.indexrelid) \
+         FROM pg_stat_user_indexes s \
+         JOIN pg_index i ON i.indexrelid = s.indexrelid \
+         WHERE s.idx_scan = 0 AND NOT i.indisunique AND NOT i.indisprimary \
+         ORDER BY s.relname, s.indexrelname",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (table, index_name, size) in unused {
+        let columns = schema
+            .tables
+            .get(&table)
+            .and_then(|t| t.indexes.iter().find(|index| index.name == index_name))
+            .map(|index| index.columns.clone())
+            .unwrap_or_default();
+
+        suggestions.push(IndexSuggestion {
+            kind: IndexSuggestionKind::Unused,
+            table,
+            columns,
+            index_name: Some(index_name),
+            reason: format!("never scanned since statistics were reset ({} bytes)", size),
+        });
+    }
+
+    let has_statements: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'pg_stat_statements')",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if !has_statements {
+        return Ok(suggestions);
+    }
+
+    // Column names differ between versions (mean_time before Postgres 13)
+    let statements: Vec<(String, f64)> = match sqlx::query_as(
+        "SELECT query, mean_exec_time FROM pg_stat_statements ORDER BY total_exec_time DESC LIMIT 50",
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(_) => {
+            sqlx::query_as("SELECT query, mean_time FROM pg_stat_statements ORDER BY total_time DESC LIMIT 50")
+                .fetch_all(pool)
+                .await?
+        }
+    };
+
+    for (query, mean_ms) in statements {
+        let Some((table_name, columns)) = equality_filter(&query) else {
+            continue;
+        };
+        let Some(table) = schema.tables.get(&table_name) else {
+            continue;
+        };
+
+        if !is_covered(table, &columns) {
+            push_missing(
+                &mut suggestions,
+                &table_name,
+                &columns,
+                format!("filtered on by a statement averaging {:.1} ms", mean_ms),
+            );
+        }
+    }
+
+    Ok(suggestions)
+}
+
+/// Table and columns of a single-table `SELECT ... WHERE a = ? AND b = ?` statement
+#[cfg(feature = "postgres")]
+fn equality_filter(sql: &str) -> Option<(String, Vec<String>)> {
+    let statements = Parser::parse_sql(&PostgreSqlDialect {}, sql).ok()?;
+    let Some(Statement::Query(query)) = statements.into_iter().next() else {
+        return None;
+    };
+    let SetExpr::Select(select) = *query.body else {
+        return None;
+    };
+
+    let [from] = select.from.as_slice() else {
+        return None;
+    };
+    if !from.joins.is_empty() {
+        return None;
+    }
+    let TableFactor::Table { name, .. } = &from.relation else {
+        return None;
+    };
+    let table = name.0.last()?.value.clone();
+
+    let mut columns = Vec::new();
+    collect_equality_columns(select.selection.as_ref()?, &mut columns);
+
+    if columns.is_empty() {
+        None
+    } else {
+        Some((table, columns))
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn collect_equality_columns(expr: &Expr, columns: &mut Vec<String>) {
+    match expr {
+        Expr::BinaryOp { left, op: BinaryOperator::And, right } => {
+            collect_equality_columns(left, columns);
+            collect_equality_columns(right, columns);
+        }
+        Expr::BinaryOp { left, op: BinaryOperator::Eq, .. } => {
+            let column = match left.as_ref() {
+                Expr::Identifier(ident) => Some(ident.value.clone()),
+                Expr::CompoundIdentifier(idents) => idents.last().map(|i| i.value.clone()),
+                _ => None,
+            };
+            if let Some(column) = column {
+                if !columns.contains(&column) {
+                    columns.push(column);
+                }
+            }
+        }
+        Expr::Nested(inner) => collect_equality_columns(inner, columns),
+        _ => {}
+    }
+}
+
+/// Check whether an index or the primary key starts with the given columns
+fn is_covered(table: &Table, columns: &[String]) -> bool {
+    table.indexes.iter().any(|index| index.columns.starts_with(columns))
+        || table
+            .primary_key
+            .as_ref()
+            .is_some_and(|pk| pk.columns.starts_with(columns))
+}
+
+fn push_missing(suggestions: &mut Vec<IndexSuggestion>, table: &str, columns: &[String], reason: String) {
+    let duplicate = suggestions.iter().any(|s| {
+        s.kind == IndexSuggestionKind::Missing && s.table == table && s.columns == columns
+    });
+
+    if !duplicate {
+        suggestions.push(IndexSuggestion {
+            kind: IndexSuggestionKind::Missing,
+            table: table.to_string(),
+            columns: columns.to_vec(),
+            index_name: None,
+            reason,
+        });
+    }
+}
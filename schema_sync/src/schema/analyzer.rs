@@ -3,12 +3,23 @@
 //! This module provides functionality to analyze an existing database schema.
 
 use async_trait::async_trait;
-use sqlx::{Any, FromRow, MySql, Pool, Postgres, Sqlite};
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use sqlx::Pool;
+#[cfg(feature = "mysql")]
+use sqlx::MySql;
+#[cfg(feature = "postgres")]
+use sqlx::{FromRow, Postgres};
+#[cfg(feature = "sqlite")]
+use sqlx::Sqlite;
 use std::collections::HashMap;
 
 use crate::db::connection::DatabaseConnection;
 use crate::error::Result;
-use crate::schema::types::{Column, DatabaseSchema, ForeignKey, Index, PrimaryKey, Table, View};
+use crate::schema::types::{DatabaseSchema, Table, View};
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite", feature = "oracle"))]
+use crate::schema::types::{Column, ForeignKey, Index, PrimaryKey};
+#[cfg(feature = "postgres")]
+use crate::schema::types::{Constraint, Function};
 
 /// Schema analyzer trait
 #[async_trait]
@@ -21,6 +32,12 @@ pub trait Analyzer {
 
     /// Analyze view definitions
     async fn analyze_views(&self, schema_name: Option<&str>) -> Result<HashMap<String, View>>;
+
+    /// Analyze a single table, returning `None` if it does not exist
+    async fn analyze_table(&self, schema_name: Option<&str>, table_name: &str) -> Result<Option<Table>>;
+
+    /// Check whether a table exists
+    async fn table_exists(&self, schema_name: Option<&str>, table_name: &str) -> Result<bool>;
 }
 
 /// Schema analyzer for database schema introspection
@@ -36,35 +53,70 @@ impl SchemaAnalyzer {
 
     /// Analyze the current database schema
     pub async fn analyze(&self) -> Result<DatabaseSchema> {
+        self.analyzer()
+            .analyze_schema(self.connection.get_schema())
+            .await
+    }
+
+    /// Analyze a single table, returning `None` if it does not exist
+    ///
+    /// Cheaper than `analyze` when an application only needs to verify one table,
+    /// e.g. before enabling a feature that depends on it.
+    pub async fn analyze_table(&self, table_name: &str) -> Result<Option<Table>> {
+        self.analyzer()
+            .analyze_table(self.connection.get_schema(), table_name)
+            .await
+    }
+
+    /// Row estimates and sizes of the tables, from the database's statistics
+    pub async fn table_stats(&self) -> Result<HashMap<String, crate::schema::stats::TableStats>> {
+        crate::schema::stats::table_stats(&self.connection).await
+    }
+
+    /// Check whether a table exists
+    pub async fn table_exists(&self, table_name: &str) -> Result<bool> {
+        self.analyzer()
+            .table_exists(self.connection.get_schema(), table_name)
+            .await
+    }
+
+    /// Whether the schema of a `database.driver` can be read back, table by table
+    ///
+    /// False for drivers SchemaSync only generates SQL for, such as Oracle built without
+    /// the `oracle` feature; their schemas come from snapshots instead.
+    pub fn supports_driver(driver: &str) -> bool {
+        match driver {
+            "postgres" | "mysql" | "sqlite" | "clickhouse" => true,
+            "oracle" => cfg!(feature = "oracle"),
+            _ => false,
+        }
+    }
+
+    /// Pick the driver-specific analyzer for the connection
+    fn analyzer(&self) -> Box<dyn Analyzer + Send + Sync + '_> {
         match &self.connection {
-            DatabaseConnection::Postgres(pool) => {
-                PostgresAnalyzer { pool }
-                    .analyze_schema(self.connection.get_schema())
-                    .await
-            }
-            DatabaseConnection::MySql(pool) => {
-                MySqlAnalyzer { pool }
-                    .analyze_schema(self.connection.get_schema())
-                    .await
-            }
-            DatabaseConnection::Sqlite(pool) => {
-                SqliteAnalyzer { pool }
-                    .analyze_schema(self.connection.get_schema())
-                    .await
-            }
-            _ => Err(crate::error::Error::SchemaAnalysisError(
-                "Unsupported database type".to_string(),
-            )),
+            #[cfg(feature = "postgres")]
+            DatabaseConnection::Postgres(pool) => Box::new(PostgresAnalyzer { pool }),
+            #[cfg(feature = "mysql")]
+            DatabaseConnection::MySql(pool) => Box::new(MySqlAnalyzer { pool }),
+            #[cfg(feature = "sqlite")]
+            DatabaseConnection::Sqlite(pool) => Box::new(SqliteAnalyzer { pool }),
+            #[cfg(feature = "clickhouse")]
+            DatabaseConnection::ClickHouse(client) => Box::new(ClickHouseAnalyzer { client }),
+            #[cfg(feature = "oracle")]
+            DatabaseConnection::Oracle(client) => Box::new(OracleAnalyzer { client }),
         }
     }
 }
 
 // Row types for PostgreSQL queries
+#[cfg(feature = "postgres")]
 #[derive(FromRow)]
 struct TableRow {
     table_name: String,
 }
 
+#[cfg(feature = "postgres")]
 #[derive(FromRow)]
 struct ColumnRow {
     column_name: String,
@@ -72,50 +124,512 @@ struct ColumnRow {
     is_nullable: String,
     column_default: Option<String>,
     character_maximum_length: Option<i64>,
+    /// `pg_attribute.attstorage`, for table columns
+    #[sqlx(default)]
+    attstorage: Option<String>,
 }
 
+#[cfg(feature = "postgres")]
 #[derive(FromRow)]
 struct PrimaryKeyRow {
     constraint_name: String,
     column_name: String,
 }
 
+#[cfg(feature = "postgres")]
 #[derive(FromRow)]
 struct IndexRow {
     index_name: String,
     column_name: String,
     is_unique: Option<bool>,
     index_method: String,
+    is_included: bool,
+    reloptions: Option<Vec<String>>,
+    tablespace: Option<String>,
+    operator_class: Option<String>,
+}
+
+#[cfg(feature = "postgres")]
+#[derive(FromRow)]
+struct ExpressionIndexRow {
+    index_name: String,
+    is_unique: bool,
+    index_method: String,
+    expression: String,
+    reloptions: Option<Vec<String>>,
+    tablespace: Option<String>,
+    operator_class: Option<String>,
 }
 
+#[cfg(feature = "postgres")]
 #[derive(FromRow)]
 struct ForeignKeyRow {
     constraint_name: String,
     column_name: String,
+    ref_schema: String,
     ref_table: String,
     ref_column: String,
     delete_rule: String,
     update_rule: String,
+    condeferrable: bool,
+    condeferred: bool,
+    match_type: String,
 }
 
+#[cfg(feature = "postgres")]
 #[derive(FromRow)]
 struct ViewRow {
     table_name: String,
     view_definition: Option<String>,
     is_updatable: Option<String>,
+    comment: Option<String>,
 }
 
+#[cfg(feature = "postgres")]
 #[derive(FromRow)]
 struct MatViewRow {
     matviewname: String,
     definition: Option<String>,
+    has_unique_index: bool,
+    comment: Option<String>,
+}
+
+#[cfg(feature = "postgres")]
+#[derive(FromRow)]
+struct ViewDependencyRow {
+    view_name: String,
+    relation_name: String,
+    column_name: String,
+}
+
+/// Split `pg_class.reloptions` entries (`fillfactor=70`) into index options
+#[cfg(feature = "postgres")]
+fn parse_reloptions(reloptions: Option<Vec<String>>) -> std::collections::BTreeMap<String, String> {
+    reloptions
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|option| option.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
 }
 
 /// PostgreSQL schema analyzer
+#[cfg(feature = "postgres")]
 struct PostgresAnalyzer<'a> {
     pool: &'a Pool<Postgres>,
 }
 
+#[cfg(feature = "postgres")]
+impl<'a> PostgresAnalyzer<'a> {
+    /// Load the schema's own functions and procedures (not those installed by extensions)
+    async fn analyze_functions(&self, schema: &str) -> Result<Vec<Function>> {
+        let sql = r#"
+            SELECT p.proname::text, pg_get_functiondef(p.oid)
+            FROM pg_proc p
+            JOIN pg_namespace n ON n.oid = p.pronamespace
+            WHERE n.nspname = $1
+                AND p.prokind IN ('f', 'p')
+                AND NOT EXISTS (
+                    SELECT 1 FROM pg_depend d
+                    WHERE d.objid = p.oid AND d.classid = 'pg_proc'::regclass AND d.deptype = 'e'
+                )
+            ORDER BY 1
+        "#;
+
+        let rows: Vec<(String, String)> = sqlx::query_as(sql)
+            .bind(schema)
+            .fetch_all(self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, definition)| Function { name, definition })
+            .collect())
+    }
+
+    /// Load columns, primary key, indexes, and foreign keys of a table known to exist
+    async fn describe_table(&self, schema: &str, table_name: &str) -> Result<Table> {
+        let mut table = Table::new(table_name);
+
+        // Get columns
+        let sql = r#"
+            SELECT 
+                column_name, 
+                -- Enum and other user-defined columns report their type's name; pgvector
+                -- columns also their dimensions, e.g. vector(1536)
+                CASE
+                    WHEN data_type = 'USER-DEFINED' AND udt_name = 'vector' THEN (
+                        SELECT format_type(a.atttypid, a.atttypmod)
+                        FROM pg_attribute a
+                        WHERE a.attrelid = format('%I.%I', table_schema, table_name)::regclass
+                            AND a.attname = column_name
+                    )
+                    WHEN data_type = 'USER-DEFINED' THEN udt_name::text
+                    ELSE data_type::text
+                END AS data_type,
+                is_nullable, 
+                column_default,
+                character_maximum_length,
+                (
+                    SELECT a.attstorage::text
+                    FROM pg_attribute a
+                    WHERE a.attrelid = format('%I.%I', table_schema, table_name)::regclass
+                        AND a.attname = column_name
+                        AND NOT a.attisdropped
+                ) AS attstorage
+            FROM information_schema.columns 
+            WHERE table_schema = $1 AND table_name = $2
+            ORDER BY ordinal_position
+        "#;
+        
+        let column_rows = sqlx::query_as::<_, ColumnRow>(sql)
+            .bind(schema)
+            .bind(table_name)
+            .fetch_all(self.pool)
+            .await?;
+
+        for col in column_rows {
+            let mut data_type = col.data_type;
+            if let Some(max_length) = col.character_maximum_length {
+                if data_type == "character varying" {
+                    data_type = format!("varchar({})", max_length);
+                } else if data_type == "bit" || data_type == "bit varying" {
+                    data_type = format!("{}({})", data_type.to_uppercase(), max_length);
+                }
+            }
+            // Spelled as models map them, so JSON, CITEXT, HSTORE, INTERVAL, vector, and range
+            // columns compare equal
+            let known = matches!(data_type.as_str(), "json" | "jsonb" | "citext" | "hstore" | "interval")
+                || data_type.starts_with("vector");
+            if known || data_type.ends_with("range") {
+                data_type = data_type.to_uppercase();
+            }
+
+            let column = Column {
+                name: col.column_name,
+                data_type,
+                nullable: col.is_nullable == "YES",
+                default: col.column_default,
+                comment: None,
+                is_unique: false, // Will be updated when checking constraints
+                is_generated: false,
+                generation_expression: None,
+                charset: None,
+                default_fn: None,
+                storage: col.attstorage.as_deref().and_then(|storage| match storage {
+                    "p" => Some("plain".to_string()),
+                    "m" => Some("main".to_string()),
+                    "e" => Some("external".to_string()),
+                    "x" => Some("extended".to_string()),
+                    _ => None,
+                }),
+                collation: None,
+            };
+
+            table.add_column(column);
+        }
+
+        // Get primary key
+        let sql = r#"
+            SELECT
+                tc.constraint_name,
+                kcu.column_name
+            FROM
+                information_schema.table_constraints tc
+            JOIN information_schema.key_column_usage kcu
+                ON tc.constraint_name = kcu.constraint_name
+                AND tc.table_schema = kcu.table_schema
+            WHERE
+                tc.constraint_type = 'PRIMARY KEY'
+                AND tc.table_schema = $1
+                AND tc.table_name = $2
+            ORDER BY kcu.ordinal_position
+        "#;
+        
+        let pk_rows = sqlx::query_as::<_, PrimaryKeyRow>(sql)
+            .bind(schema)
+            .bind(table_name)
+            .fetch_all(self.pool)
+            .await?;
+
+        if !pk_rows.is_empty() {
+            let pk_name = pk_rows[0].constraint_name.clone();
+            let pk_columns = pk_rows.iter().map(|r| r.column_name.clone()).collect();
+
+            table.set_primary_key(PrimaryKey {
+                name: Some(pk_name),
+                columns: pk_columns,
+            });
+        }
+
+        // Get the table's tablespace (reltablespace is 0 for the database default)
+        let sql = r#"
+            SELECT ts.spcname::text
+            FROM pg_class c
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            JOIN pg_tablespace ts ON ts.oid = c.reltablespace
+            WHERE n.nspname = $1 AND c.relname = $2
+        "#;
+
+        table.tablespace = sqlx::query_scalar(sql)
+            .bind(schema)
+            .bind(table_name)
+            .fetch_optional(self.pool)
+            .await?;
+
+        // Get the table comment set by COMMENT ON TABLE
+        let sql = r#"
+            SELECT obj_description(c.oid, 'pg_class')
+            FROM pg_class c
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            WHERE n.nspname = $1 AND c.relname = $2
+        "#;
+
+        table.comment = sqlx::query_scalar::<_, Option<String>>(sql)
+            .bind(schema)
+            .bind(table_name)
+            .fetch_optional(self.pool)
+            .await?
+            .flatten();
+
+        // Get indexes
+        let sql = r#"
+            SELECT
+                i.relname as index_name,
+                a.attname as column_name,
+                ix.indisunique as is_unique,
+                am.amname as index_method,
+                array_position(ix.indkey::int2[], a.attnum) > ix.indnkeyatts as is_included,
+                i.reloptions,
+                ts.spcname::text as tablespace,
+                -- Only a non-default operator class is reported, e.g. jsonb_path_ops
+                (SELECT opc.opcname::text FROM pg_opclass opc
+                    WHERE opc.oid = ix.indclass[0] AND NOT opc.opcdefault) as operator_class
+            FROM
+                pg_index ix
+            JOIN pg_class i ON i.oid = ix.indexrelid
+            JOIN pg_class t ON t.oid = ix.indrelid
+            JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = ANY(ix.indkey)
+            JOIN pg_namespace n ON n.oid = t.relnamespace
+            JOIN pg_am am ON am.oid = i.relam
+            LEFT JOIN pg_tablespace ts ON ts.oid = i.reltablespace
+            WHERE
+                t.relname = $1
+                AND n.nspname = $2
+                AND NOT ix.indisprimary
+            ORDER BY i.relname, array_position(ix.indkey::int2[], a.attnum)
+        "#;
+        
+        let index_rows = sqlx::query_as::<_, IndexRow>(sql)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_all(self.pool)
+            .await?;
+
+        let mut indexes = HashMap::new();
+        for row in index_rows {
+            let index_name = row.index_name;
+            let column_name = row.column_name;
+            let is_unique = row.is_unique.unwrap_or(false);
+            let method = row.index_method;
+            let options = parse_reloptions(row.reloptions);
+            let tablespace = row.tablespace;
+
+            let index = indexes
+                .entry(index_name.clone())
+                .or_insert_with(|| Index {
+                    name: index_name.clone(),
+                    columns: Vec::new(),
+                    is_unique,
+                    method: Some(method),
+                    include: Vec::new(),
+                    expression: None,
+                    options,
+                    tablespace,
+                    operator_class: row.operator_class,
+                });
+
+            // Columns past indnkeyatts are INCLUDE columns
+            if row.is_included {
+                index.include.push(column_name);
+            } else {
+                index.columns.push(column_name);
+            }
+        }
+
+        // Expression keys have no pg_attribute row, so they are read separately
+        let sql = r#"
+            SELECT
+                i.relname::text AS index_name,
+                ix.indisunique AS is_unique,
+                am.amname::text AS index_method,
+                pg_get_expr(ix.indexprs, ix.indrelid) AS expression,
+                i.reloptions,
+                ts.spcname::text AS tablespace,
+                (SELECT opc.opcname::text FROM pg_opclass opc
+                    WHERE opc.oid = ix.indclass[0] AND NOT opc.opcdefault) AS operator_class
+            FROM
+                pg_index ix
+            JOIN pg_class i ON i.oid = ix.indexrelid
+            JOIN pg_class t ON t.oid = ix.indrelid
+            JOIN pg_namespace n ON n.oid = t.relnamespace
+            JOIN pg_am am ON am.oid = i.relam
+            LEFT JOIN pg_tablespace ts ON ts.oid = i.reltablespace
+            WHERE
+                t.relname = $1
+                AND n.nspname = $2
+                AND ix.indexprs IS NOT NULL
+                AND NOT ix.indisprimary
+        "#;
+
+        let expression_rows = sqlx::query_as::<_, ExpressionIndexRow>(sql)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_all(self.pool)
+            .await?;
+
+        for row in expression_rows {
+            indexes
+                .entry(row.index_name.clone())
+                .or_insert_with(|| Index {
+                    name: row.index_name,
+                    columns: Vec::new(),
+                    is_unique: row.is_unique,
+                    method: Some(row.index_method),
+                    include: Vec::new(),
+                    expression: None,
+                    options: parse_reloptions(row.reloptions),
+                    tablespace: row.tablespace,
+                    operator_class: row.operator_class,
+                })
+                .expression = Some(row.expression);
+        }
+
+        table.indexes = indexes.into_values().collect();
+
+        // Get foreign keys
+        let sql = r#"
+            SELECT
+                tc.constraint_name,
+                kcu.column_name,
+                ccu.table_schema AS ref_schema,
+                ccu.table_name AS ref_table,
+                ccu.column_name AS ref_column,
+                rc.delete_rule,
+                rc.update_rule,
+                con.condeferrable,
+                con.condeferred,
+                con.confmatchtype::text AS match_type
+            FROM
+                information_schema.table_constraints tc
+            JOIN information_schema.key_column_usage kcu
+                ON tc.constraint_name = kcu.constraint_name
+                AND tc.table_schema = kcu.table_schema
+            JOIN information_schema.constraint_column_usage ccu
+                ON ccu.constraint_name = tc.constraint_name
+                AND ccu.constraint_schema = tc.constraint_schema
+            JOIN information_schema.referential_constraints rc
+                ON tc.constraint_name = rc.constraint_name
+                AND tc.table_schema = rc.constraint_schema
+            JOIN pg_catalog.pg_constraint con
+                ON con.conname = tc.constraint_name
+                AND con.connamespace = tc.table_schema::regnamespace
+            WHERE
+                tc.constraint_type = 'FOREIGN KEY'
+                AND tc.table_schema = $1
+                AND tc.table_name = $2
+            ORDER BY tc.constraint_name, kcu.ordinal_position
+        "#;
+        
+        let fk_rows = sqlx::query_as::<_, ForeignKeyRow>(sql)
+            .bind(schema)
+            .bind(table_name)
+            .fetch_all(self.pool)
+            .await?;
+
+        let mut foreign_keys = HashMap::new();
+        for row in fk_rows {
+            let fk_name = row.constraint_name;
+            let column_name = row.column_name;
+            // Targets in other schemas keep their qualification, as models declare them
+            let ref_table = if row.ref_schema == schema {
+                row.ref_table
+            } else {
+                format!("{}.{}", row.ref_schema, row.ref_table)
+            };
+            let ref_column = row.ref_column;
+            let on_delete = row.delete_rule;
+            let on_update = row.update_rule;
+            let deferrable = match (row.condeferrable, row.condeferred) {
+                (true, true) => Some("initially_deferred".to_string()),
+                (true, false) => Some("initially_immediate".to_string()),
+                _ => None,
+            };
+            let match_type = match row.match_type.as_str() {
+                "f" => Some("full".to_string()),
+                "p" => Some("partial".to_string()),
+                _ => None,
+            };
+
+            let entry_key = fk_name.clone();
+            foreign_keys
+                .entry(entry_key.clone())
+                .or_insert_with(|| ForeignKey {
+                    name: fk_name,
+                    columns: Vec::new(),
+                    ref_table,
+                    ref_columns: Vec::new(),
+                    on_delete: Some(on_delete),
+                    on_update: Some(on_update),
+                    deferrable,
+                    match_type,
+                })
+                .columns
+                .push(column_name);
+
+            foreign_keys
+                .get_mut(&entry_key)
+                .unwrap()
+                .ref_columns
+                .push(ref_column);
+        }
+
+        table.foreign_keys = foreign_keys.into_values().collect();
+
+        // Get exclusion and CHECK constraints; pg_get_constraintdef gives
+        // "EXCLUDE USING gist (...)" or "CHECK ((...))"
+        let sql = r#"
+            SELECT con.conname::text, pg_get_constraintdef(con.oid), con.contype::text
+            FROM pg_catalog.pg_constraint con
+            JOIN pg_catalog.pg_class c ON c.oid = con.conrelid
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE con.contype IN ('x', 'c')
+                AND n.nspname = $1
+                AND c.relname = $2
+            ORDER BY con.conname
+        "#;
+
+        let constraint_rows: Vec<(String, String, String)> = sqlx::query_as(sql)
+            .bind(schema)
+            .bind(table_name)
+            .fetch_all(self.pool)
+            .await?;
+
+        table.constraints = constraint_rows
+            .into_iter()
+            .map(|(name, definition, contype)| Constraint {
+                name,
+                // NOT VALID constraints still report their full definition with a suffix
+                definition: definition.trim_end_matches(" NOT VALID").to_string(),
+                constraint_type: if contype == "x" { "EXCLUDE" } else { "CHECK" }.to_string(),
+            })
+            .collect();
+
+        Ok(table)
+    }
+}
+
+#[cfg(feature = "postgres")]
 #[async_trait]
 impl<'a> Analyzer for PostgresAnalyzer<'a> {
     async fn analyze_schema(&self, schema_name: Option<&str>) -> Result<DatabaseSchema> {
@@ -128,6 +642,27 @@ impl<'a> Analyzer for PostgresAnalyzer<'a> {
         // Get views
         db_schema.views = self.analyze_views(Some(schema)).await?;
 
+        // Get functions, for impact analysis
+        db_schema.functions = self.analyze_functions(schema).await?;
+
+        // Get enum types, labels in declaration order
+        let sql = r#"
+            SELECT t.typname::text, e.enumlabel::text
+            FROM pg_catalog.pg_type t
+            JOIN pg_catalog.pg_enum e ON e.enumtypid = t.oid
+            JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+            WHERE n.nspname = $1
+            ORDER BY t.typname, e.enumsortorder
+        "#;
+
+        let enum_rows: Vec<(String, String)> = sqlx::query_as(sql)
+            .bind(schema)
+            .fetch_all(self.pool)
+            .await?;
+        for (name, label) in enum_rows {
+            db_schema.enum_types.entry(name).or_default().push(label);
+        }
+
         Ok(db_schema)
     }
 
@@ -148,200 +683,41 @@ impl<'a> Analyzer for PostgresAnalyzer<'a> {
             .await?;
 
         for row in table_rows {
-            let table_name = row.table_name;
-            let mut table = Table::new(&table_name);
-
-            // Get columns
-            let sql = r#"
-                SELECT 
-                    column_name, 
-                    data_type, 
-                    is_nullable, 
-                    column_default,
-                    character_maximum_length
-                FROM information_schema.columns 
-                WHERE table_schema = $1 AND table_name = $2
-                ORDER BY ordinal_position
-            "#;
-            
-            let column_rows = sqlx::query_as::<_, ColumnRow>(sql)
-                .bind(schema)
-                .bind(&table_name)
-                .fetch_all(self.pool)
-                .await?;
-
-            for col in column_rows {
-                let mut data_type = col.data_type;
-                if let Some(max_length) = col.character_maximum_length {
-                    if data_type == "character varying" {
-                        data_type = format!("varchar({})", max_length);
-                    }
-                }
-
-                let column = Column {
-                    name: col.column_name,
-                    data_type,
-                    nullable: col.is_nullable == "YES",
-                    default: col.column_default,
-                    comment: None,
-                    is_unique: false, // Will be updated when checking constraints
-                    is_generated: false,
-                    generation_expression: None,
-                };
-
-                table.add_column(column);
-            }
-
-            // Get primary key
-            let sql = r#"
-                SELECT
-                    tc.constraint_name,
-                    kcu.column_name
-                FROM
-                    information_schema.table_constraints tc
-                JOIN information_schema.key_column_usage kcu
-                    ON tc.constraint_name = kcu.constraint_name
-                    AND tc.table_schema = kcu.table_schema
-                WHERE
-                    tc.constraint_type = 'PRIMARY KEY'
-                    AND tc.table_schema = $1
-                    AND tc.table_name = $2
-                ORDER BY kcu.ordinal_position
-            "#;
-            
-            let pk_rows = sqlx::query_as::<_, PrimaryKeyRow>(sql)
-                .bind(schema)
-                .bind(&table_name)
-                .fetch_all(self.pool)
-                .await?;
-
-            if !pk_rows.is_empty() {
-                let pk_name = pk_rows[0].constraint_name.clone();
-                let pk_columns = pk_rows.iter().map(|r| r.column_name.clone()).collect();
-
-                table.set_primary_key(PrimaryKey {
-                    name: Some(pk_name),
-                    columns: pk_columns,
-                });
-            }
+            let table = self.describe_table(schema, &row.table_name).await?;
+            tables.insert(row.table_name, table);
+        }
 
-            // Get indexes
-            let sql = r#"
-                SELECT
-                    i.relname as index_name,
-                    a.attname as column_name,
-                    ix.indisunique as is_unique,
-                    am.amname as index_method
-                FROM
-                    pg_index ix
-                JOIN pg_class i ON i.oid = ix.indexrelid
-                JOIN pg_class t ON t.oid = ix.indrelid
-                JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = ANY(ix.indkey)
-                JOIN pg_namespace n ON n.oid = t.relnamespace
-                JOIN pg_am am ON am.oid = i.relam
-                WHERE
-                    t.relname = $1
-                    AND n.nspname = $2
-                    AND NOT ix.indisprimary
-                ORDER BY i.relname, a.attnum
-            "#;
-            
-            let index_rows = sqlx::query_as::<_, IndexRow>(sql)
-                .bind(&table_name)
-                .bind(schema)
-                .fetch_all(self.pool)
-                .await?;
+        Ok(tables)
+    }
 
-            let mut indexes = HashMap::new();
-            for row in index_rows {
-                let index_name = row.index_name;
-                let column_name = row.column_name;
-                let is_unique = row.is_unique.unwrap_or(false);
-                let method = row.index_method;
-
-                indexes
-                    .entry(index_name.clone())
-                    .or_insert_with(|| Index {
-                        name: index_name.clone(),
-                        columns: Vec::new(),
-                        is_unique,
-                        method: Some(method),
-                    })
-                    .columns
-                    .push(column_name);
-            }
+    async fn analyze_table(&self, schema_name: Option<&str>, table_name: &str) -> Result<Option<Table>> {
+        let schema = schema_name.unwrap_or("public");
 
-            table.indexes = indexes.into_values().collect();
+        if !self.table_exists(Some(schema), table_name).await? {
+            return Ok(None);
+        }
 
-            // Get foreign keys
-            let sql = r#"
-                SELECT
-                    tc.constraint_name,
-                    kcu.column_name,
-                    ccu.table_name AS ref_table,
-                    ccu.column_name AS ref_column,
-                    rc.delete_rule,
-                    rc.update_rule
-                FROM
-                    information_schema.table_constraints tc
-                JOIN information_schema.key_column_usage kcu
-                    ON tc.constraint_name = kcu.constraint_name
-                    AND tc.table_schema = kcu.table_schema
-                JOIN information_schema.constraint_column_usage ccu
-                    ON ccu.constraint_name = tc.constraint_name
-                    AND ccu.table_schema = tc.table_schema
-                JOIN information_schema.referential_constraints rc
-                    ON tc.constraint_name = rc.constraint_name
-                    AND tc.table_schema = rc.constraint_schema
-                WHERE
-                    tc.constraint_type = 'FOREIGN KEY'
-                    AND tc.table_schema = $1
-                    AND tc.table_name = $2
-                ORDER BY tc.constraint_name, kcu.ordinal_position
-            "#;
-            
-            let fk_rows = sqlx::query_as::<_, ForeignKeyRow>(sql)
-                .bind(schema)
-                .bind(&table_name)
-                .fetch_all(self.pool)
-                .await?;
+        Ok(Some(self.describe_table(schema, table_name).await?))
+    }
 
-            let mut foreign_keys = HashMap::new();
-            for row in fk_rows {
-                let fk_name = row.constraint_name;
-                let column_name = row.column_name;
-                let ref_table = row.ref_table;
-                let ref_column = row.ref_column;
-                let on_delete = row.delete_rule;
-                let on_update = row.update_rule;
-
-                let entry_key = fk_name.clone();
-                foreign_keys
-                    .entry(entry_key.clone())
-                    .or_insert_with(|| ForeignKey {
-                        name: fk_name,
-                        columns: Vec::new(),
-                        ref_table,
-                        ref_columns: Vec::new(),
-                        on_delete: Some(on_delete),
-                        on_update: Some(on_update),
-                    })
-                    .columns
-                    .push(column_name);
-
-                foreign_keys
-                    .get_mut(&entry_key)
-                    .unwrap()
-                    .ref_columns
-                    .push(ref_column);
-            }
+    async fn table_exists(&self, schema_name: Option<&str>, table_name: &str) -> Result<bool> {
+        let schema = schema_name.unwrap_or("public");
 
-            table.foreign_keys = foreign_keys.into_values().collect();
+        let sql = r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM information_schema.tables
+                WHERE table_schema = $1 AND table_name = $2 AND table_type = 'BASE TABLE'
+            )
+        "#;
 
-            tables.insert(table_name, table);
-        }
+        let exists: bool = sqlx::query_scalar(sql)
+            .bind(schema)
+            .bind(table_name)
+            .fetch_one(self.pool)
+            .await?;
 
-        Ok(tables)
+        Ok(exists)
     }
 
     async fn analyze_views(&self, schema_name: Option<&str>) -> Result<HashMap<String, View>> {
@@ -350,7 +726,11 @@ impl<'a> Analyzer for PostgresAnalyzer<'a> {
 
         // Query to get view definitions
         let sql = r#"
-            SELECT table_name, view_definition, is_updatable
+            SELECT
+                table_name,
+                view_definition,
+                is_updatable,
+                obj_description(format('%I.%I', table_schema, table_name)::regclass, 'pg_class') AS comment
             FROM information_schema.views
             WHERE table_schema = $1
         "#;
@@ -392,6 +772,10 @@ impl<'a> Analyzer for PostgresAnalyzer<'a> {
                     is_unique: false,
                     is_generated: false,
                     generation_expression: None,
+                    charset: None,
+                    default_fn: None,
+                    storage: None,
+                    collation: None,
                 })
                 .collect();
 
@@ -400,6 +784,9 @@ impl<'a> Analyzer for PostgresAnalyzer<'a> {
                 definition: view_definition,
                 columns,
                 is_materialized: false, // Need separate query for materialized views
+                depends_on: Vec::new(),
+                has_unique_index: false,
+                comment: row.comment,
             };
 
             views.insert(view_name, view);
@@ -407,9 +794,20 @@ impl<'a> Analyzer for PostgresAnalyzer<'a> {
 
         // Add materialized views
         let sql = r#"
-            SELECT matviewname, definition
-            FROM pg_matviews
-            WHERE schemaname = $1
+            SELECT
+                m.matviewname,
+                m.definition,
+                EXISTS (
+                    SELECT 1
+                    FROM pg_index ix
+                    WHERE ix.indrelid = format('%I.%I', m.schemaname, m.matviewname)::regclass
+                        AND ix.indisunique
+                        AND ix.indexprs IS NULL
+                        AND ix.indpred IS NULL
+                ) AS has_unique_index,
+                obj_description(format('%I.%I', m.schemaname, m.matviewname)::regclass, 'pg_class') AS comment
+            FROM pg_matviews m
+            WHERE m.schemaname = $1
         "#;
         
         let mat_view_rows = sqlx::query_as::<_, MatViewRow>(sql)
@@ -449,6 +847,10 @@ impl<'a> Analyzer for PostgresAnalyzer<'a> {
                     is_unique: false,
                     is_generated: false,
                     generation_expression: None,
+                    charset: None,
+                    default_fn: None,
+                    storage: None,
+                    collation: None,
                 })
                 .collect();
 
@@ -457,11 +859,44 @@ impl<'a> Analyzer for PostgresAnalyzer<'a> {
                 definition: view_definition,
                 columns,
                 is_materialized: true,
+                depends_on: Vec::new(),
+                has_unique_index: row.has_unique_index,
+                comment: row.comment,
             };
 
             views.insert(view_name, view);
         }
 
+        // Record the columns each view reads, so column changes can recreate it
+        let sql = r#"
+            SELECT DISTINCT
+                v.relname::text AS view_name,
+                t.relname::text AS relation_name,
+                a.attname::text AS column_name
+            FROM pg_depend d
+            JOIN pg_rewrite r ON r.oid = d.objid
+            JOIN pg_class v ON v.oid = r.ev_class
+            JOIN pg_class t ON t.oid = d.refobjid
+            JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = d.refobjsubid
+            JOIN pg_namespace n ON n.oid = v.relnamespace
+            WHERE d.classid = 'pg_rewrite'::regclass
+                AND d.refclassid = 'pg_class'::regclass
+                AND v.oid <> t.oid
+                AND n.nspname = $1
+            ORDER BY 1, 2, 3
+        "#;
+
+        let dependency_rows = sqlx::query_as::<_, ViewDependencyRow>(sql)
+            .bind(schema)
+            .fetch_all(self.pool)
+            .await?;
+
+        for row in dependency_rows {
+            if let Some(view) = views.get_mut(&row.view_name) {
+                view.depends_on.push(format!("{}.{}", row.relation_name, row.column_name));
+            }
+        }
+
         Ok(views)
     }
 }
@@ -469,10 +904,12 @@ impl<'a> Analyzer for PostgresAnalyzer<'a> {
 // Similar implementations for MySQL and SQLite analyzers
 // (abbreviated here for brevity - would implement specific versions for each database type)
 
+#[cfg(feature = "mysql")]
 struct MySqlAnalyzer<'a> {
     pool: &'a Pool<MySql>,
 }
 
+#[cfg(feature = "mysql")]
 #[async_trait]
 impl<'a> Analyzer for MySqlAnalyzer<'a> {
     async fn analyze_schema(&self, schema_name: Option<&str>) -> Result<DatabaseSchema> {
@@ -489,12 +926,146 @@ impl<'a> Analyzer for MySqlAnalyzer<'a> {
         // MySQL-specific implementation
         todo!("Implement MySQL view analysis")
     }
+
+    async fn analyze_table(&self, schema_name: Option<&str>, table_name: &str) -> Result<Option<Table>> {
+        if !self.table_exists(schema_name, table_name).await? {
+            return Ok(None);
+        }
+
+        let mut table = Table::new(table_name);
+
+        // Column types are reported as MySQL spells them, e.g. "VARCHAR(255)" or "TINYINT(1)"
+        let columns: Vec<(String, String, String, Option<String>, String, String, Option<String>)> = sqlx::query_as(
+            "SELECT CAST(COLUMN_NAME AS CHAR), CAST(COLUMN_TYPE AS CHAR), CAST(IS_NULLABLE AS CHAR), \
+             CAST(COLUMN_DEFAULT AS CHAR), CAST(EXTRA AS CHAR), CAST(COLUMN_COMMENT AS CHAR), \
+             CAST(GENERATION_EXPRESSION AS CHAR) \
+             FROM information_schema.COLUMNS \
+             WHERE TABLE_SCHEMA = COALESCE(?, DATABASE()) AND TABLE_NAME = ? \
+             ORDER BY ORDINAL_POSITION",
+        )
+        .bind(schema_name)
+        .bind(table_name)
+        .fetch_all(self.pool)
+        .await?;
+
+        for (name, column_type, is_nullable, default, extra, comment, generation_expression) in columns {
+            let extra = extra.to_lowercase();
+            let mut data_type = column_type.to_uppercase();
+            if extra.contains("auto_increment") {
+                data_type.push_str(" AUTO_INCREMENT");
+            }
+
+            let mut column = Column::new(&name, &data_type).nullable(is_nullable == "YES");
+            column.default = default;
+            column.comment = Some(comment).filter(|comment| !comment.is_empty());
+            if extra.contains("generated") && !extra.contains("default_generated") {
+                column.is_generated = true;
+                column.generation_expression = generation_expression.filter(|expression| !expression.is_empty());
+            }
+            table.add_column(column);
+        }
+
+        // Index columns in key order; the PRIMARY index is the primary key
+        let index_columns: Vec<(String, i64, Option<String>, String)> = sqlx::query_as(
+            "SELECT CAST(INDEX_NAME AS CHAR), CAST(NON_UNIQUE AS SIGNED), CAST(COLUMN_NAME AS CHAR), \
+             CAST(INDEX_TYPE AS CHAR) \
+             FROM information_schema.STATISTICS \
+             WHERE TABLE_SCHEMA = COALESCE(?, DATABASE()) AND TABLE_NAME = ? \
+             ORDER BY INDEX_NAME, SEQ_IN_INDEX",
+        )
+        .bind(schema_name)
+        .bind(table_name)
+        .fetch_all(self.pool)
+        .await?;
+
+        let mut indexes: Vec<Index> = Vec::new();
+        for (index_name, non_unique, column_name, index_type) in index_columns {
+            if index_name == "PRIMARY" {
+                let primary_key = table.primary_key.get_or_insert_with(|| PrimaryKey { name: None, columns: Vec::new() });
+                primary_key.columns.extend(column_name);
+                continue;
+            }
+
+            if indexes.last().map(|index| index.name != index_name).unwrap_or(true) {
+                indexes.push(Index {
+                    name: index_name,
+                    columns: Vec::new(),
+                    is_unique: non_unique == 0,
+                    method: Some(index_type.to_lowercase()),
+                    include: Vec::new(),
+                    expression: None,
+                    options: Default::default(),
+                    tablespace: None,
+                    operator_class: None,
+                });
+            }
+            if let Some(index) = indexes.last_mut() {
+                match column_name {
+                    Some(column_name) => index.columns.push(column_name),
+                    // Functional key parts (MySQL 8.0.13+) have no column name
+                    None => index.expression = Some(String::new()),
+                }
+            }
+        }
+        table.indexes = indexes;
+
+        let foreign_key_columns: Vec<(String, String, String, String, String, String)> = sqlx::query_as(
+            "SELECT CAST(k.CONSTRAINT_NAME AS CHAR), CAST(k.COLUMN_NAME AS CHAR), \
+             CAST(k.REFERENCED_TABLE_NAME AS CHAR), CAST(k.REFERENCED_COLUMN_NAME AS CHAR), \
+             CAST(r.DELETE_RULE AS CHAR), CAST(r.UPDATE_RULE AS CHAR) \
+             FROM information_schema.KEY_COLUMN_USAGE k \
+             JOIN information_schema.REFERENTIAL_CONSTRAINTS r \
+               ON r.CONSTRAINT_SCHEMA = k.CONSTRAINT_SCHEMA AND r.CONSTRAINT_NAME = k.CONSTRAINT_NAME \
+             WHERE k.TABLE_SCHEMA = COALESCE(?, DATABASE()) AND k.TABLE_NAME = ? \
+             ORDER BY k.CONSTRAINT_NAME, k.ORDINAL_POSITION",
+        )
+        .bind(schema_name)
+        .bind(table_name)
+        .fetch_all(self.pool)
+        .await?;
+
+        for (name, column, ref_table, ref_column, on_delete, on_update) in foreign_key_columns {
+            match table.foreign_keys.last_mut() {
+                Some(foreign_key) if foreign_key.name == name => {
+                    foreign_key.columns.push(column);
+                    foreign_key.ref_columns.push(ref_column);
+                }
+                _ => table.add_foreign_key(ForeignKey {
+                    name,
+                    columns: vec![column],
+                    ref_table,
+                    ref_columns: vec![ref_column],
+                    on_delete: Some(on_delete),
+                    on_update: Some(on_update),
+                    deferrable: None,
+                    match_type: None,
+                }),
+            }
+        }
+
+        Ok(Some(table))
+    }
+
+    async fn table_exists(&self, schema_name: Option<&str>, table_name: &str) -> Result<bool> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM information_schema.TABLES \
+             WHERE TABLE_SCHEMA = COALESCE(?, DATABASE()) AND TABLE_NAME = ? AND TABLE_TYPE = 'BASE TABLE'",
+        )
+        .bind(schema_name)
+        .bind(table_name)
+        .fetch_one(self.pool)
+        .await?;
+
+        Ok(count > 0)
+    }
 }
 
+#[cfg(feature = "sqlite")]
 struct SqliteAnalyzer<'a> {
     pool: &'a Pool<Sqlite>,
 }
 
+#[cfg(feature = "sqlite")]
 #[async_trait]
 impl<'a> Analyzer for SqliteAnalyzer<'a> {
     async fn analyze_schema(&self, schema_name: Option<&str>) -> Result<DatabaseSchema> {
@@ -511,4 +1082,733 @@ impl<'a> Analyzer for SqliteAnalyzer<'a> {
         // SQLite-specific implementation
         todo!("Implement SQLite view analysis")
     }
-}
\ No newline at end of file
+
+    async fn analyze_table(&self, schema_name: Option<&str>, table_name: &str) -> Result<Option<Table>> {
+        if !self.table_exists(schema_name, table_name).await? {
+            return Ok(None);
+        }
+
+        let mut table = Table::new(table_name);
+
+        // Column types are reported as declared, e.g. "INTEGER" or "VARCHAR(255)"
+        let columns: Vec<(String, String, bool, Option<String>, i64)> = sqlx::query_as(
+            "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?) ORDER BY cid",
+        )
+        .bind(table_name)
+        .fetch_all(self.pool)
+        .await?;
+
+        let mut primary_key: Vec<(i64, String)> = Vec::new();
+        for (name, data_type, not_null, default, pk) in columns {
+            if pk > 0 {
+                primary_key.push((pk, name.clone()));
+            }
+            let mut column = Column::new(&name, &data_type.to_uppercase()).nullable(!not_null && pk == 0);
+            column.default = default;
+            table.add_column(column);
+        }
+        if !primary_key.is_empty() {
+            primary_key.sort();
+            table.set_primary_key(PrimaryKey {
+                name: None,
+                columns: primary_key.into_iter().map(|(_, name)| name).collect(),
+            });
+        }
+
+        // Only indexes made by CREATE INDEX; those backing PRIMARY KEY and UNIQUE clauses are implicit
+        let indexes: Vec<(String, bool)> = sqlx::query_as(
+            "SELECT name, \"unique\" FROM pragma_index_list(?) WHERE origin = 'c' ORDER BY name",
+        )
+        .bind(table_name)
+        .fetch_all(self.pool)
+        .await?;
+
+        for (index_name, is_unique) in indexes {
+            let key_columns: Vec<(Option<String>,)> = sqlx::query_as(
+                "SELECT name FROM pragma_index_info(?) ORDER BY seqno",
+            )
+            .bind(&index_name)
+            .fetch_all(self.pool)
+            .await?;
+
+            table.add_index(Index {
+                name: index_name,
+                columns: key_columns.iter().filter_map(|(name,)| name.clone()).collect(),
+                is_unique,
+                method: None,
+                include: Vec::new(),
+                // Expression keys have no column name
+                expression: key_columns.iter().any(|(name,)| name.is_none()).then(String::new),
+                options: Default::default(),
+                tablespace: None,
+                operator_class: None,
+            });
+        }
+
+        // SQLite keeps no foreign key names, so keys are named as snapshots name them
+        let foreign_key_columns: Vec<(i64, String, String, Option<String>, String, String)> = sqlx::query_as(
+            "SELECT id, \"table\", \"from\", \"to\", on_update, on_delete \
+             FROM pragma_foreign_key_list(?) ORDER BY id, seq",
+        )
+        .bind(table_name)
+        .fetch_all(self.pool)
+        .await?;
+
+        let mut last_id = None;
+        for (id, ref_table, column, ref_column, on_update, on_delete) in foreign_key_columns {
+            // `to` is NULL when the key references the parent's primary key implicitly
+            let ref_column = ref_column.unwrap_or_default();
+            match table.foreign_keys.last_mut() {
+                Some(foreign_key) if last_id == Some(id) => {
+                    foreign_key.columns.push(column);
+                    foreign_key.ref_columns.push(ref_column);
+                }
+                _ => table.add_foreign_key(ForeignKey {
+                    name: String::new(),
+                    columns: vec![column],
+                    ref_table,
+                    ref_columns: vec![ref_column],
+                    on_delete: Some(on_delete),
+                    on_update: Some(on_update),
+                    deferrable: None,
+                    match_type: None,
+                }),
+            }
+            last_id = Some(id);
+        }
+        for foreign_key in &mut table.foreign_keys {
+            foreign_key.name = format!("fk_{}_{}", table_name, foreign_key.columns.join("_"));
+        }
+
+        Ok(Some(table))
+    }
+
+    async fn table_exists(&self, _schema_name: Option<&str>, table_name: &str) -> Result<bool> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(table_name)
+            .fetch_one(self.pool)
+            .await?;
+
+        Ok(count > 0)
+    }
+}
+#[cfg(feature = "clickhouse")]
+struct ClickHouseAnalyzer<'a> {
+    client: &'a crate::db::clickhouse::ClickHouseClient,
+}
+
+// Row types for ClickHouse's system tables, decoded from JSONEachRow
+#[cfg(feature = "clickhouse")]
+#[derive(serde::Deserialize)]
+struct ClickHouseTableRow {
+    name: String,
+    engine: String,
+    sorting_key: String,
+    partition_key: String,
+    comment: String,
+    as_select: String,
+    create_table_query: String,
+}
+
+#[cfg(feature = "clickhouse")]
+#[derive(serde::Deserialize)]
+struct ClickHouseColumnRow {
+    table: String,
+    name: String,
+    #[serde(rename = "type")]
+    data_type: String,
+    default_kind: String,
+    default_expression: String,
+    comment: String,
+    is_in_primary_key: u8,
+}
+
+#[cfg(feature = "clickhouse")]
+impl<'a> ClickHouseAnalyzer<'a> {
+    /// Tables and views in the database from `system.tables`, optionally just one
+    async fn table_rows(&self, database: &str, table_name: Option<&str>) -> Result<Vec<ClickHouseTableRow>> {
+        let mut sql = format!(
+            "SELECT name, engine, sorting_key, partition_key, comment, as_select, create_table_query \
+             FROM system.tables WHERE database = {} AND NOT is_temporary",
+            clickhouse_literal(database)
+        );
+        if let Some(table_name) = table_name {
+            sql.push_str(&format!(" AND name = {}", clickhouse_literal(table_name)));
+        }
+        self.client.query_rows(&sql).await
+    }
+
+    /// Columns of the database from `system.columns`, grouped by table in position order
+    async fn column_rows(&self, database: &str, table_name: Option<&str>) -> Result<HashMap<String, Vec<ClickHouseColumnRow>>> {
+        let mut sql = format!(
+            "SELECT table, name, type, default_kind, default_expression, comment, is_in_primary_key \
+             FROM system.columns WHERE database = {}",
+            clickhouse_literal(database)
+        );
+        if let Some(table_name) = table_name {
+            sql.push_str(&format!(" AND table = {}", clickhouse_literal(table_name)));
+        }
+        sql.push_str(" ORDER BY table, position");
+
+        let mut columns: HashMap<String, Vec<ClickHouseColumnRow>> = HashMap::new();
+        for row in self.client.query_rows::<ClickHouseColumnRow>(&sql).await? {
+            columns.entry(row.table.clone()).or_default().push(row);
+        }
+        Ok(columns)
+    }
+}
+
+#[cfg(feature = "clickhouse")]
+#[async_trait]
+impl<'a> Analyzer for ClickHouseAnalyzer<'a> {
+    async fn analyze_schema(&self, schema_name: Option<&str>) -> Result<DatabaseSchema> {
+        let database = schema_name.unwrap_or(self.client.database());
+        let mut db_schema = DatabaseSchema::new(Some(database.to_string()));
+
+        db_schema.tables = self.analyze_tables(Some(database)).await?;
+        db_schema.views = self.analyze_views(Some(database)).await?;
+
+        Ok(db_schema)
+    }
+
+    async fn analyze_tables(&self, schema_name: Option<&str>) -> Result<HashMap<String, Table>> {
+        let database = schema_name.unwrap_or(self.client.database());
+        let mut columns = self.column_rows(database, None).await?;
+
+        Ok(self
+            .table_rows(database, None)
+            .await?
+            .into_iter()
+            .filter(|row| !is_clickhouse_view(&row.engine))
+            .map(|row| {
+                let table_columns = columns.remove(&row.name).unwrap_or_default();
+                (row.name.clone(), clickhouse_table(row, table_columns))
+            })
+            .collect())
+    }
+
+    async fn analyze_views(&self, schema_name: Option<&str>) -> Result<HashMap<String, View>> {
+        let database = schema_name.unwrap_or(self.client.database());
+        let mut columns = self.column_rows(database, None).await?;
+
+        Ok(self
+            .table_rows(database, None)
+            .await?
+            .into_iter()
+            .filter(|row| is_clickhouse_view(&row.engine))
+            .map(|row| {
+                let view = View {
+                    name: row.name.clone(),
+                    definition: row.as_select,
+                    columns: columns
+                        .remove(&row.name)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(clickhouse_column)
+                        .collect(),
+                    is_materialized: row.engine == "MaterializedView",
+                    depends_on: Vec::new(),
+                    has_unique_index: false,
+                    comment: Some(row.comment).filter(|comment| !comment.is_empty()),
+                };
+                (row.name, view)
+            })
+            .collect())
+    }
+
+    async fn analyze_table(&self, schema_name: Option<&str>, table_name: &str) -> Result<Option<Table>> {
+        let database = schema_name.unwrap_or(self.client.database());
+        let row = match self.table_rows(database, Some(table_name)).await?.into_iter().next() {
+            Some(row) if !is_clickhouse_view(&row.engine) => row,
+            _ => return Ok(None),
+        };
+        let columns = self.column_rows(database, Some(table_name)).await?.remove(table_name).unwrap_or_default();
+
+        Ok(Some(clickhouse_table(row, columns)))
+    }
+
+    async fn table_exists(&self, schema_name: Option<&str>, table_name: &str) -> Result<bool> {
+        let database = schema_name.unwrap_or(self.client.database());
+        let rows = self.table_rows(database, Some(table_name)).await?;
+        Ok(rows.iter().any(|row| !is_clickhouse_view(&row.engine)))
+    }
+}
+
+/// Quote a string as a ClickHouse literal
+#[cfg(feature = "clickhouse")]
+fn clickhouse_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(feature = "clickhouse")]
+fn is_clickhouse_view(engine: &str) -> bool {
+    matches!(engine, "View" | "MaterializedView" | "LiveView" | "WindowView")
+}
+
+/// Build a table from its `system.tables` row and `system.columns` rows
+///
+/// The engine, sorting key, and partition key are reported as the `engine`, `order_by`,
+/// and `partition_by` table options the generator reads.
+#[cfg(feature = "clickhouse")]
+fn clickhouse_table(row: ClickHouseTableRow, columns: Vec<ClickHouseColumnRow>) -> Table {
+    let mut table = Table::new(&row.name);
+
+    let primary_key: Vec<String> = columns
+        .iter()
+        .filter(|column| column.is_in_primary_key == 1)
+        .map(|column| column.name.clone())
+        .collect();
+    if !primary_key.is_empty() {
+        table.primary_key = Some(crate::schema::types::PrimaryKey { name: None, columns: primary_key });
+    }
+
+    table.columns = columns.into_iter().map(clickhouse_column).collect();
+    table.constraints = clickhouse_constraints(&row.create_table_query);
+    table.comment = Some(row.comment).filter(|comment| !comment.is_empty());
+    table.options.insert("engine".to_string(), row.engine);
+    for (option, value) in [("order_by", row.sorting_key), ("partition_by", row.partition_key)] {
+        if !value.is_empty() {
+            table.options.insert(option.to_string(), value);
+        }
+    }
+
+    table
+}
+
+/// Build a column from its `system.columns` row, unwrapping `Nullable(...)` into `nullable`
+#[cfg(feature = "clickhouse")]
+fn clickhouse_column(row: ClickHouseColumnRow) -> crate::schema::types::Column {
+    let (data_type, nullable) = match row.data_type.strip_prefix("Nullable(").and_then(|t| t.strip_suffix(')')) {
+        Some(inner) => (inner.to_string(), true),
+        None => match row
+            .data_type
+            .strip_prefix("LowCardinality(Nullable(")
+            .and_then(|t| t.strip_suffix("))"))
+        {
+            Some(inner) => (format!("LowCardinality({})", inner), true),
+            None => (row.data_type.clone(), false),
+        },
+    };
+
+    let mut column = crate::schema::types::Column::new(&row.name, &data_type).nullable(nullable);
+    column.comment = Some(row.comment).filter(|comment| !comment.is_empty());
+    match row.default_kind.as_str() {
+        "DEFAULT" if !row.default_expression.is_empty() => column.default = Some(row.default_expression),
+        "MATERIALIZED" | "ALIAS" => {
+            column.is_generated = true;
+            column.generation_expression = Some(row.default_expression);
+        }
+        _ => {}
+    }
+
+    column
+}
+
+/// CHECK constraints declared in a `CREATE TABLE` statement
+///
+/// ClickHouse lists constraints nowhere but the table's DDL, where each is a
+/// `CONSTRAINT name CHECK expr` entry of the column list.
+#[cfg(feature = "clickhouse")]
+fn clickhouse_constraints(create_table_query: &str) -> Vec<crate::schema::types::Constraint> {
+    let body = match create_table_query.split_once('(') {
+        Some((_, body)) => body,
+        None => return Vec::new(),
+    };
+
+    // Split the column list at top-level commas, stopping at its closing parenthesis
+    let mut entries = vec![String::new()];
+    let (mut depth, mut quoted, mut escaped) = (0, false, false);
+    for c in body.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '\'' => quoted = !quoted,
+            _ if quoted => {}
+            '(' => depth += 1,
+            ')' if depth == 0 => break,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(entry) = entries.last_mut() {
+            entry.push(c);
+        }
+    }
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (name, rest) = entry.trim().strip_prefix("CONSTRAINT ")?.split_once(' ')?;
+            let expression = rest.trim_start().strip_prefix("CHECK ")?;
+            Some(crate::schema::types::Constraint::check(name.trim_matches('`'), expression))
+        })
+        .collect()
+}
+
+#[cfg(feature = "oracle")]
+struct OracleAnalyzer<'a> {
+    client: &'a crate::db::oracle::OracleClient,
+}
+
+// Row types for Oracle's data dictionary views, decoded from ORDS result sets
+#[cfg(feature = "oracle")]
+#[derive(serde::Deserialize)]
+struct OracleTableRow {
+    table_name: String,
+    tablespace_name: Option<String>,
+    comments: Option<String>,
+}
+
+#[cfg(feature = "oracle")]
+#[derive(serde::Deserialize)]
+struct OracleColumnRow {
+    table_name: String,
+    column_name: String,
+    data_type: String,
+    data_length: Option<i64>,
+    char_length: Option<i64>,
+    char_used: Option<String>,
+    data_precision: Option<i64>,
+    data_scale: Option<i64>,
+    nullable: String,
+    data_default: Option<String>,
+    comments: Option<String>,
+}
+
+#[cfg(feature = "oracle")]
+#[derive(serde::Deserialize)]
+struct OracleConstraintRow {
+    table_name: String,
+    constraint_name: String,
+    constraint_type: String,
+    search_condition: Option<String>,
+    generated: Option<String>,
+    delete_rule: Option<String>,
+    deferrable: Option<String>,
+    deferred: Option<String>,
+    column_name: Option<String>,
+    ref_table: Option<String>,
+    ref_column: Option<String>,
+}
+
+#[cfg(feature = "oracle")]
+#[derive(serde::Deserialize)]
+struct OracleIndexRow {
+    table_name: String,
+    index_name: String,
+    uniqueness: String,
+    index_type: String,
+    column_name: String,
+}
+
+#[cfg(feature = "oracle")]
+#[derive(serde::Deserialize)]
+struct OracleViewRow {
+    view_name: String,
+    text: Option<String>,
+    comments: Option<String>,
+}
+
+#[cfg(feature = "oracle")]
+impl<'a> OracleAnalyzer<'a> {
+    /// Tables of the schema with their columns, keys, checks, and indexes, optionally just one
+    ///
+    /// Each dictionary view is read once for the whole schema and grouped by table.
+    async fn tables(&self, owner: &str, table_name: Option<&str>) -> Result<HashMap<String, Table>> {
+        let only = |alias: &str, column: &str| match table_name {
+            Some(table_name) => format!(" AND {}.{} = {}", alias, column, oracle_literal(table_name)),
+            None => String::new(),
+        };
+
+        let table_rows: Vec<OracleTableRow> = self
+            .client
+            .query_rows(&format!(
+                "SELECT t.table_name, t.tablespace_name, c.comments FROM all_tables t \
+                 LEFT JOIN all_tab_comments c ON c.owner = t.owner AND c.table_name = t.table_name \
+                 WHERE t.owner = {owner} AND t.dropped = 'NO' AND t.nested = 'NO' AND t.secondary = 'N' \
+                 AND (t.iot_type IS NULL OR t.iot_type = 'IOT') \
+                 AND NOT EXISTS (SELECT 1 FROM all_mviews m WHERE m.owner = t.owner AND m.mview_name = t.table_name){}",
+                only("t", "table_name")
+            ))
+            .await?;
+        let mut tables: HashMap<String, Table> = table_rows
+            .into_iter()
+            .map(|row| {
+                let mut table = Table::new(&row.table_name);
+                table.tablespace = row.tablespace_name;
+                table.comment = row.comments.filter(|comment| !comment.is_empty());
+                (row.table_name, table)
+            })
+            .collect();
+        if tables.is_empty() {
+            return Ok(tables);
+        }
+
+        for (table_name, columns) in self.columns(owner, table_name).await? {
+            if let Some(table) = tables.get_mut(&table_name) {
+                table.columns = columns;
+            }
+        }
+
+        // Keys and checks; unique constraints are read back as the unique indexes behind them
+        let constraint_rows: Vec<OracleConstraintRow> = self
+            .client
+            .query_rows(&format!(
+                "SELECT c.table_name, c.constraint_name, c.constraint_type, c.search_condition, c.generated, \
+                 c.delete_rule, c.deferrable, c.deferred, cc.column_name, \
+                 r.table_name AS ref_table, rc.column_name AS ref_column \
+                 FROM all_constraints c \
+                 LEFT JOIN all_cons_columns cc ON cc.owner = c.owner AND cc.constraint_name = c.constraint_name \
+                 LEFT JOIN all_constraints r ON r.owner = c.r_owner AND r.constraint_name = c.r_constraint_name \
+                 LEFT JOIN all_cons_columns rc ON rc.owner = r.owner AND rc.constraint_name = r.constraint_name \
+                 AND rc.position = cc.position \
+                 WHERE c.owner = {owner} AND c.constraint_type IN ('P', 'R', 'C'){} \
+                 ORDER BY c.table_name, c.constraint_name, cc.position",
+                only("c", "table_name")
+            ))
+            .await?;
+        for row in constraint_rows {
+            if let Some(table) = tables.get_mut(&row.table_name) {
+                add_oracle_constraint(table, row);
+            }
+        }
+
+        // Indexes in key order, leaving out the primary key's and LOB segments'
+        let index_rows: Vec<OracleIndexRow> = self
+            .client
+            .query_rows(&format!(
+                "SELECT i.table_name, i.index_name, i.uniqueness, i.index_type, ic.column_name \
+                 FROM all_indexes i \
+                 JOIN all_ind_columns ic ON ic.index_owner = i.owner AND ic.index_name = i.index_name \
+                 WHERE i.table_owner = {owner} AND i.index_type <> 'LOB'{} \
+                 AND NOT EXISTS (SELECT 1 FROM all_constraints p WHERE p.owner = i.table_owner \
+                 AND p.table_name = i.table_name AND p.index_name = i.index_name AND p.constraint_type = 'P') \
+                 ORDER BY i.table_name, i.index_name, ic.column_position",
+                only("i", "table_name")
+            ))
+            .await?;
+        for row in index_rows {
+            let table = match tables.get_mut(&row.table_name) {
+                Some(table) => table,
+                None => continue,
+            };
+            if table.indexes.last().map(|index| index.name != row.index_name).unwrap_or(true) {
+                table.indexes.push(Index {
+                    name: row.index_name.clone(),
+                    columns: Vec::new(),
+                    is_unique: row.uniqueness == "UNIQUE",
+                    method: row.index_type.starts_with("BITMAP").then(|| "bitmap".to_string()),
+                    include: Vec::new(),
+                    // Function-based keys are hidden columns named SYS_NC...$
+                    expression: row.index_type.contains("FUNCTION-BASED").then(String::new),
+                    options: Default::default(),
+                    tablespace: None,
+                    operator_class: None,
+                });
+            }
+            if let Some(index) = table.indexes.last_mut() {
+                index.columns.push(row.column_name);
+            }
+        }
+
+        Ok(tables)
+    }
+
+    /// Columns of the schema's tables and views from `ALL_TAB_COLUMNS`, grouped by table in order
+    async fn columns(&self, owner: &str, table_name: Option<&str>) -> Result<HashMap<String, Vec<Column>>> {
+        let mut sql = format!(
+            "SELECT c.table_name, c.column_name, c.data_type, c.data_length, c.char_length, c.char_used, \
+             c.data_precision, c.data_scale, c.nullable, c.data_default, m.comments \
+             FROM all_tab_columns c \
+             LEFT JOIN all_col_comments m ON m.owner = c.owner AND m.table_name = c.table_name \
+             AND m.column_name = c.column_name \
+             WHERE c.owner = {}",
+            owner
+        );
+        if let Some(table_name) = table_name {
+            sql.push_str(&format!(" AND c.table_name = {}", oracle_literal(table_name)));
+        }
+        sql.push_str(" ORDER BY c.table_name, c.column_id");
+
+        let mut columns: HashMap<String, Vec<Column>> = HashMap::new();
+        for row in self.client.query_rows::<OracleColumnRow>(&sql).await? {
+            columns.entry(row.table_name.clone()).or_default().push(oracle_column(row));
+        }
+        Ok(columns)
+    }
+}
+
+#[cfg(feature = "oracle")]
+#[async_trait]
+impl<'a> Analyzer for OracleAnalyzer<'a> {
+    async fn analyze_schema(&self, schema_name: Option<&str>) -> Result<DatabaseSchema> {
+        let mut db_schema = DatabaseSchema::new(schema_name.map(str::to_string));
+
+        db_schema.tables = self.analyze_tables(schema_name).await?;
+        db_schema.views = self.analyze_views(schema_name).await?;
+
+        Ok(db_schema)
+    }
+
+    async fn analyze_tables(&self, schema_name: Option<&str>) -> Result<HashMap<String, Table>> {
+        self.tables(&oracle_owner(schema_name), None).await
+    }
+
+    async fn analyze_views(&self, schema_name: Option<&str>) -> Result<HashMap<String, View>> {
+        let owner = oracle_owner(schema_name);
+        let mut columns = self.columns(&owner, None).await?;
+
+        let views: Vec<OracleViewRow> = self
+            .client
+            .query_rows(&format!(
+                "SELECT v.view_name, v.text, c.comments FROM all_views v \
+                 LEFT JOIN all_tab_comments c ON c.owner = v.owner AND c.table_name = v.view_name \
+                 WHERE v.owner = {}",
+                owner
+            ))
+            .await?;
+        let materialized: Vec<OracleViewRow> = self
+            .client
+            .query_rows(&format!(
+                "SELECT m.mview_name AS view_name, m.query AS text, c.comments FROM all_mviews m \
+                 LEFT JOIN all_mview_comments c ON c.owner = m.owner AND c.mview_name = m.mview_name \
+                 WHERE m.owner = {}",
+                owner
+            ))
+            .await?;
+
+        let rows = views.into_iter().map(|row| (row, false)).chain(materialized.into_iter().map(|row| (row, true)));
+        Ok(rows
+            .map(|(row, is_materialized)| {
+                let view = View {
+                    name: row.view_name.clone(),
+                    definition: row.text.unwrap_or_default().trim().to_string(),
+                    columns: columns.remove(&row.view_name).unwrap_or_default(),
+                    is_materialized,
+                    depends_on: Vec::new(),
+                    has_unique_index: false,
+                    comment: row.comments.filter(|comment| !comment.is_empty()),
+                };
+                (row.view_name, view)
+            })
+            .collect())
+    }
+
+    async fn analyze_table(&self, schema_name: Option<&str>, table_name: &str) -> Result<Option<Table>> {
+        Ok(self.tables(&oracle_owner(schema_name), Some(table_name)).await?.remove(table_name))
+    }
+
+    async fn table_exists(&self, schema_name: Option<&str>, table_name: &str) -> Result<bool> {
+        let count = self
+            .client
+            .query_column(&format!(
+                "SELECT COUNT(*) FROM all_tables WHERE owner = {} AND table_name = {} AND dropped = 'NO'",
+                oracle_owner(schema_name),
+                oracle_literal(table_name)
+            ))
+            .await?;
+        Ok(count.into_iter().flatten().next().is_some_and(|count| count != "0"))
+    }
+}
+
+/// Quote a string as an Oracle literal
+#[cfg(feature = "oracle")]
+fn oracle_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Owner to read the dictionary views for: the named schema, or the session's current one
+#[cfg(feature = "oracle")]
+fn oracle_owner(schema_name: Option<&str>) -> String {
+    match schema_name {
+        Some(schema_name) => oracle_literal(schema_name),
+        None => "SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA')".to_string(),
+    }
+}
+
+/// Build a column from its `ALL_TAB_COLUMNS` row, in the types the generator writes
+///
+/// Identity columns report their sequence as the default, which is left out. `NUMBER(1)`
+/// defaults of 1 and 0 are booleans, as the generator writes them.
+#[cfg(feature = "oracle")]
+fn oracle_column(row: OracleColumnRow) -> Column {
+    let data_type = crate::schema::dialect::oracle_type(
+        &row.data_type,
+        row.data_length,
+        row.char_length,
+        row.char_used.as_deref(),
+        row.data_precision,
+        row.data_scale,
+    );
+    let default = row
+        .data_default
+        .map(|default| default.trim().to_string())
+        .filter(|default| !default.is_empty() && !default.to_uppercase().contains("ISEQ$$_"))
+        .map(|default| match (data_type.as_str(), default.as_str()) {
+            ("NUMBER(1)", "1") => "true".to_string(),
+            ("NUMBER(1)", "0") => "false".to_string(),
+            _ => default,
+        });
+
+    let mut column = Column::new(&row.column_name, &data_type).nullable(row.nullable == "Y");
+    column.default = default;
+    column.comment = row.comments.filter(|comment| !comment.is_empty());
+    column
+}
+
+/// Add one `ALL_CONSTRAINTS` row, a constraint and one of its columns, to the table
+///
+/// Rows of the same constraint arrive together in column order. The NOT NULL checks
+/// Oracle names itself are left out, since they are the columns' nullability.
+#[cfg(feature = "oracle")]
+fn add_oracle_constraint(table: &mut Table, row: OracleConstraintRow) {
+    match row.constraint_type.as_str() {
+        "P" => {
+            let primary_key = table.primary_key.get_or_insert_with(|| PrimaryKey {
+                name: Some(row.constraint_name.clone()),
+                columns: Vec::new(),
+            });
+            primary_key.columns.extend(row.column_name);
+        }
+        "R" => {
+            let (column, ref_column) = match (row.column_name, row.ref_column) {
+                (Some(column), Some(ref_column)) => (column, ref_column),
+                _ => return,
+            };
+            match table.foreign_keys.last_mut() {
+                Some(foreign_key) if foreign_key.name == row.constraint_name => {
+                    foreign_key.columns.push(column);
+                    foreign_key.ref_columns.push(ref_column);
+                }
+                _ => table.add_foreign_key(ForeignKey {
+                    name: row.constraint_name,
+                    columns: vec![column],
+                    ref_table: row.ref_table.unwrap_or_default(),
+                    ref_columns: vec![ref_column],
+                    on_delete: row.delete_rule,
+                    on_update: None,
+                    deferrable: match (row.deferrable.as_deref(), row.deferred.as_deref()) {
+                        (Some("DEFERRABLE"), Some("DEFERRED")) => Some("initially_deferred".to_string()),
+                        (Some("DEFERRABLE"), _) => Some("initially_immediate".to_string()),
+                        _ => None,
+                    },
+                    match_type: None,
+                }),
+            }
+        }
+        "C" => {
+            let condition = row.search_condition.unwrap_or_default();
+            let condition = condition.trim();
+            let not_null = row.generated.as_deref() == Some("GENERATED NAME")
+                && condition.to_uppercase().ends_with(" IS NOT NULL");
+            let seen = table.constraints.iter().any(|constraint| constraint.name == row.constraint_name);
+            if !not_null && !seen && !condition.is_empty() {
+                table.constraints.push(crate::schema::types::Constraint::check(&row.constraint_name, condition));
+            }
+        }
+        _ => {}
+    }
+}
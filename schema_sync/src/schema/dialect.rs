@@ -0,0 +1,608 @@
+//! SQL dialects
+//!
+//! This module describes what each supported database can express, so the generator
+//! can decide per feature whether to emit SQL or report that it has to be skipped.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::schema::types::Constraint;
+
+/// SQL dialect targeted by the migration generator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+    /// Experimental; column-oriented, so there are no foreign keys or secondary indexes
+    ClickHouse,
+    Oracle,
+}
+
+/// Features a dialect supports
+///
+/// The generator consults these before emitting SQL: a missing feature is either left out
+/// with a warning (comments, deferrable constraints) or reported as an
+/// `Error::UnsupportedOperation` naming the `Capability`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DialectCapabilities {
+    /// Comments on tables (`COMMENT ON TABLE` / `COMMENT=` table option)
+    pub table_comments: bool,
+    /// Comments on columns (`COMMENT ON COLUMN` / inline `COMMENT`)
+    pub column_comments: bool,
+    /// `DEFERRABLE` foreign keys checked at commit time
+    pub deferrable_constraints: bool,
+    /// `EXCLUDE USING ...` constraints
+    pub exclusion_constraints: bool,
+    /// Adding or dropping CHECK constraints on an existing table
+    pub alter_check_constraints: bool,
+    /// Dropping a column from an existing table
+    pub drop_column: bool,
+    /// Changing a column's type, nullability, or default in place
+    pub alter_column: bool,
+    /// Adding a foreign key to an existing table
+    pub add_foreign_key: bool,
+    /// Dropping a foreign key from an existing table
+    pub drop_foreign_key: bool,
+    /// Building an index without blocking writes (`CREATE INDEX CONCURRENTLY`)
+    pub concurrent_index: bool,
+    /// DDL that can be rolled back with the transaction it ran in
+    pub transactional_ddl: bool,
+}
+
+impl DialectCapabilities {
+    /// Whether the dialect has `capability`
+    pub fn supports(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::TableComments => self.table_comments,
+            Capability::ColumnComments => self.column_comments,
+            Capability::DeferrableConstraints => self.deferrable_constraints,
+            Capability::ExclusionConstraints => self.exclusion_constraints,
+            Capability::AlterCheckConstraints => self.alter_check_constraints,
+            Capability::DropColumn => self.drop_column,
+            Capability::AlterColumn => self.alter_column,
+            Capability::AddForeignKey => self.add_foreign_key,
+            Capability::DropForeignKey => self.drop_foreign_key,
+            Capability::ConcurrentIndex => self.concurrent_index,
+            Capability::TransactionalDdl => self.transactional_ddl,
+        }
+    }
+}
+
+/// One feature of `DialectCapabilities`, as named in errors and diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    TableComments,
+    ColumnComments,
+    DeferrableConstraints,
+    ExclusionConstraints,
+    AlterCheckConstraints,
+    DropColumn,
+    AlterColumn,
+    AddForeignKey,
+    DropForeignKey,
+    ConcurrentIndex,
+    TransactionalDdl,
+}
+
+impl Capability {
+    /// Every capability, in declaration order
+    pub const ALL: [Capability; 11] = [
+        Capability::TableComments,
+        Capability::ColumnComments,
+        Capability::DeferrableConstraints,
+        Capability::ExclusionConstraints,
+        Capability::AlterCheckConstraints,
+        Capability::DropColumn,
+        Capability::AlterColumn,
+        Capability::AddForeignKey,
+        Capability::DropForeignKey,
+        Capability::ConcurrentIndex,
+        Capability::TransactionalDdl,
+    ];
+
+    /// Name of the capability, e.g. "drop_column"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Capability::TableComments => "table_comments",
+            Capability::ColumnComments => "column_comments",
+            Capability::DeferrableConstraints => "deferrable_constraints",
+            Capability::ExclusionConstraints => "exclusion_constraints",
+            Capability::AlterCheckConstraints => "alter_check_constraints",
+            Capability::DropColumn => "drop_column",
+            Capability::AlterColumn => "alter_column",
+            Capability::AddForeignKey => "add_foreign_key",
+            Capability::DropForeignKey => "drop_foreign_key",
+            Capability::ConcurrentIndex => "concurrent_index",
+            Capability::TransactionalDdl => "transactional_ddl",
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Dialect {
+    /// Dialect for a `database.driver` name
+    pub fn from_driver(driver: &str) -> Option<Self> {
+        match driver {
+            "postgres" => Some(Dialect::Postgres),
+            "mysql" => Some(Dialect::MySql),
+            "sqlite" => Some(Dialect::Sqlite),
+            "clickhouse" => Some(Dialect::ClickHouse),
+            "oracle" => Some(Dialect::Oracle),
+            _ => None,
+        }
+    }
+
+    /// Name used for this dialect in `database.driver`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dialect::Postgres => "postgres",
+            Dialect::MySql => "mysql",
+            Dialect::Sqlite => "sqlite",
+            Dialect::ClickHouse => "clickhouse",
+            Dialect::Oracle => "oracle",
+        }
+    }
+
+    /// Column definition of an auto-incrementing integer primary key, for SchemaSync's own tables
+    ///
+    /// ClickHouse has no auto-increment, so its tables get a generated UUID instead.
+    /// Oracle's is an identity column, which needs 12c or later.
+    pub fn serial_primary_key(&self) -> &'static str {
+        match self {
+            Dialect::Postgres => "SERIAL PRIMARY KEY",
+            Dialect::MySql => "INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY",
+            Dialect::Sqlite => "INTEGER PRIMARY KEY AUTOINCREMENT",
+            Dialect::ClickHouse => "UUID DEFAULT generateUUIDv4()",
+            Dialect::Oracle => "NUMBER(19) GENERATED BY DEFAULT ON NULL AS IDENTITY PRIMARY KEY",
+        }
+    }
+
+    /// Type of a point-in-time column, for SchemaSync's own tables
+    ///
+    /// MySQL `TIMESTAMP` values are stored in UTC; SQLite keeps whatever text is written.
+    pub fn timestamp_type(&self) -> &'static str {
+        match self {
+            Dialect::Postgres | Dialect::Oracle => "TIMESTAMP WITH TIME ZONE",
+            Dialect::MySql | Dialect::Sqlite => "TIMESTAMP",
+            Dialect::ClickHouse => "DateTime64(6)",
+        }
+    }
+
+    /// Expression for the current time, as a column default
+    pub fn current_timestamp(&self) -> &'static str {
+        match self {
+            Dialect::Postgres | Dialect::MySql | Dialect::Sqlite | Dialect::Oracle => "CURRENT_TIMESTAMP",
+            Dialect::ClickHouse => "now64(6)",
+        }
+    }
+
+    /// Column default that generates a UUID, given the function a model asked for
+    ///
+    /// Postgres keeps the requested function (`gen_random_uuid()` or `uuid_generate_v4()`),
+    /// MySQL 8 uses `UUID()`, Oracle `SYS_GUID()` (16 raw bytes), and SQLite returns `None`
+    /// because it has no UUID function, so the application has to supply the value.
+    pub fn uuid_default(&self, requested: &str) -> Option<String> {
+        match self {
+            Dialect::Postgres => Some(requested.to_string()),
+            Dialect::MySql => Some("(UUID())".to_string()),
+            Dialect::Sqlite => None,
+            Dialect::ClickHouse => Some("generateUUIDv4()".to_string()),
+            Dialect::Oracle => Some("SYS_GUID()".to_string()),
+        }
+    }
+
+    /// Function giving the length of a string in characters
+    pub fn char_length(&self) -> &'static str {
+        match self {
+            Dialect::Postgres => "char_length",
+            Dialect::MySql => "CHAR_LENGTH",
+            Dialect::Sqlite => "length",
+            Dialect::ClickHouse => "lengthUTF8",
+            Dialect::Oracle => "LENGTH",
+        }
+    }
+
+    /// Expression matching a column against a regular expression, if the dialect has one
+    ///
+    /// SQLite parses `REGEXP` but only runs it when the application registers a
+    /// `regexp()` function, so patterns are left to the application there.
+    pub fn regex_match(&self, column: &str, pattern: &str) -> Option<String> {
+        let pattern = pattern.replace('\'', "''");
+        match self {
+            Dialect::Postgres => Some(format!("{} ~ '{}'", column, pattern)),
+            Dialect::MySql | Dialect::Oracle => Some(format!("REGEXP_LIKE({}, '{}')", column, pattern)),
+            Dialect::Sqlite => None,
+            // Backslashes are escapes in ClickHouse string literals
+            Dialect::ClickHouse => Some(format!("match({}, '{}')", column, pattern.replace('\\', "\\\\"))),
+        }
+    }
+
+    /// The name the database stores for an identifier as written in DDL
+    ///
+    /// Postgres folds unquoted identifiers to lowercase and keeps quoted ones exactly.
+    /// MySQL column names and SQLite identifiers are case-insensitive, so they are
+    /// lowercased whether quoted or not. ClickHouse keeps every identifier as written, and
+    /// Oracle folds unquoted identifiers to uppercase.
+    pub fn fold_identifier(&self, identifier: &str) -> String {
+        let quoted = |open: char, close: char| {
+            identifier
+                .strip_prefix(open)
+                .and_then(|rest| rest.strip_suffix(close))
+        };
+
+        match self {
+            Dialect::Postgres => match quoted('"', '"') {
+                Some(name) => name.replace("\"\"", "\""),
+                None => identifier.to_lowercase(),
+            },
+            Dialect::MySql => quoted('`', '`').unwrap_or(identifier).to_lowercase(),
+            Dialect::Sqlite => quoted('"', '"')
+                .or_else(|| quoted('`', '`'))
+                .or_else(|| quoted('[', ']'))
+                .unwrap_or(identifier)
+                .to_lowercase(),
+            Dialect::ClickHouse => quoted('`', '`')
+                .or_else(|| quoted('"', '"'))
+                .unwrap_or(identifier)
+                .to_string(),
+            Dialect::Oracle => match quoted('"', '"') {
+                Some(name) => name.to_string(),
+                None => identifier.to_uppercase(),
+            },
+        }
+    }
+
+    /// Check whether an identifier written in a model names an object the database reports as `stored`
+    pub fn same_identifier(&self, declared: &str, stored: &str) -> bool {
+        match self {
+            Dialect::Postgres | Dialect::ClickHouse | Dialect::Oracle => self.fold_identifier(declared) == stored,
+            Dialect::MySql | Dialect::Sqlite => self.fold_identifier(declared) == self.fold_identifier(stored),
+        }
+    }
+
+    /// Features this dialect supports
+    ///
+    /// SQLite alters existing tables only by adding and renaming columns; anything else
+    /// there means recreating the table. MySQL commits implicitly after each DDL statement.
+    /// ClickHouse has no foreign keys at all and no DDL transactions.
+    /// Oracle commits before and after every DDL statement.
+    pub fn capabilities(&self) -> DialectCapabilities {
+        match self {
+            Dialect::Postgres => DialectCapabilities {
+                table_comments: true,
+                column_comments: true,
+                deferrable_constraints: true,
+                exclusion_constraints: true,
+                alter_check_constraints: true,
+                drop_column: true,
+                alter_column: true,
+                add_foreign_key: true,
+                drop_foreign_key: true,
+                concurrent_index: true,
+                transactional_ddl: true,
+            },
+            Dialect::MySql => DialectCapabilities {
+                table_comments: true,
+                column_comments: true,
+                deferrable_constraints: false,
+                exclusion_constraints: false,
+                alter_check_constraints: true,
+                drop_column: true,
+                alter_column: true,
+                add_foreign_key: true,
+                drop_foreign_key: true,
+                concurrent_index: false,
+                transactional_ddl: false,
+            },
+            Dialect::Sqlite => DialectCapabilities {
+                table_comments: false,
+                column_comments: false,
+                deferrable_constraints: true,
+                exclusion_constraints: false,
+                alter_check_constraints: false,
+                drop_column: false,
+                alter_column: false,
+                add_foreign_key: false,
+                drop_foreign_key: false,
+                concurrent_index: false,
+                transactional_ddl: true,
+            },
+            Dialect::ClickHouse => DialectCapabilities {
+                table_comments: true,
+                column_comments: true,
+                deferrable_constraints: false,
+                exclusion_constraints: false,
+                alter_check_constraints: true,
+                drop_column: true,
+                alter_column: true,
+                add_foreign_key: false,
+                drop_foreign_key: false,
+                concurrent_index: false,
+                transactional_ddl: false,
+            },
+            Dialect::Oracle => DialectCapabilities {
+                table_comments: true,
+                column_comments: true,
+                deferrable_constraints: true,
+                exclusion_constraints: false,
+                alter_check_constraints: true,
+                drop_column: true,
+                alter_column: true,
+                add_foreign_key: true,
+                drop_foreign_key: true,
+                concurrent_index: false,
+                transactional_ddl: false,
+            },
+        }
+    }
+}
+
+/// Check whether a default expression generates a UUID
+pub fn is_uuid_generator(default: &str) -> bool {
+    matches!(
+        normalize_default(default).as_str(),
+        "gen_random_uuid()" | "uuid_generate_v1()" | "uuid_generate_v4()" | "uuid()"
+    )
+}
+
+/// Extension that provides a default expression's function on Postgres, if any
+pub fn required_postgres_extension(default: &str) -> Option<&'static str> {
+    if normalize_default(default).starts_with("uuid_generate_v") {
+        Some("uuid-ossp")
+    } else {
+        None
+    }
+}
+
+/// Extension that provides a column type on Postgres, if any
+pub fn type_postgres_extension(data_type: &str) -> Option<&'static str> {
+    let data_type = data_type.trim().to_lowercase();
+    match data_type.split('(').next().unwrap_or_default().trim() {
+        "citext" => Some("citext"),
+        "hstore" => Some("hstore"),
+        "vector" => Some("vector"),
+        _ => None,
+    }
+}
+
+/// ClickHouse spelling of a column type written the way the registry writes it
+///
+/// Types that are already ClickHouse names (`LowCardinality(String)`, `DateTime64(3)`)
+/// pass through, so the mapping can be applied more than once. Nullability is not part
+/// of the result; see [`clickhouse_nullable`].
+pub fn clickhouse_type(data_type: &str) -> String {
+    let data_type = data_type.trim();
+    if let Some(element) = data_type.strip_suffix("[]") {
+        return format!("Array({})", clickhouse_type(element));
+    }
+
+    let lower = data_type.to_lowercase();
+    let (base, params) = match lower.split_once('(') {
+        Some((base, rest)) => (base.trim(), rest.split(')').next().unwrap_or_default()),
+        None => (lower.as_str(), ""),
+    };
+    let params: Vec<&str> = params.split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+
+    match base {
+        "smallint" | "int2" | "smallserial" => "Int16".to_string(),
+        "integer" | "int" | "int4" | "serial" => "Int32".to_string(),
+        "bigint" | "int8" | "bigserial" | "interval" => "Int64".to_string(),
+        "real" | "float4" => "Float32".to_string(),
+        "double precision" | "float8" => "Float64".to_string(),
+        "numeric" | "decimal" => match params.as_slice() {
+            [precision, scale] => format!("Decimal({}, {})", precision, scale),
+            [precision] => format!("Decimal({}, 0)", precision),
+            _ => "Decimal(38, 10)".to_string(),
+        },
+        "boolean" | "bool" => "Bool".to_string(),
+        "date" => "Date32".to_string(),
+        "timestamp" | "timestamptz" => {
+            let precision = params.first().copied().unwrap_or("6");
+            if base == "timestamptz" || lower.contains("with time zone") {
+                format!("DateTime64({}, 'UTC')", precision)
+            } else {
+                format!("DateTime64({})", precision)
+            }
+        }
+        "uuid" => "UUID".to_string(),
+        "hstore" => "Map(String, String)".to_string(),
+        "vector" => "Array(Float32)".to_string(),
+        "varchar" | "character varying" | "char" | "character" | "text" | "citext" | "json" | "jsonb"
+        | "bytea" | "time" | "inet" | "bit" | "bit varying" => "String".to_string(),
+        t if t.ends_with("range") => "String".to_string(),
+        _ => data_type.to_string(),
+    }
+}
+
+/// Wrap a ClickHouse type so it accepts NULL
+///
+/// Arrays and maps cannot be `Nullable` and store empty values instead; `LowCardinality`
+/// takes the wrapper inside.
+pub fn clickhouse_nullable(data_type: &str) -> String {
+    if let Some(inner) = data_type.strip_prefix("LowCardinality(").and_then(|t| t.strip_suffix(')')) {
+        return format!("LowCardinality({})", clickhouse_nullable(inner));
+    }
+
+    if ["Nullable(", "Array(", "Map(", "Tuple("].iter().any(|prefix| data_type.starts_with(prefix)) {
+        data_type.to_string()
+    } else {
+        format!("Nullable({})", data_type)
+    }
+}
+
+/// Oracle column type as the generator writes it, from the fields of an `ALL_TAB_COLUMNS` row
+///
+/// `NUMBER` keeps its precision and scale, `VARCHAR2` and `CHAR` their length in characters
+/// (`VARCHAR2(n CHAR)`) or bytes, and `RAW` its size. Timestamps and intervals leave out
+/// their default precisions, as the generator does.
+pub fn oracle_type(
+    data_type: &str,
+    data_length: Option<i64>,
+    char_length: Option<i64>,
+    char_used: Option<&str>,
+    precision: Option<i64>,
+    scale: Option<i64>,
+) -> String {
+    let data_type = data_type.trim().to_uppercase();
+
+    match data_type.as_str() {
+        "NUMBER" => match (precision, scale) {
+            (Some(precision), Some(0) | None) => format!("NUMBER({})", precision),
+            (Some(precision), Some(scale)) => format!("NUMBER({},{})", precision, scale),
+            (None, Some(0)) => "INTEGER".to_string(),
+            (None, _) => "NUMBER".to_string(),
+        },
+        "FLOAT" => match precision {
+            Some(precision) => format!("FLOAT({})", precision),
+            None => "FLOAT".to_string(),
+        },
+        "VARCHAR2" | "CHAR" => match (char_used, char_length) {
+            (Some("C"), Some(length)) => format!("{}({} CHAR)", data_type, length),
+            _ => format!("{}({})", data_type, data_length.unwrap_or_default()),
+        },
+        "NVARCHAR2" | "NCHAR" => format!("{}({})", data_type, char_length.unwrap_or_default()),
+        "RAW" => format!("RAW({})", data_length.unwrap_or_default()),
+        _ => data_type
+            .replace("TIMESTAMP(6)", "TIMESTAMP")
+            .replace("INTERVAL DAY(2) TO SECOND(6)", "INTERVAL DAY TO SECOND"),
+    }
+}
+
+/// Wrap an Oracle statement in a PL/SQL block that ignores the given errors
+///
+/// Oracle has no `IF EXISTS` or `IF NOT EXISTS` before 23ai, so statements are made safe to
+/// run twice by catching the error of an object that is already there (ORA-00955) or
+/// already gone (ORA-00942). `codes` are the ORA numbers, without the sign.
+pub fn oracle_guarded(statement: &str, codes: &[u32]) -> String {
+    let codes: Vec<String> = codes.iter().map(|code| format!("-{}", code)).collect();
+    format!(
+        "BEGIN EXECUTE IMMEDIATE '{}'; EXCEPTION WHEN OTHERS THEN IF SQLCODE NOT IN ({}) THEN RAISE; END IF; END;",
+        statement.trim().trim_end_matches(';').replace('\'', "''"),
+        codes.join(", ")
+    )
+}
+
+/// Extension a Postgres constraint depends on, if any
+///
+/// GiST exclusion constraints that compare plain scalars with `=` (the usual
+/// "same room, overlapping period" shape) need the operator classes from `btree_gist`.
+pub fn constraint_postgres_extension(constraint: &Constraint) -> Option<&'static str> {
+    let definition = constraint.definition.to_lowercase();
+
+    if constraint.constraint_type == "EXCLUDE"
+        && definition.starts_with("exclude using gist")
+        && definition.contains("with =")
+    {
+        Some("btree_gist")
+    } else {
+        None
+    }
+}
+
+/// Normalize a column default so model and introspected values compare equal
+///
+/// Strips wrapping parentheses (MySQL reports `(UUID())` as `uuid()`), Postgres casts on
+/// literals (`'a'::character varying`), and case differences outside string literals.
+pub fn normalize_default(default: &str) -> String {
+    let mut expr = default.trim();
+
+    while expr.starts_with('(') && expr.ends_with(')') && wraps_whole(expr) {
+        expr = expr[1..expr.len() - 1].trim();
+    }
+
+    if expr.starts_with('\'') {
+        if let Some(end) = literal_end(expr) {
+            if expr[end..].starts_with("::") {
+                return expr[..end].to_string();
+            }
+        }
+        expr.to_string()
+    } else {
+        expr.to_lowercase()
+    }
+}
+
+/// Normalize an index expression so model and introspected values compare equal
+///
+/// Postgres reports `lower(email)` as `lower((email)::text)`, so casts, parentheses,
+/// whitespace, and case outside string literals are all dropped. The result is only
+/// meant for comparison.
+pub fn normalize_expression(expr: &str) -> String {
+    // Words that continue a multi-word type name after a cast
+    const TYPE_WORDS: [&str; 6] = ["varying", "with", "without", "time", "zone", "precision"];
+
+    let mut normalized = String::new();
+    let mut rest = expr.trim();
+
+    while let Some(c) = rest.chars().next() {
+        if c == '\'' {
+            let end = literal_end(rest).unwrap_or(rest.len());
+            normalized.push_str(&rest[..end]);
+            rest = &rest[end..];
+        } else if let Some(cast) = rest.strip_prefix("::") {
+            // Skip the type name, including multi-word names like "character varying"
+            let mut tail = cast.trim_start();
+            loop {
+                let word_end = tail
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '"' || c == '[' || c == ']'))
+                    .unwrap_or(tail.len());
+                tail = &tail[word_end..];
+                let next = tail.trim_start();
+                match TYPE_WORDS.iter().find(|word| next.to_lowercase().starts_with(*word)) {
+                    Some(_) if next.len() < tail.len() => tail = next,
+                    _ => break,
+                }
+            }
+            rest = tail;
+        } else {
+            if !(c.is_whitespace() || c == '(' || c == ')') {
+                normalized.extend(c.to_lowercase());
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    normalized
+}
+
+/// Check whether the opening parenthesis of `expr` closes at its last character
+pub(crate) fn wraps_whole(expr: &str) -> bool {
+    let mut depth = 0;
+    for (i, c) in expr.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i == expr.len() - 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Byte offset just past the closing quote of a leading string literal
+fn literal_end(expr: &str) -> Option<usize> {
+    let bytes = expr.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        if bytes[i] == b'\'' {
+            if bytes.get(i + 1) == Some(&b'\'') {
+                i += 2;
+                continue;
+            }
+            return Some(i + 1);
+        }
+        i += 1;
+    }
+    None
+}
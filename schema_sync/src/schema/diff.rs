@@ -2,11 +2,17 @@
 //!
 //! This module compares two database schemas and calculates the differences
 
-use std::collections::{HashMap, HashSet};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
 
 use crate::config::SchemaConfig;
 use crate::error::Result;
-use crate::schema::types::{Column, DatabaseSchema, Table};
+use crate::schema::dialect::normalize_default;
+use crate::schema::graph::{DependencyGraph, SchemaNode};
+use crate::schema::stats::TableStats;
+use crate::schema::types::{normalize_referential_action, Column, Constraint, DatabaseSchema, ForeignKey, Index, Table, View};
 
 /// Represents changes needed to synchronize two schemas
 #[derive(Debug, Clone)]
@@ -16,10 +22,27 @@ pub struct SchemaDiff {
     pub columns_to_add: HashMap<String, Vec<Column>>,
     pub columns_to_drop: HashMap<String, Vec<String>>,
     pub columns_to_alter: HashMap<String, Vec<ColumnChange>>,
-    pub indices_to_create: HashMap<String, Vec<String>>,
+    pub indices_to_create: HashMap<String, Vec<Index>>,
     pub indices_to_drop: HashMap<String, Vec<String>>,
-    pub foreign_keys_to_create: HashMap<String, Vec<String>>,
+    pub foreign_keys_to_create: HashMap<String, Vec<ForeignKey>>,
     pub foreign_keys_to_drop: HashMap<String, Vec<String>>,
+    pub constraints_to_create: HashMap<String, Vec<Constraint>>,
+    pub constraints_to_drop: HashMap<String, Vec<String>>,
+    /// Postgres enum types the models use that don't exist yet, with their labels
+    pub enum_types_to_create: BTreeMap<String, Vec<String>>,
+    /// Labels missing from existing Postgres enum types
+    pub enum_values_to_add: BTreeMap<String, Vec<String>>,
+    /// Tables to move to another tablespace, by table name
+    pub tablespaces_to_set: HashMap<String, String>,
+    /// Tables whose comment the models change, with the new comment
+    pub table_comments_to_set: HashMap<String, String>,
+    /// Existing views that read altered columns, dependencies first. They are dropped
+    /// before the columns change and recreated from their definitions afterwards.
+    pub views_to_recreate: Vec<View>,
+    /// Existing materialized views over changed tables, refreshed once the migration is done
+    pub materialized_views_to_refresh: Vec<View>,
+    /// Objects that use a dropped or altered column, keyed by `table.column`
+    pub impacts: BTreeMap<String, Vec<ImpactedObject>>,
 }
 
 impl SchemaDiff {
@@ -29,13 +52,15 @@ impl SchemaDiff {
         target_schema: DatabaseSchema, 
         schema_config: &SchemaConfig
     ) -> Self {
-        // Tables to create (in target but not in current)
-        let tables_to_create = target_schema
-            .tables
-            .values()
-            .filter(|table| !current_schema.tables.contains_key(&table.name))
-            .cloned()
-            .collect();
+        // Tables to create (in target but not in current), referenced tables first
+        let tables_to_create = Self::order_by_dependencies(
+            target_schema
+                .tables
+                .values()
+                .filter(|table| !current_schema.tables.contains_key(&table.name))
+                .cloned()
+                .collect(),
+        );
             
         // Tables to drop (in current but not in target)
         let tables_to_drop = if schema_config.allow_table_removal {
@@ -49,10 +74,18 @@ impl SchemaDiff {
             Vec::new()
         };
         
-        // Process tables that exist in both schemas for column changes
+        // Process tables that exist in both schemas for column and foreign key changes
         let mut columns_to_add = HashMap::new();
         let mut columns_to_drop = HashMap::new();
         let mut columns_to_alter = HashMap::new();
+        let mut foreign_keys_to_create = HashMap::new();
+        let mut foreign_keys_to_drop = HashMap::new();
+        let mut constraints_to_create = HashMap::new();
+        let mut indices_to_create = HashMap::new();
+        let mut indices_to_drop = HashMap::new();
+        let mut tablespaces_to_set = HashMap::new();
+        let mut table_comments_to_set = HashMap::new();
+        let mut constraints_to_drop = HashMap::new();
         
         for (table_name, target_table) in &target_schema.tables {
             if let Some(current_table) = current_schema.tables.get(table_name) {
@@ -119,10 +152,155 @@ impl SchemaDiff {
                 if !alter_columns.is_empty() {
                     columns_to_alter.insert(table_name.clone(), alter_columns);
                 }
+                
+                // Foreign keys to add, matched by definition since generated and
+                // introspected constraint names may differ. A key whose actions or
+                // deferrability changed is dropped and re-added.
+                let mut add_foreign_keys = Vec::new();
+                let mut drop_foreign_keys = Vec::new();
+                for target_fk in &target_table.foreign_keys {
+                    let current_fk = current_table.foreign_keys.iter().find(|current_fk| {
+                        current_fk.columns == target_fk.columns
+                            && current_fk.ref_table == target_fk.ref_table
+                            && current_fk.ref_columns == target_fk.ref_columns
+                    });
+                    
+                    match current_fk {
+                        None => add_foreign_keys.push(target_fk.clone()),
+                        Some(current_fk) if !Self::foreign_key_options_match(current_fk, target_fk) => {
+                            drop_foreign_keys.push(current_fk.name.clone());
+                            add_foreign_keys.push(target_fk.clone());
+                        }
+                        Some(_) => {}
+                    }
+                }
+                
+                if !add_foreign_keys.is_empty() {
+                    foreign_keys_to_create.insert(table_name.clone(), add_foreign_keys);
+                }
+                if !drop_foreign_keys.is_empty() {
+                    foreign_keys_to_drop.insert(table_name.clone(), drop_foreign_keys);
+                }
+                
+                // Tablespace, when the model places the table explicitly
+                if let Some(tablespace) = &target_table.tablespace {
+                    if current_table.tablespace.as_ref() != Some(tablespace) {
+                        tablespaces_to_set.insert(table_name.clone(), tablespace.clone());
+                    }
+                }
+                
+                // Comment, when the model sets one
+                if let Some(comment) = &target_table.comment {
+                    if current_table.comment.as_ref() != Some(comment) {
+                        table_comments_to_set.insert(table_name.clone(), comment.clone());
+                    }
+                }
+                
+                // Indexes, matched by name or else by definition. An index whose definition
+                // changed under the same name is rebuilt; indexes the models don't mention
+                // are left alone, since they may have been added by hand.
+                let mut add_indexes = Vec::new();
+                let mut drop_indexes = Vec::new();
+                for target_index in &target_table.indexes {
+                    match current_table.indexes.iter().find(|index| index.name == target_index.name) {
+                        Some(current_index) if current_index.satisfies(target_index) => {}
+                        Some(current_index) => {
+                            drop_indexes.push(current_index.name.clone());
+                            add_indexes.push(target_index.clone());
+                        }
+                        None => {
+                            if !current_table.indexes.iter().any(|index| index.satisfies(target_index)) {
+                                add_indexes.push(target_index.clone());
+                            }
+                        }
+                    }
+                }
+                
+                if !add_indexes.is_empty() {
+                    indices_to_create.insert(table_name.clone(), add_indexes);
+                }
+                if !drop_indexes.is_empty() {
+                    indices_to_drop.insert(table_name.clone(), drop_indexes);
+                }
+                
+                // Exclusion and CHECK constraints, matched by definition. Changing one
+                // means dropping it and adding the new definition.
+                let is_synced = |constraint: &&Constraint| matches!(constraint.constraint_type.as_str(), "EXCLUDE" | "CHECK");
+                let add_constraints: Vec<Constraint> = target_table.constraints.iter()
+                    .filter(is_synced)
+                    .filter(|target| !current_table.constraints.iter().any(|current| current.same_definition(target)))
+                    .cloned()
+                    .collect();
+                let drop_constraints: Vec<String> = current_table.constraints.iter()
+                    .filter(is_synced)
+                    .filter(|current| !target_table.constraints.iter().any(|target| target.same_definition(current)))
+                    .map(|current| current.name.clone())
+                    .collect();
+                
+                if !add_constraints.is_empty() {
+                    constraints_to_create.insert(table_name.clone(), add_constraints);
+                }
+                if !drop_constraints.is_empty() {
+                    constraints_to_drop.insert(table_name.clone(), drop_constraints);
+                }
+            }
+        }
+        
+        // TODO: Implement foreign key removal logic
+        
+        let altered_columns: HashSet<String> = columns_to_alter
+            .iter()
+            .flat_map(|(table_name, changes)| {
+                changes.iter().map(move |change| format!("{}.{}", table_name, change.column_name))
+            })
+            .collect();
+        let views_to_recreate = Self::dependent_views(&current_schema, |column| altered_columns.contains(column));
+        
+        // Recreated views are filled on creation; others over changed tables may be stale
+        let changed_tables: HashSet<&str> = columns_to_add.keys()
+            .chain(columns_to_drop.keys())
+            .chain(columns_to_alter.keys())
+            .map(String::as_str)
+            .collect();
+        let materialized_views_to_refresh = Self::dependent_views(&current_schema, |column| {
+            column.rsplit_once('.').is_some_and(|(table, _)| changed_tables.contains(table))
+        })
+        .into_iter()
+        .filter(|view| view.is_materialized && !views_to_recreate.iter().any(|v| v.name == view.name))
+        .collect();
+        
+        // Enum types, matched by name. New labels are appended; labels are never removed,
+        // since rows may still hold them.
+        let mut enum_types_to_create = BTreeMap::new();
+        let mut enum_values_to_add = BTreeMap::new();
+        for (name, labels) in &target_schema.enum_types {
+            match current_schema.enum_types.get(name) {
+                Some(current) => {
+                    let missing: Vec<String> = labels.iter().filter(|label| !current.contains(label)).cloned().collect();
+                    if !missing.is_empty() {
+                        enum_values_to_add.insert(name.clone(), missing);
+                    }
+                }
+                None => {
+                    enum_types_to_create.insert(name.clone(), labels.clone());
+                }
             }
         }
         
-        // TODO: Implement index and foreign key diff logic
+        // Everything that uses a column about to be dropped or altered
+        let mut impacts = BTreeMap::new();
+        let graph = DependencyGraph::new(&current_schema);
+        let changed_columns = columns_to_drop.iter()
+            .flat_map(|(table, columns)| columns.iter().map(move |column| (table, column.clone())))
+            .chain(columns_to_alter.iter().flat_map(|(table, changes)| {
+                changes.iter().map(move |change| (table, change.column_name.clone()))
+            }));
+        for (table, column) in changed_columns {
+            let objects = Self::column_impacts(&current_schema, &graph, table, &column);
+            if !objects.is_empty() {
+                impacts.insert(format!("{}.{}", table, column), objects);
+            }
+        }
         
         Self {
             tables_to_create,
@@ -130,13 +308,158 @@ impl SchemaDiff {
             columns_to_add,
             columns_to_drop,
             columns_to_alter,
-            indices_to_create: HashMap::new(),
-            indices_to_drop: HashMap::new(),
-            foreign_keys_to_create: HashMap::new(),
-            foreign_keys_to_drop: HashMap::new(),
+            indices_to_create,
+            indices_to_drop,
+            foreign_keys_to_create,
+            foreign_keys_to_drop,
+            constraints_to_create,
+            constraints_to_drop,
+            enum_types_to_create,
+            enum_values_to_add,
+            tablespaces_to_set,
+            table_comments_to_set,
+            views_to_recreate,
+            materialized_views_to_refresh,
+            impacts,
         }
     }
     
+    /// Foreign keys, indexes, constraints, views and functions that use `table.column`
+    ///
+    /// Filled for the columns the diff drops or alters; empty for any other column.
+    pub fn impacted_objects(&self, table: &str, column: &str) -> &[ImpactedObject] {
+        self.impacts
+            .get(&format!("{}.{}", table, column))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+    
+    /// Objects in `schema` that use `table.column`
+    ///
+    /// Views come from their recorded dependencies; CHECK constraints, index expressions and
+    /// functions are matched on the column name appearing in their text.
+    fn column_impacts(schema: &DatabaseSchema, graph: &DependencyGraph, table: &str, column: &str) -> Vec<ImpactedObject> {
+        let mentions = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(column))).unwrap();
+        let mentions_table = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(table))).unwrap();
+        let mut objects = Vec::new();
+        let mut push = |kind: &str, name: &str, on: &str| objects.push(ImpactedObject {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            table: Some(on.to_string()),
+        });
+        
+        if let Some(current) = schema.tables.get(table) {
+            if current.primary_key.as_ref().is_some_and(|pk| pk.columns.iter().any(|c| c == column)) {
+                let name = current.primary_key.as_ref().and_then(|pk| pk.name.clone());
+                push("primary_key", name.as_deref().unwrap_or("primary key"), table);
+            }
+            for index in &current.indexes {
+                let uses = index.columns.iter().chain(&index.include).any(|c| c == column)
+                    || index.expression.as_deref().is_some_and(|e| mentions.is_match(e));
+                if uses {
+                    push("index", &index.name, table);
+                }
+            }
+            for constraint in current.constraints.iter().filter(|c| mentions.is_match(&c.definition)) {
+                push("constraint", &constraint.name, table);
+            }
+        }
+        
+        let mut table_names: Vec<&String> = schema.tables.keys().collect();
+        table_names.sort();
+        for other in table_names.into_iter().map(|name| &schema.tables[name]) {
+            for fk in &other.foreign_keys {
+                let on_column = other.name == table && fk.columns.iter().any(|c| c == column);
+                let references_column = fk.ref_table == table && fk.ref_columns.iter().any(|c| c == column);
+                if on_column || references_column {
+                    push("foreign_key", &fk.name, &other.name);
+                }
+            }
+        }
+        
+        for dependent in graph.dependents(&format!("{}.{}", table, column)) {
+            if let SchemaNode::View { name } = dependent {
+                objects.push(ImpactedObject { kind: "view".to_string(), name, table: None });
+            }
+        }
+        
+        for function in &schema.functions {
+            if mentions.is_match(&function.definition) && mentions_table.is_match(&function.definition) {
+                objects.push(ImpactedObject { kind: "function".to_string(), name: function.name.clone(), table: None });
+            }
+        }
+        
+        objects
+    }
+    
+    /// Views that read a `relation.column` matching `reads`, directly or through other views
+    ///
+    /// Views come before the views built on them, so they can be created in order and
+    /// dropped in reverse.
+    fn dependent_views(schema: &DatabaseSchema, reads: impl Fn(&str) -> bool) -> Vec<View> {
+        let graph = DependencyGraph::new(schema);
+        let mut affected: HashSet<&str> = HashSet::new();
+        for view in schema.views.values() {
+            for dependency in view.depends_on.iter().filter(|dependency| reads(dependency)) {
+                affected.insert(&view.name);
+                for dependent in graph.dependents(dependency) {
+                    if let SchemaNode::View { name } = dependent {
+                        if let Some((name, _)) = schema.views.get_key_value(&name) {
+                            affected.insert(name);
+                        }
+                    }
+                }
+            }
+        }
+        
+        let mut remaining: Vec<&View> = schema.views.values()
+            .filter(|view| affected.contains(view.name.as_str()))
+            .collect();
+        remaining.sort_by(|a, b| a.name.cmp(&b.name));
+        
+        let mut ordered: Vec<View> = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            let ready = remaining.iter().position(|view| {
+                view.depends_on.iter().all(|dependency| {
+                    let relation = dependency.rsplit_once('.').map_or(dependency.as_str(), |(relation, _)| relation);
+                    !affected.contains(relation) || ordered.iter().any(|v| v.name == relation)
+                })
+            });
+            ordered.push(remaining.remove(ready.unwrap_or(0)).clone());
+        }
+        
+        ordered
+    }
+    
+    /// Order new tables so each comes after the tables its foreign keys reference
+    ///
+    /// Self-references and references to existing tables impose no order. Tables in a
+    /// reference cycle keep name order; their keys must be added after all of them exist.
+    fn order_by_dependencies(tables: Vec<Table>) -> Vec<Table> {
+        let order = DependencyGraph::from_parts(&tables, []).table_order();
+        let mut tables: HashMap<String, Table> = tables.into_iter().map(|t| (t.name.clone(), t)).collect();
+        
+        order.iter().filter_map(|name| tables.remove(name)).collect()
+    }
+    
+    /// Check whether two foreign keys on the same columns behave the same way
+    fn foreign_key_options_match(current: &ForeignKey, target: &ForeignKey) -> bool {
+        // An unspecified action is the database default, which introspection reports
+        // as NO ACTION (or RESTRICT on MySQL)
+        let action_matches = |current: &Option<String>, target: &Option<String>| {
+            let current = current.as_deref().and_then(normalize_referential_action);
+            match target.as_deref().and_then(normalize_referential_action) {
+                Some(target) => current.as_deref().unwrap_or("NO ACTION") == target,
+                None => matches!(current.as_deref(), None | Some("NO ACTION") | Some("RESTRICT")),
+            }
+        };
+        
+        action_matches(&current.on_delete, &target.on_delete)
+            && action_matches(&current.on_update, &target.on_update)
+            && current.deferrable == target.deferrable
+            && current.match_type == target.match_type
+    }
+    
     /// Check if a column needs to be altered
     fn column_needs_alteration(
         current: &Column, 
@@ -154,7 +477,9 @@ impl SchemaDiff {
         }
         
         // Default value different
-        if current.default != target.default {
+        if current.default.as_deref().map(normalize_default)
+            != target.default.as_deref().map(normalize_default)
+        {
             return true;
         }
         
@@ -163,6 +488,27 @@ impl SchemaDiff {
             return true;
         }
         
+        // Character set, when the database reports one and the model declares one
+        if let (Some(current), Some(target)) = (&current.charset, &target.charset) {
+            if !current.eq_ignore_ascii_case(target) {
+                return true;
+            }
+        }
+        
+        // Collation, likewise only compared when both sides have one
+        if let (Some(current), Some(target)) = (&current.collation, &target.collation) {
+            if !current.eq_ignore_ascii_case(target) {
+                return true;
+            }
+        }
+        
+        // Storage strategy, likewise only compared when both sides have one
+        if let (Some(current), Some(target)) = (&current.storage, &target.storage) {
+            if !current.eq_ignore_ascii_case(target) {
+                return true;
+            }
+        }
+        
         false
     }
     
@@ -177,6 +523,126 @@ impl SchemaDiff {
             && self.indices_to_drop.is_empty()
             && self.foreign_keys_to_create.is_empty()
             && self.foreign_keys_to_drop.is_empty()
+            && self.constraints_to_create.is_empty()
+            && self.constraints_to_drop.is_empty()
+            && self.enum_types_to_create.is_empty()
+            && self.enum_values_to_add.is_empty()
+            && self.tablespaces_to_set.is_empty()
+            && self.table_comments_to_set.is_empty()
+    }
+    
+    /// One line per change the diff would make, sorted, e.g. "missing column users.email"
+    pub fn differences(&self) -> Vec<String> {
+        self.table_differences().into_iter().map(|(_, difference)| difference).collect()
+    }
+    
+    /// Like `differences`, paired with the table each difference is about
+    pub fn table_differences(&self) -> Vec<(String, String)> {
+        let mut differences = Vec::new();
+        
+        for table in &self.tables_to_create {
+            differences.push((table.name.clone(), format!("missing table {}", table.name)));
+        }
+        for table_name in &self.tables_to_drop {
+            differences.push((table_name.clone(), format!("unexpected table {}", table_name)));
+        }
+        for (table_name, columns) in &self.columns_to_add {
+            for column in columns {
+                differences.push((table_name.clone(), format!("missing column {}.{}", table_name, column.name)));
+            }
+        }
+        for (table_name, column_names) in &self.columns_to_drop {
+            for column_name in column_names {
+                differences.push((table_name.clone(), format!("unexpected column {}.{}", table_name, column_name)));
+            }
+        }
+        for (table_name, changes) in &self.columns_to_alter {
+            for change in changes {
+                differences.push((table_name.clone(), format!(
+                    "column {}.{} is {}{}, expected {}{}",
+                    table_name,
+                    change.column_name,
+                    change.from.data_type,
+                    if change.from.nullable { " NULL" } else { " NOT NULL" },
+                    change.to.data_type,
+                    if change.to.nullable { " NULL" } else { " NOT NULL" }
+                )));
+            }
+        }
+        for (table_name, indexes) in &self.indices_to_create {
+            for index in indexes {
+                differences.push((table_name.clone(), format!("missing or changed index {} on {}", index.name, table_name)));
+            }
+        }
+        for (table_name, foreign_keys) in &self.foreign_keys_to_create {
+            for fk in foreign_keys {
+                differences.push((table_name.clone(), format!("missing or changed foreign key {} on {}", fk.name, table_name)));
+            }
+        }
+        for (table_name, constraints) in &self.constraints_to_create {
+            for constraint in constraints {
+                differences.push((table_name.clone(), format!("missing or changed constraint {} on {}", constraint.name, table_name)));
+            }
+        }
+        for name in self.enum_types_to_create.keys() {
+            differences.push((name.clone(), format!("missing enum type {}", name)));
+        }
+        for (name, labels) in &self.enum_values_to_add {
+            for label in labels {
+                differences.push((name.clone(), format!("enum type {} lacks value '{}'", name, label)));
+            }
+        }
+        for (table_name, tablespace) in &self.tablespaces_to_set {
+            differences.push((table_name.clone(), format!("table {} is not in tablespace {}", table_name, tablespace)));
+        }
+        for table_name in self.table_comments_to_set.keys() {
+            differences.push((table_name.clone(), format!("comment on table {} differs", table_name)));
+        }
+        
+        differences.sort_by(|a, b| a.1.cmp(&b.1));
+        differences
+    }
+    
+    /// Names of the tables this diff creates, drops, or changes
+    pub fn touched_tables(&self) -> BTreeSet<String> {
+        self.table_differences()
+            .into_iter()
+            .map(|(table, _)| table)
+            .filter(|name| !self.enum_types_to_create.contains_key(name) && !self.enum_values_to_add.contains_key(name))
+            .collect()
+    }
+    
+    /// What still differs in the tables this diff touched, once they have been read back
+    ///
+    /// `applied` holds those tables as the database now reports them (a dropped table is
+    /// simply absent) and `models` the schema the diff was generated towards. Empty when
+    /// every statement had its intended effect; otherwise one line per difference, such as
+    /// a column whose type the database silently coerced.
+    pub fn unapplied_differences(
+        &self,
+        applied: DatabaseSchema,
+        models: &DatabaseSchema,
+        schema_config: &SchemaConfig,
+    ) -> Vec<String> {
+        SchemaDiff::differences_in(&self.touched_tables(), applied, models, schema_config)
+    }
+    
+    /// What differs between `applied` and `models` in the `tables` named
+    ///
+    /// `applied` holds those tables as read from the database; tables outside `tables` are
+    /// not compared, so models that a migration did not touch are never reported.
+    pub fn differences_in(
+        tables: &BTreeSet<String>,
+        applied: DatabaseSchema,
+        models: &DatabaseSchema,
+        schema_config: &SchemaConfig,
+    ) -> Vec<String> {
+        let mut expected = DatabaseSchema::new(models.schema_name.clone());
+        for table in models.tables.values().filter(|table| tables.contains(&table.name)) {
+            expected.add_table(table.clone());
+        }
+        
+        SchemaDiff::generate(applied, expected, schema_config).differences()
     }
 }
 
@@ -186,4 +652,79 @@ pub struct ColumnChange {
     pub column_name: String,
     pub from: Column,
     pub to: Column,
+}
+
+/// A schema object that uses a column the diff changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImpactedObject {
+    /// "primary_key", "index", "constraint", "foreign_key", "view", or "function"
+    pub kind: String,
+    pub name: String,
+    /// Table the object belongs to, for table-level objects
+    pub table: Option<String>,
+}
+
+impl fmt::Display for ImpactedObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.table {
+            Some(table) => write!(f, "{} {} on {}", self.kind.replace('_', " "), self.name, table),
+            None => write!(f, "{} {}", self.kind, self.name),
+        }
+    }
+}
+
+/// Drift between the database and the models, grouped by table, with each table's size
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DriftReport {
+    pub tables: Vec<TableDrift>,
+}
+
+/// The differences found for one table
+#[derive(Debug, Clone, Serialize)]
+pub struct TableDrift {
+    pub table: String,
+    pub differences: Vec<String>,
+    /// Row estimate and size from the database's statistics; `None` for missing tables
+    pub stats: Option<TableStats>,
+}
+
+impl DriftReport {
+    /// Group a diff's differences by table, attaching the tables' statistics
+    pub fn new(diff: &SchemaDiff, stats: &HashMap<String, TableStats>) -> Self {
+        let mut tables: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (table, difference) in diff.table_differences() {
+            tables.entry(table).or_default().push(difference);
+        }
+        
+        Self {
+            tables: tables
+                .into_iter()
+                .map(|(table, differences)| TableDrift {
+                    stats: stats.get(&table).copied(),
+                    table,
+                    differences,
+                })
+                .collect(),
+        }
+    }
+    
+    /// Whether no table drifted
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for drift in &self.tables {
+            match &drift.stats {
+                Some(stats) => writeln!(f, "{} ({}):", drift.table, stats)?,
+                None => writeln!(f, "{}:", drift.table)?,
+            }
+            for difference in &drift.differences {
+                writeln!(f, "  - {}", difference)?;
+            }
+        }
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,464 @@
+//! SQL formatting
+//!
+//! This module lays out generated SQL the same way every time, so committed migration files
+//! diff cleanly whoever generated them: keywords in one case, each statement on its own
+//! line, and statements that would run past the line width broken one list item or clause
+//! per line. Only whitespace and keyword case change; identifiers, string literals,
+//! dollar-quoted bodies and comments are kept as written.
+
+use crate::config::FormatConfig;
+use crate::error::{Error, Result};
+
+/// Words put in the configured case
+const KEYWORDS: &[&str] = &[
+    "ADD", "ALL", "ALTER", "ALWAYS", "AND", "ANY", "AS", "ASC", "AUTO_INCREMENT", "BEGIN", "BETWEEN",
+    "BIGINT", "BIGSERIAL", "BOOLEAN", "BTREE", "BY", "BYTEA", "CASCADE", "CASE", "CHAR", "CHARACTER",
+    "CHECK", "COLLATE", "COLUMN", "COMMENT", "COMMIT", "CONCURRENTLY", "CONSTRAINT", "CREATE", "CROSS",
+    "CURRENT_DATE", "CURRENT_TIME", "CURRENT_TIMESTAMP", "DATABASE", "DATE", "DEALLOCATE", "DECIMAL",
+    "DEFAULT", "DEFERRABLE", "DEFERRED", "DELETE", "DESC", "DISTINCT", "DO", "DOUBLE", "DROP", "ELSE",
+    "END", "ENGINE", "EXCLUDE", "EXECUTE", "EXISTS", "FALSE", "FIRST", "FLOAT", "FOREIGN", "FROM",
+    "FULL", "FUNCTION", "GENERATED", "GIN", "GIST", "GROUP", "HASH", "HAVING", "IDENTITY", "IF",
+    "IMMEDIATE", "IN", "INCLUDE", "INDEX", "INITIALLY", "INNER", "INSERT", "INT", "INTEGER", "INTERVAL",
+    "INTO", "IS", "JOIN", "JSON", "JSONB", "KEY", "LANGUAGE", "LAST", "LEFT", "LIKE", "LIMIT", "MATCH",
+    "MATERIALIZED", "MODIFY", "NOT", "NULL", "NULLS", "NUMERIC", "ON", "ONLY", "OR", "ORDER", "OUTER",
+    "PARTIAL", "PRECISION", "PREPARE", "PRIMARY", "REAL", "REFERENCES", "REFRESH", "RENAME", "REPLACE",
+    "RESTRICT", "RETURNS", "RIGHT", "SELECT", "SERIAL", "SET", "SIMPLE", "SMALLINT", "STORED", "TABLE",
+    "TABLESPACE", "TEXT", "THEN", "TIME", "TIMESTAMP", "TIMESTAMPTZ", "TINYINT", "TO", "TRIGGER", "TRUE",
+    "TYPE", "UNION", "UNIQUE", "UNSIGNED", "UPDATE", "USING", "UUID", "VALID", "VALIDATE", "VALUES",
+    "VARCHAR", "VIEW", "VIRTUAL", "WHEN", "WHERE", "WITH", "WITHOUT", "ZONE",
+];
+
+/// Keywords that start a new line in a long query
+const CLAUSES: &[&str] = &[
+    "SELECT", "FROM", "WHERE", "GROUP", "HAVING", "ORDER", "LIMIT", "UNION", "JOIN", "LEFT", "RIGHT",
+    "INNER", "FULL", "CROSS",
+];
+
+/// Keywords that start an action in an `ALTER TABLE`
+const ALTER_ACTIONS: &[&str] = &["ADD", "ALTER", "CHANGE", "DROP", "MODIFY", "RENAME", "SET", "VALIDATE"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Word,
+    /// A literal, quoted identifier, dollar-quoted body, or block comment
+    Quoted,
+    /// A `--` comment, which runs to the end of the line
+    Comment,
+    Open,
+    Close,
+    Comma,
+    Semicolon,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    text: String,
+    /// Whether the token was preceded by whitespace
+    space_before: bool,
+}
+
+impl Token {
+    fn is_word(&self, words: &[&str]) -> bool {
+        self.kind == Kind::Word && words.contains(&self.text.to_uppercase().as_str())
+    }
+}
+
+/// Formats SQL as configured by `[migrations.format]`
+#[derive(Debug, Clone)]
+pub struct SqlFormatter {
+    keyword_case: String,
+    indent: usize,
+    max_line_width: usize,
+}
+
+impl SqlFormatter {
+    pub fn new(config: &FormatConfig) -> Result<Self> {
+        let keyword_case = config.keyword_case.clone().unwrap_or_else(|| "upper".to_string());
+        if !matches!(keyword_case.as_str(), "upper" | "lower" | "preserve") {
+            return Err(Error::ConfigError(format!(
+                "unknown format keyword_case '{}', expected \"upper\", \"lower\", or \"preserve\"",
+                keyword_case
+            )));
+        }
+
+        Ok(Self {
+            keyword_case,
+            indent: config.indent.unwrap_or(2),
+            max_line_width: config.max_line_width.unwrap_or(100),
+        })
+    }
+
+    /// Format each statement in `sql`, one after another, each ending with a newline
+    pub fn format(&self, sql: &str) -> String {
+        let mut out = String::new();
+
+        for mut statement in statements(tokenize(sql)) {
+            let terminated = statement.last().is_some_and(|token| token.kind == Kind::Semicolon);
+            if terminated {
+                statement.pop();
+            }
+            if statement.is_empty() {
+                continue;
+            }
+
+            out.push_str(&self.statement(&statement));
+            if terminated {
+                out.push(';');
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    fn statement(&self, tokens: &[Token]) -> String {
+        let words: Vec<String> = tokens
+            .iter()
+            .filter(|token| token.kind == Kind::Word)
+            .take(3)
+            .map(|token| token.text.to_uppercase())
+            .collect();
+        let starts_with = |prefix: &[&str]| words.len() >= prefix.len() && words.iter().zip(prefix).all(|(w, p)| w == p);
+
+        // Table definitions always list one column per line
+        if starts_with(&["CREATE", "TABLE"])
+            || starts_with(&["CREATE", "TEMPORARY", "TABLE"])
+            || starts_with(&["CREATE", "UNLOGGED", "TABLE"])
+        {
+            return self.layout(tokens, 0, true);
+        }
+
+        let flat = self.flat(tokens);
+        if self.fits(&flat, 0, 1) {
+            return flat;
+        }
+        if starts_with(&["ALTER", "TABLE"]) {
+            if let Some(sql) = self.alter_table(tokens) {
+                return sql;
+            }
+        }
+        self.layout(tokens, 0, true)
+    }
+
+    /// An `ALTER TABLE` with several actions, one per line under the table name
+    fn alter_table(&self, tokens: &[Token]) -> Option<String> {
+        let actions = split_top_level(tokens);
+        if actions.len() < 2 {
+            return None;
+        }
+
+        let table = actions[0].iter().position(|token| token.is_word(&["TABLE"]))?;
+        let first = actions[0]
+            .iter()
+            .enumerate()
+            .skip(table + 2)
+            .find(|(_, token)| token.is_word(ALTER_ACTIONS))
+            .map(|(i, _)| i)?;
+
+        let mut out = self.layout(&actions[0][..first], 0, false);
+        let actions: Vec<&[Token]> = std::iter::once(&actions[0][first..]).chain(actions[1..].iter().copied()).collect();
+        for (n, action) in actions.iter().enumerate() {
+            self.newline(&mut out, 1);
+            out.push_str(&self.layout(action, 1, false));
+            if n + 1 < actions.len() {
+                out.push(',');
+            }
+        }
+        Some(out)
+    }
+
+    /// Tokens on as few lines as possible, or broken at lists and clauses when too long
+    ///
+    /// `force` breaks them even when they would fit.
+    fn layout(&self, tokens: &[Token], level: usize, force: bool) -> String {
+        let flat = self.flat(tokens);
+        if !force && self.fits(&flat, level, 0) {
+            return flat;
+        }
+
+        let query = tokens.iter().any(|token| token.is_word(&["SELECT"]));
+        let mut out = String::new();
+        let mut previous: Option<&Token> = None;
+        let mut broken = false;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let token = &tokens[i];
+
+            if token.kind == Kind::Open {
+                if let Some(close) = closing(tokens, i) {
+                    let items = split_top_level(&tokens[i + 1..close]);
+                    // A forced layout always breaks its first list, e.g. a single-column table
+                    let first_list = force && !broken && close > i + 1;
+                    out.push_str(separator(previous, token));
+                    if items.len() > 1 || first_list {
+                        broken = true;
+                        out.push('(');
+                        for (n, item) in items.iter().enumerate() {
+                            self.newline(&mut out, level + 1);
+                            out.push_str(&self.layout(item, level + 1, false));
+                            if n + 1 < items.len() {
+                                out.push(',');
+                            }
+                        }
+                        self.newline(&mut out, level);
+                        out.push(')');
+                    } else {
+                        out.push_str(&self.flat(&tokens[i..=close]));
+                    }
+                    previous = Some(&tokens[close]);
+                    i = close + 1;
+                    continue;
+                }
+            }
+
+            let joins = ["LEFT", "RIGHT", "INNER", "FULL", "CROSS", "OUTER", "NATURAL"];
+            let clause = query
+                && i > 0
+                && token.is_word(CLAUSES)
+                && !(token.is_word(&["JOIN"]) && tokens[i - 1].is_word(&joins));
+            if clause && previous.is_some_and(|p| p.kind != Kind::Comment) {
+                self.newline(&mut out, level);
+            } else {
+                out.push_str(separator(previous, token));
+            }
+            out.push_str(&self.text(tokens, i));
+            if token.kind == Kind::Comment {
+                self.newline(&mut out, level);
+            }
+
+            previous = Some(token);
+            i += 1;
+        }
+
+        out
+    }
+
+    /// Tokens on one line, apart from the line breaks comments need
+    fn flat(&self, tokens: &[Token]) -> String {
+        let mut out = String::new();
+        let mut previous = None;
+
+        for (i, token) in tokens.iter().enumerate() {
+            out.push_str(separator(previous, token));
+            out.push_str(&self.text(tokens, i));
+            if token.kind == Kind::Comment {
+                out.push('\n');
+            }
+            previous = Some(token);
+        }
+
+        out
+    }
+
+    /// A token's text, in the configured case if it is a keyword
+    fn text(&self, tokens: &[Token], i: usize) -> String {
+        let token = &tokens[i];
+        // Parts of qualified names are identifiers, whatever they are called
+        let qualified = tokens.get(i + 1).is_some_and(|next| next.text == "." && !next.space_before)
+            || (i > 0 && tokens[i - 1].text == "." && !token.space_before);
+
+        if token.kind != Kind::Word || qualified || !token.is_word(KEYWORDS) {
+            return token.text.clone();
+        }
+        match self.keyword_case.as_str() {
+            "upper" => token.text.to_uppercase(),
+            "lower" => token.text.to_lowercase(),
+            _ => token.text.clone(),
+        }
+    }
+
+    fn fits(&self, text: &str, level: usize, extra: usize) -> bool {
+        let longest = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        level * self.indent + longest + extra <= self.max_line_width
+    }
+
+    fn newline(&self, out: &mut String, level: usize) {
+        let trimmed = out.trim_end_matches(' ').len();
+        out.truncate(trimmed);
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&" ".repeat(level * self.indent));
+    }
+}
+
+/// Whitespace between two tokens: commas are followed by a space, brackets hug their contents
+fn separator(previous: Option<&Token>, token: &Token) -> &'static str {
+    let Some(previous) = previous else {
+        return "";
+    };
+    match (previous.kind, token.kind) {
+        (Kind::Comment, _) => "",
+        (_, Kind::Close | Kind::Comma | Kind::Semicolon) => "",
+        (Kind::Open, _) => "",
+        (Kind::Comma, _) => " ",
+        _ if token.space_before => " ",
+        _ => "",
+    }
+}
+
+/// Index of the bracket closing the one at `open`
+fn closing(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token.kind {
+            Kind::Open => depth += 1,
+            Kind::Close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split tokens at the commas outside brackets
+fn split_top_level(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut items = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            Kind::Open => depth += 1,
+            Kind::Close => depth -= 1,
+            Kind::Comma if depth == 0 => {
+                items.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&tokens[start..]);
+
+    items
+}
+
+/// Group tokens into statements, each ending with its `;` when it has one
+fn statements(tokens: Vec<Token>) -> Vec<Vec<Token>> {
+    let mut statements = Vec::new();
+    let mut current = Vec::new();
+    let mut depth = 0i32;
+
+    for token in tokens {
+        match token.kind {
+            Kind::Open => depth += 1,
+            Kind::Close => depth -= 1,
+            _ => {}
+        }
+        let ends = token.kind == Kind::Semicolon && depth <= 0;
+        current.push(token);
+        if ends {
+            statements.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        statements.push(current);
+    }
+
+    statements
+}
+
+fn tokenize(sql: &str) -> Vec<Token> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut space_before = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let start = i;
+
+        let kind = if c.is_whitespace() {
+            space_before = true;
+            i += 1;
+            continue;
+        } else if c == '-' && next == Some('-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            Kind::Comment
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i = (i + 2).min(chars.len());
+            Kind::Quoted
+        } else if matches!(c, '\'' | '"' | '`') {
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == c {
+                    // A doubled quote is an escaped one
+                    if chars.get(i + 1) == Some(&c) {
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                i += 1;
+            }
+            Kind::Quoted
+        } else if c == '$' && dollar_tag(&chars, i).is_some() {
+            let tag = dollar_tag(&chars, i).unwrap();
+            i += tag.len();
+            while i < chars.len() && !chars[i..].starts_with(&tag) {
+                i += 1;
+            }
+            i = (i + tag.len()).min(chars.len());
+            Kind::Quoted
+        } else if c.is_alphanumeric() || matches!(c, '_' | '@' | '$') {
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$')) {
+                i += 1;
+            }
+            Kind::Word
+        } else {
+            i += 1;
+            match c {
+                '(' => Kind::Open,
+                ')' => Kind::Close,
+                ',' => Kind::Comma,
+                ';' => Kind::Semicolon,
+                _ => {
+                    let operator = |c: char| "<>=!|:+-*/%&^~#?".contains(c);
+                    if operator(c) {
+                        while i < chars.len() && operator(chars[i]) && !(chars[i] == '-' && chars.get(i + 1) == Some(&'-')) {
+                            i += 1;
+                        }
+                    }
+                    Kind::Other
+                }
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            text: chars[start..i].iter().collect(),
+            space_before,
+        });
+        space_before = false;
+    }
+
+    tokens
+}
+
+/// The `$tag$` opening a dollar-quoted string at `start`, if there is one
+fn dollar_tag(chars: &[char], start: usize) -> Option<Vec<char>> {
+    let mut end = start + 1;
+    while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+        if end == start + 1 && chars[end].is_ascii_digit() {
+            return None;
+        }
+        end += 1;
+    }
+    (chars.get(end) == Some(&'$')).then(|| chars[start..=end].to_vec())
+}
@@ -2,29 +2,239 @@
 //!
 //! This module generates SQL migration statements from schema diffs
 
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
 use crate::config::Config;
-use crate::error::Result;
+use crate::diagnostics::Diagnostics;
+use crate::error::{Error, MigrationFailure, Result, UnsupportedOperation};
+use crate::schema::dialect::{
+    clickhouse_nullable, clickhouse_type, constraint_postgres_extension, oracle_guarded,
+    required_postgres_extension, type_postgres_extension, wraps_whole, Capability, Dialect,
+};
 use crate::schema::diff::{ColumnChange, SchemaDiff};
-use crate::schema::types::{Column, Table};
+use crate::schema::format::SqlFormatter;
+use crate::schema::templates::apply_templates;
+use crate::schema::types::{Column, Constraint, Table, View};
+use crate::schema::validate::{default_problem, validate_sql};
 
 /// Migration SQL generator
 pub struct MigrationGenerator<'a> {
     config: &'a Config,
+    diagnostics: Mutex<Diagnostics>,
 }
 
 impl<'a> MigrationGenerator<'a> {
     /// Create a new migration generator
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            diagnostics: Mutex::new(Diagnostics::new()),
+        }
+    }
+    
+    /// Parts of the schema that could not be expressed in the target dialect
+    pub fn diagnostics(&self) -> Diagnostics {
+        self.diagnostics.lock().unwrap().clone()
+    }
+    
+    /// Record and log something the generated SQL leaves out
+    fn warn(&self, code: &str, message: String, table: &str, column: Option<&str>) {
+        tracing::warn!("{}", message);
+        self.diagnostics.lock().unwrap().warn(code, message, Some(table), column);
+    }
+    
+    /// Fail with `Error::UnsupportedOperation` unless the target dialect has `capability`
+    ///
+    /// Unknown drivers pass, so they are reported as unsupported drivers instead.
+    fn require(&self, capability: Capability, table_name: &str, hint: &str) -> Result<()> {
+        match Dialect::from_driver(&self.config.database.driver) {
+            Some(dialect) if !dialect.capabilities().supports(capability) => {
+                Err(Error::UnsupportedOperation(UnsupportedOperation {
+                    dialect: dialect.name().to_string(),
+                    capability,
+                    table: Some(table_name.to_string()),
+                    column: None,
+                    hint: Some(hint.to_string()),
+                }))
+            }
+            _ => Ok(()),
+        }
+    }
+    
+    /// Warn about column character sets, which only MySQL stores
+    fn check_charsets_supported(&self, table_name: &str, columns: &[Column]) {
+        if self.config.database.driver == "mysql" {
+            return;
+        }
+        
+        for column in columns {
+            if let Some(charset) = &column.charset {
+                self.warn(
+                    "unsupported_charset",
+                    format!(
+                        "{} has no column character sets; charset '{}' on '{}.{}' was not applied",
+                        self.config.database.driver,
+                        charset,
+                        table_name,
+                        column.name
+                    ),
+                    table_name,
+                    Some(&column.name),
+                );
+            }
+        }
+    }
+    
+    /// Warn about column storage strategies, which only PostgreSQL has
+    fn check_storage_supported(&self, table_name: &str, columns: &[Column]) {
+        if self.config.database.driver == "postgres" {
+            return;
+        }
+        
+        for column in columns {
+            if let Some(storage) = &column.storage {
+                self.warn(
+                    "unsupported_column_storage",
+                    format!(
+                        "{} has no column storage strategies; storage '{}' on '{}.{}' was not applied",
+                        self.config.database.driver,
+                        storage,
+                        table_name,
+                        column.name
+                    ),
+                    table_name,
+                    Some(&column.name),
+                );
+            }
+        }
+    }
+    
+    /// Warn about column defaults the target dialect would reject
+    fn check_defaults<'c>(&self, table_name: &str, columns: impl IntoIterator<Item = &'c Column>) {
+        for column in columns {
+            let problem = column
+                .default
+                .as_deref()
+                .and_then(|default| default_problem(default, &self.config.database.driver).map(|problem| (default, problem)));
+            if let Some((default, problem)) = problem {
+                self.warn(
+                    "invalid_default",
+                    format!("default '{}' on '{}.{}' {}", default, table_name, column.name, problem),
+                    table_name,
+                    Some(&column.name),
+                );
+            }
+        }
+    }
+    
+    /// Warn about comments the target dialect has no way to store
+    fn check_comments_supported(&self, table_name: &str, table_comment: Option<&str>, columns: &[Column]) {
+        let dialect = match Dialect::from_driver(&self.config.database.driver) {
+            Some(dialect) => dialect,
+            None => return,
+        };
+        let capabilities = dialect.capabilities();
+        
+        if table_comment.is_some() && !capabilities.table_comments {
+            self.warn(
+                "unsupported_comment",
+                format!(
+                    "{} does not support table comments; comment on '{}' was not applied",
+                    dialect.name(),
+                    table_name
+                ),
+                table_name,
+                None,
+            );
+        }
+        
+        if !capabilities.column_comments {
+            for column in columns.iter().filter(|c| c.comment.is_some()) {
+                self.warn(
+                    "unsupported_comment",
+                    format!(
+                        "{} does not support column comments; comment on '{}.{}' was not applied",
+                        dialect.name(),
+                        table_name,
+                        column.name
+                    ),
+                    table_name,
+                    Some(&column.name),
+                );
+            }
+        }
     }
     
     /// Generate migration SQL from a schema diff
     pub async fn generate_migration_sql(&self, diff: &SchemaDiff) -> Result<Vec<String>> {
         let mut migrations = Vec::new();
         
-        // Handle table creation
+        // Enable extensions that new column types and defaults depend on
+        if self.config.database.driver == "postgres" {
+            let mut extensions = BTreeSet::new();
+            let columns = diff.tables_to_create.iter().flat_map(|t| t.columns.iter())
+                .chain(diff.columns_to_add.values().flatten())
+                .chain(diff.columns_to_alter.values().flatten().map(|change| &change.to));
+            
+            for column in columns {
+                if let Some(extension) = column.default.as_deref().and_then(required_postgres_extension) {
+                    extensions.insert(extension);
+                }
+                if let Some(extension) = type_postgres_extension(&column.data_type) {
+                    extensions.insert(extension);
+                }
+            }
+            
+            let constraints = diff.tables_to_create.iter().flat_map(|t| t.constraints.iter())
+                .chain(diff.constraints_to_create.values().flatten());
+            extensions.extend(constraints.filter_map(constraint_postgres_extension));
+            
+            for extension in extensions {
+                migrations.push(format!("CREATE EXTENSION IF NOT EXISTS \"{}\";\n", extension));
+            }
+        }
+        
+        // Enum types come before the tables whose columns use them
+        let quote_labels = |labels: &[String]| -> Vec<String> {
+            labels.iter().map(|label| format!("'{}'", label.replace('\'', "''"))).collect()
+        };
+        for (name, labels) in &diff.enum_types_to_create {
+            migrations.push(format!("CREATE TYPE {} AS ENUM ({});\n", name, quote_labels(labels).join(", ")));
+        }
+        for (name, labels) in &diff.enum_values_to_add {
+            migrations.push(quote_labels(labels).iter()
+                .map(|label| format!("ALTER TYPE {} ADD VALUE IF NOT EXISTS {};\n", name, label))
+                .collect());
+        }
+        
+        // Handle table creation. With `create_indexes_last`, a new table's non-unique indexes
+        // are held back so rows loaded in the meantime don't each update them
+        let mut deferred_indexes = Vec::new();
         for table in &diff.tables_to_create {
-            migrations.push(self.generate_create_table_sql(table)?);
+            if self.create_indexes_last() && table.indexes.iter().any(|index| !index.is_unique) {
+                let mut table = table.clone();
+                let (unique, secondary) = table.indexes.into_iter().partition(|index| index.is_unique);
+                table.indexes = unique;
+                migrations.push(self.generate_create_table_sql(&table)?);
+                deferred_indexes.push((table.name, secondary));
+            } else {
+                migrations.push(self.generate_create_table_sql(table)?);
+            }
+        }
+        
+        // Postgres and Oracle add keys for new tables once all of them exist, so tables
+        // that reference each other can be created in any order
+        if matches!(self.config.database.driver.as_str(), "postgres" | "oracle") {
+            for table in diff.tables_to_create.iter().filter(|t| !t.foreign_keys.is_empty()) {
+                let sql: String = table.foreign_keys.iter()
+                    .map(|fk| match self.config.database.driver.as_str() {
+                        "oracle" => self.oracle_add_foreign_key_sql(&table.name, fk),
+                        _ => self.postgres_add_foreign_key_sql(&table.name, fk, false),
+                    })
+                    .collect();
+                migrations.push(sql);
+            }
         }
         
         // Handle table deletion
@@ -32,82 +242,232 @@ impl<'a> MigrationGenerator<'a> {
             migrations.push(self.generate_drop_table_sql(table_name)?);
         }
         
-        // Handle column additions
-        for (table_name, columns) in &diff.columns_to_add {
-            migrations.push(self.generate_add_columns_sql(table_name, columns)?);
+        // Views reading altered columns would block the change (Postgres refuses to alter
+        // a column a view uses), so they are dropped first, dependents before their sources
+        if !diff.views_to_recreate.is_empty() {
+            migrations.push(diff.views_to_recreate.iter().rev().map(drop_view_sql).collect());
+        }
+        
+        if self.config.database.driver == "mysql" {
+            // Each MySQL ALTER TABLE may rebuild the table, so all column changes
+            // for a table are combined into a single statement
+            let table_names: BTreeSet<&String> = diff.columns_to_add.keys()
+                .chain(diff.columns_to_drop.keys())
+                .chain(diff.columns_to_alter.keys())
+                .collect();
+                
+            for table_name in table_names {
+                migrations.push(self.generate_mysql_batched_alter_sql(
+                    table_name,
+                    diff.columns_to_add.get(table_name).map(Vec::as_slice).unwrap_or_default(),
+                    diff.columns_to_drop.get(table_name).map(Vec::as_slice).unwrap_or_default(),
+                    diff.columns_to_alter.get(table_name).map(Vec::as_slice).unwrap_or_default(),
+                ));
+            }
+        } else {
+            // Handle column additions
+            for (table_name, columns) in &diff.columns_to_add {
+                migrations.push(self.generate_add_columns_sql(table_name, columns)?);
+            }
+            
+            // Handle column deletions
+            for (table_name, column_names) in &diff.columns_to_drop {
+                migrations.push(self.generate_drop_columns_sql(table_name, column_names)?);
+            }
+            
+            // Handle column modifications
+            for (table_name, column_changes) in &diff.columns_to_alter {
+                migrations.push(self.generate_alter_columns_sql(table_name, column_changes)?);
+            }
         }
         
-        // Handle column deletions
-        for (table_name, column_names) in &diff.columns_to_drop {
-            migrations.push(self.generate_drop_columns_sql(table_name, column_names)?);
+        // Recreate the dropped views from their original definitions
+        if !diff.views_to_recreate.is_empty() {
+            migrations.push(diff.views_to_recreate.iter().map(create_view_sql).collect());
         }
         
-        // Handle column modifications
-        for (table_name, column_changes) in &diff.columns_to_alter {
-            migrations.push(self.generate_alter_columns_sql(table_name, column_changes)?);
+        // Move tables whose tablespace changed; this rewrites the table, but leaves its indexes in place
+        for (table_name, tablespace) in &diff.tablespaces_to_set {
+            if let Some(sql) = self.generate_set_tablespace_sql(table_name, tablespace) {
+                migrations.push(sql);
+            }
         }
         
-        // Handle index additions
-        for (table_name, index_names) in &diff.indices_to_create {
-            if let Some(table) = self.find_table_by_name(table_name, diff) {
-                let indices: Vec<_> = table.indexes.iter()
-                    .filter(|idx| index_names.contains(&idx.name))
-                    .collect();
-                    
-                if !indices.is_empty() {
-                    migrations.push(self.generate_create_indices_sql(table_name, &indices)?);
-                }
+        // Set table comments that changed
+        for (table_name, comment) in &diff.table_comments_to_set {
+            if let Some(sql) = self.generate_set_table_comment_sql(table_name, comment) {
+                migrations.push(sql);
             }
         }
         
-        // Handle index deletions
+        // Handle index deletions first, so changed indexes can be rebuilt under the same name
         for (table_name, index_names) in &diff.indices_to_drop {
             migrations.push(self.generate_drop_indices_sql(table_name, index_names)?);
         }
         
-        // Handle foreign key additions
-        for (table_name, fk_names) in &diff.foreign_keys_to_create {
-            if let Some(table) = self.find_table_by_name(table_name, diff) {
-                let foreign_keys: Vec<_> = table.foreign_keys.iter()
-                    .filter(|fk| fk_names.contains(&fk.name))
-                    .collect();
-                    
-                if !foreign_keys.is_empty() {
-                    migrations.push(self.generate_create_foreign_keys_sql(table_name, &foreign_keys)?);
-                }
+        // Handle index additions; dialects without secondary indexes (ClickHouse) generate none
+        for (table_name, indices) in &diff.indices_to_create {
+            let indices: Vec<_> = indices.iter().collect();
+            let sql = self.generate_create_indices_sql(table_name, &indices)?;
+            if !sql.trim().is_empty() {
+                migrations.push(sql);
             }
         }
         
-        // Handle foreign key deletions
+        // Handle foreign key deletions first, so changed keys can be re-added under the same name
         for (table_name, fk_names) in &diff.foreign_keys_to_drop {
             migrations.push(self.generate_drop_foreign_keys_sql(table_name, fk_names)?);
         }
         
+        // Handle foreign key additions
+        let mut validations = Vec::new();
+        for (table_name, foreign_keys) in &diff.foreign_keys_to_create {
+            let foreign_keys: Vec<_> = foreign_keys.iter().collect();
+            migrations.push(self.generate_create_foreign_keys_sql(table_name, &foreign_keys)?);
+            
+            if self.validate_constraints_separately() {
+                for fk in &foreign_keys {
+                    validations.push(format!(
+                        "ALTER TABLE {} VALIDATE CONSTRAINT {};\n",
+                        table_name, fk.name
+                    ));
+                }
+            }
+        }
+        
+        // Replace changed exclusion and CHECK constraints
+        for (table_name, constraint_names) in &diff.constraints_to_drop {
+            migrations.push(self.generate_drop_constraints_sql(table_name, constraint_names)?);
+        }
+        for (table_name, constraints) in &diff.constraints_to_create {
+            if let Some(sql) = self.generate_add_constraints_sql(table_name, constraints)? {
+                migrations.push(sql);
+            }
+            
+            if self.validate_constraints_separately() {
+                for constraint in constraints.iter().filter(|c| c.constraint_type == "CHECK") {
+                    validations.push(format!(
+                        "ALTER TABLE {} VALIDATE CONSTRAINT {};\n",
+                        table_name, constraint.name
+                    ));
+                }
+            }
+        }
+        
+        // Validate NOT VALID constraints last, each in its own step, so the scan
+        // holds only a SHARE UPDATE EXCLUSIVE lock and can be retried on its own
+        migrations.extend(validations);
+        
+        // Build the held-back indexes once everything before them, data loads included, has run
+        for (table_name, indexes) in &deferred_indexes {
+            let indexes: Vec<&crate::schema::types::Index> = indexes.iter().collect();
+            let sql = self.generate_create_indices_sql(table_name, &indexes)?;
+            if !sql.trim().is_empty() {
+                migrations.push(sql);
+            }
+        }
+        
+        // Refresh materialized views over changed tables once everything else is in place
+        for view in &diff.materialized_views_to_refresh {
+            if let Some(sql) = self.refresh_materialized_view_sql(view) {
+                migrations.push(sql);
+            }
+        }
+        
+        // A failed migration can leave earlier DDL behind where DDL commits implicitly
+        if let Some(dialect) = Dialect::from_driver(&self.config.database.driver) {
+            if !migrations.is_empty()
+                && self.config.migrations.transaction_per_migration
+                && !dialect.capabilities().transactional_ddl
+            {
+                let message = format!(
+                    "{} commits each DDL statement on its own; a failed migration is not rolled back",
+                    dialect.name()
+                );
+                tracing::warn!("{}", message);
+                self.diagnostics.lock().unwrap().warn("non_transactional_ddl", message, None, None);
+            }
+        }
+        
+        // Rewrite statements through the configured templates
+        if let Some(templates) = &self.config.migrations.templates {
+            migrations = apply_templates(migrations, templates)?;
+        }
+        
+        // Lay the SQL out as configured by [migrations.format]
+        if let Some(format) = &self.config.migrations.format {
+            let formatter = SqlFormatter::new(format)?;
+            migrations = migrations.iter().map(|sql| formatter.format(sql)).collect();
+        }
+        
+        // Catch malformed SQL here, before it reaches the database
+        if self.config.migrations.validate_sql.unwrap_or(false) {
+            validate_sql(&migrations, &self.config.database.driver)?;
+        }
+        
         Ok(migrations)
     }
     
-    /// Find a table by name in the diff
-    fn find_table_by_name<'b>(&self, table_name: &str, diff: &'b SchemaDiff) -> Option<&'b Table> {
-        diff.tables_to_create.iter().find(|t| t.name == table_name)
+    /// Whether new tables' non-unique indexes are created at the end of the plan
+    fn create_indexes_last(&self) -> bool {
+        self.config
+            .performance
+            .as_ref()
+            .and_then(|p| p.create_indexes_last)
+            .unwrap_or(false)
+    }
+    
+    /// Whether constraints on existing tables are added as NOT VALID and validated later
+    ///
+    /// Only Postgres supports this; elsewhere constraints are always validated on creation.
+    fn validate_constraints_separately(&self) -> bool {
+        self.config.database.driver == "postgres"
+            && self
+                .config
+                .performance
+                .as_ref()
+                .and_then(|p| p.validate_constraints_separately)
+                .unwrap_or(false)
     }
     
     /// Generate SQL to create a table
     fn generate_create_table_sql(&self, table: &Table) -> Result<String> {
         let db_type = &self.config.database.driver;
+        self.check_comments_supported(&table.name, table.comment.as_deref(), &table.columns);
+        self.check_charsets_supported(&table.name, &table.columns);
+        self.check_storage_supported(&table.name, &table.columns);
+        self.check_defaults(&table.name, &table.columns);
+        for fk in &table.foreign_keys {
+            self.check_foreign_key_options(&table.name, fk);
+        }
+        self.exclusion_constraints_supported(&table.name, &table.constraints);
+        self.check_index_options(&table.name, &table.indexes.iter().collect::<Vec<_>>());
+        if let Some(tablespace) = &table.tablespace {
+            self.check_tablespace_supported(&table.name, tablespace);
+        }
+        self.check_table_options(table);
         
-        match db_type.as_str() {
-            "postgres" => self.generate_postgres_create_table_sql(table),
-            "mysql" => self.generate_mysql_create_table_sql(table),
-            "sqlite" => self.generate_sqlite_create_table_sql(table),
-            _ => Err(crate::error::Error::MigrationError(format!(
-                "Unsupported database type: {}", db_type
-            ))),
+        let mut sql = match db_type.as_str() {
+            "postgres" => self.generate_postgres_create_table_sql(table)?,
+            "mysql" => self.generate_mysql_create_table_sql(table)?,
+            "sqlite" => self.generate_sqlite_create_table_sql(table)?,
+            "clickhouse" => self.generate_clickhouse_create_table_sql(table),
+            "oracle" => self.generate_oracle_create_table_sql(table)?,
+            _ => return Err(unsupported_driver(db_type)),
+        };
+        
+        // The model's extra SQL is passed through as written, after everything else
+        for statement in &table.extra_sql {
+            sql.push_str(&format!("{};\n", statement));
         }
+        
+        Ok(sql)
     }
     
     /// Generate PostgreSQL-specific table creation SQL
     fn generate_postgres_create_table_sql(&self, table: &Table) -> Result<String> {
-        let mut sql = format!("CREATE TABLE IF NOT EXISTS {} (\n", table.name);
+        let unlogged = if table_flag(table, "unlogged") { "UNLOGGED " } else { "" };
+        let mut sql = format!("CREATE {}TABLE IF NOT EXISTS {} (\n", unlogged, table.name);
         
         // Add columns
         let mut column_defs = Vec::new();
@@ -134,8 +494,27 @@ impl<'a> MigrationGenerator<'a> {
             column_defs.push(format!("  PRIMARY KEY ({})", columns));
         }
         
+        // Add exclusion and CHECK constraints
+        for constraint in table.constraints.iter().filter(|c| matches!(c.constraint_type.as_str(), "EXCLUDE" | "CHECK")) {
+            column_defs.push(format!("  CONSTRAINT {} {}", constraint.name, constraint.definition));
+        }
+        
         sql.push_str(&column_defs.join(",\n"));
-        sql.push_str("\n);\n");
+        sql.push_str("\n)");
+        if let Some(method) = table.options.get("access_method") {
+            sql.push_str(&format!(" USING {}", method));
+        }
+        let storage: Vec<String> = table.options.iter()
+            .filter(|(key, _)| POSTGRES_STORAGE_PARAMETERS.contains(&key.as_str()))
+            .map(|(key, value)| format!("{} = {}", key, value))
+            .collect();
+        if !storage.is_empty() {
+            sql.push_str(&format!(" WITH ({})", storage.join(", ")));
+        }
+        if let Some(tablespace) = &table.tablespace {
+            sql.push_str(&format!(" TABLESPACE {}", tablespace));
+        }
+        sql.push_str(";\n");
         
         // Add table comment if present
         if let Some(comment) = &table.comment {
@@ -158,39 +537,14 @@ impl<'a> MigrationGenerator<'a> {
             }
         }
         
-        // Add indices
-        for index in &table.indexes {
-            let unique = if index.is_unique { "UNIQUE " } else { "" };
-            let method = index.method.as_deref().unwrap_or("btree");
-            let columns = index.columns.join(", ");
-            
-            sql.push_str(&format!(
-                "CREATE {}INDEX {} ON {} USING {} ({});\n",
-                unique,
-                index.name,
-                table.name,
-                method,
-                columns
-            ));
+        // Storage strategies can only be set once the column exists
+        for column in &table.columns {
+            sql.push_str(&postgres_storage_sql(&table.name, column));
         }
         
-        // Add foreign keys
-        for fk in &table.foreign_keys {
-            let columns = fk.columns.join(", ");
-            let ref_columns = fk.ref_columns.join(", ");
-            let on_delete = fk.on_delete.as_deref().unwrap_or("NO ACTION");
-            let on_update = fk.on_update.as_deref().unwrap_or("NO ACTION");
-            
-            sql.push_str(&format!(
-                "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({}) ON DELETE {} ON UPDATE {};\n",
-                table.name,
-                fk.name,
-                columns,
-                fk.ref_table,
-                ref_columns,
-                on_delete,
-                on_update
-            ));
+        // Add indices
+        for index in &table.indexes {
+            sql.push_str(&self.postgres_create_index_sql(&table.name, index, false));
         }
         
         Ok(sql)
@@ -217,9 +571,11 @@ impl<'a> MigrationGenerator<'a> {
             
             // MySQL uses backticks for identifiers
             column_defs.push(format!(
-                "  `{}` {}{} {}",
+                "  `{}` {}{}{}{} {}",
                 column.name,
                 self.translate_data_type_for_mysql(&column.data_type),
+                mysql_charset(column),
+                collate_clause(column),
                 default,
                 nullable
             ));
@@ -244,14 +600,11 @@ impl<'a> MigrationGenerator<'a> {
         
         // Add keys for all unique constraints
         for index in table.indexes.iter().filter(|idx| idx.is_unique) {
-            let index_columns: Vec<String> = index.columns.iter()
-                .map(|col| format!("`{}`", col))
-                .collect();
-            
             column_defs.push(format!(
-                "  UNIQUE KEY `{}` ({})",
+                "  UNIQUE KEY `{}` ({}){}",
                 index.name,
-                index_columns.join(", ")
+                index_key_list(index, |column| format!("`{}`", column), true),
+                mysql_index_options(index)
             ));
         }
         
@@ -269,16 +622,21 @@ impl<'a> MigrationGenerator<'a> {
             let on_update = fk.on_update.as_deref().unwrap_or("RESTRICT");
             
             column_defs.push(format!(
-                "  CONSTRAINT `{}` FOREIGN KEY ({}) REFERENCES `{}` ({}) ON DELETE {} ON UPDATE {}",
+                "  CONSTRAINT `{}` FOREIGN KEY ({}) REFERENCES {} ({}) ON DELETE {} ON UPDATE {}",
                 fk.name,
                 fk_columns.join(", "),
-                fk.ref_table,
+                mysql_qualified_name(&fk.ref_table),
                 ref_columns.join(", "),
                 on_delete,
                 on_update
             ));
         }
         
+        // Add CHECK constraints (enforced from MySQL 8.0.16)
+        for constraint in table.constraints.iter().filter(|c| c.constraint_type == "CHECK") {
+            column_defs.push(format!("  CONSTRAINT `{}` {}", constraint.name, constraint.definition));
+        }
+        
         sql.push_str(&column_defs.join(",\n"));
         
         // Add table options
@@ -294,6 +652,19 @@ impl<'a> MigrationGenerator<'a> {
             table_options.push(comment_option);
         }
         
+        if let Some(tablespace) = &table.tablespace {
+            table_options.push(format!("TABLESPACE `{}`", tablespace));
+        }
+        
+        for (key, value) in &table.options {
+            match key.as_str() {
+                "engine" => table_options.insert(0, format!("ENGINE={}", value)),
+                "compression" => table_options.push(format!("COMPRESSION='{}'", value)),
+                "row_format" | "key_block_size" => table_options.push(format!("{}={}", key.to_uppercase(), value)),
+                _ => {}
+            }
+        }
+        
         if !table_options.is_empty() {
             sql.push_str(&format!("\n) {};\n", table_options.join(" ")));
         } else {
@@ -302,15 +673,12 @@ impl<'a> MigrationGenerator<'a> {
         
         // Create non-unique indices (MySQL doesn't include these in the CREATE TABLE)
         for index in table.indexes.iter().filter(|idx| !idx.is_unique) {
-            let index_columns: Vec<String> = index.columns.iter()
-                .map(|col| format!("`{}`", col))
-                .collect();
-            
             sql.push_str(&format!(
-                "CREATE INDEX `{}` ON `{}` ({});\n",
+                "CREATE INDEX `{}` ON `{}` ({}){};\n",
                 index.name,
                 table.name,
-                index_columns.join(", ")
+                index_key_list(index, |column| format!("`{}`", column), true),
+                mysql_index_options(index)
             ));
         }
         
@@ -332,9 +700,10 @@ impl<'a> MigrationGenerator<'a> {
             };
             
             let mut column_def = format!(
-                "  \"{}\" {}{}",
+                "  \"{}\" {}{}{}",
                 column.name,
                 self.translate_data_type_for_sqlite(&column.data_type),
+                collate_clause(column),
                 default
             );
             
@@ -391,37 +760,229 @@ impl<'a> MigrationGenerator<'a> {
             };
             
             column_defs.push(format!(
-                "  FOREIGN KEY ({}) REFERENCES \"{}\" ({}){}{}",
+                "  FOREIGN KEY ({}) REFERENCES \"{}\" ({}){}{}{}",
                 fk_columns.join(", "),
                 fk.ref_table,
                 ref_columns.join(", "),
                 on_delete,
-                on_update
+                on_update,
+                deferrable_clause(fk)
             ));
         }
         
+        // Add CHECK constraints
+        for constraint in table.constraints.iter().filter(|c| c.constraint_type == "CHECK") {
+            column_defs.push(format!("  CONSTRAINT \"{}\" {}", constraint.name, constraint.definition));
+        }
+        
         sql.push_str(&column_defs.join(",\n"));
-        sql.push_str("\n);\n");
+        let table_options: Vec<&str> = [("strict", "STRICT"), ("without_rowid", "WITHOUT ROWID")]
+            .into_iter()
+            .filter(|(key, _)| table_flag(table, key))
+            .map(|(_, option)| option)
+            .collect();
+        if table_options.is_empty() {
+            sql.push_str("\n);\n");
+        } else {
+            sql.push_str(&format!("\n) {};\n", table_options.join(", ")));
+        }
         
         // Create indices (SQLite doesn't include these in the CREATE TABLE)
         for index in &table.indexes {
             let unique = if index.is_unique { "UNIQUE " } else { "" };
-            let index_columns: Vec<String> = index.columns.iter()
-                .map(|col| format!("\"{}\"", col))
-                .collect();
-            
             sql.push_str(&format!(
                 "CREATE {}INDEX IF NOT EXISTS \"{}\" ON \"{}\" ({});\n",
                 unique,
                 index.name,
                 table.name,
-                index_columns.join(", ")
+                index_key_list(index, |column| format!("\"{}\"", column), false)
+            ));
+        }
+        
+        Ok(sql)
+    }
+    
+    /// Generate ClickHouse-specific table creation SQL
+    ///
+    /// The `engine` table option picks the engine (default MergeTree). MergeTree-family
+    /// tables are sorted by the `order_by` option, else by the primary key columns.
+    /// Foreign keys and indexes have no ClickHouse equivalent and are left out.
+    fn generate_clickhouse_create_table_sql(&self, table: &Table) -> String {
+        for index in &table.indexes {
+            self.warn(
+                "unsupported_index",
+                format!("clickhouse has no secondary B-tree indexes; {} was not created", index.name),
+                &table.name,
+                None,
+            );
+        }
+        for fk in &table.foreign_keys {
+            self.warn(
+                "unsupported_foreign_key",
+                format!("clickhouse has no foreign keys; {} was not created", fk.name),
+                &table.name,
+                fk.columns.first().map(String::as_str),
+            );
+        }
+        
+        let mut column_defs: Vec<String> = table.columns.iter()
+            .map(|column| format!("  {}", clickhouse_column_definition(&column.name, column)))
+            .collect();
+        for constraint in table.constraints.iter().filter(|c| c.constraint_type == "CHECK") {
+            column_defs.push(format!("  CONSTRAINT `{}` {}", constraint.name, constraint.definition));
+        }
+        
+        let engine = table.options.get("engine").map(String::as_str).unwrap_or("MergeTree");
+        let mut sql = format!(
+            "CREATE TABLE IF NOT EXISTS `{}` (\n{}\n) ENGINE = {}",
+            table.name,
+            column_defs.join(",\n"),
+            engine
+        );
+        if let Some(partition_by) = table.options.get("partition_by") {
+            sql.push_str(&format!("\nPARTITION BY {}", partition_by));
+        }
+        if engine.contains("MergeTree") {
+            let order_by = match (table.options.get("order_by"), &table.primary_key) {
+                (Some(order_by), _) => order_by.clone(),
+                (None, Some(pk)) if !pk.columns.is_empty() => {
+                    let columns: Vec<String> = pk.columns.iter().map(|col| format!("`{}`", col)).collect();
+                    format!("({})", columns.join(", "))
+                }
+                _ => "tuple()".to_string(),
+            };
+            sql.push_str(&format!("\nORDER BY {}", order_by));
+        }
+        if let Some(comment) = &table.comment {
+            sql.push_str(&format!("\nCOMMENT '{}'", comment.replace('\'', "''")));
+        }
+        sql.push_str(";\n");
+        
+        sql
+    }
+    
+    /// Generate Oracle-specific table creation SQL
+    ///
+    /// Foreign keys are added once every new table exists, as on Postgres. Before 12.1
+    /// Oracle has no identity columns, so serial and identity columns are filled from a
+    /// sequence by a trigger instead.
+    fn generate_oracle_create_table_sql(&self, table: &Table) -> Result<String> {
+        let release = self.config.database.oracle_release()?;
+        let mut column_defs: Vec<String> = table.columns.iter()
+            .map(|column| format!("  {}", self.oracle_column_definition(&column.name, column, release)))
+            .collect();
+        
+        if let Some(pk) = table.primary_key.as_ref().filter(|pk| !pk.columns.is_empty()) {
+            let pk_columns: Vec<String> = pk.columns.iter().map(|col| format!("\"{}\"", col)).collect();
+            let name = pk.name.as_ref().map(|name| format!("CONSTRAINT \"{}\" ", name)).unwrap_or_default();
+            column_defs.push(format!("  {}PRIMARY KEY ({})", name, pk_columns.join(", ")));
+        }
+        
+        for constraint in table.constraints.iter().filter(|c| c.constraint_type == "CHECK") {
+            column_defs.push(format!("  CONSTRAINT \"{}\" {}", constraint.name, constraint.definition));
+        }
+        
+        let mut sql = format!("CREATE TABLE \"{}\" (\n{}\n)", table.name, column_defs.join(",\n"));
+        if let Some(tablespace) = &table.tablespace {
+            sql.push_str(&format!(" TABLESPACE {}", tablespace));
+        }
+        sql.push_str(";\n");
+        
+        if release < (12, 1) {
+            for column in table.columns.iter().filter(|column| is_identity_type(&column.data_type)) {
+                sql.push_str(&oracle_identity_trigger_sql(&table.name, &column.name));
+            }
+        }
+        
+        for index in &table.indexes {
+            let unique = if index.is_unique { "UNIQUE " } else { "" };
+            sql.push_str(&format!(
+                "CREATE {}INDEX \"{}\" ON \"{}\" ({});\n",
+                unique,
+                index.name,
+                table.name,
+                index_key_list(index, |column| format!("\"{}\"", column), false)
             ));
         }
         
+        if let Some(comment) = &table.comment {
+            sql.push_str(&format!("COMMENT ON TABLE \"{}\" IS '{}';\n", table.name, comment.replace('\'', "''")));
+        }
+        for column in &table.columns {
+            if let Some(comment) = &column.comment {
+                sql.push_str(&format!(
+                    "COMMENT ON COLUMN \"{}\".\"{}\" IS '{}';\n",
+                    table.name,
+                    column.name,
+                    comment.replace('\'', "''")
+                ));
+            }
+        }
+        
         Ok(sql)
     }
     
+    /// Oracle column definition: name, type (or identity), collation, default, and nullability
+    fn oracle_column_definition(&self, name: &str, column: &Column, release: (u32, u32)) -> String {
+        let mut definition = format!(
+            "\"{}\" {}{}",
+            name,
+            self.translate_data_type_for_oracle(&column.data_type, release),
+            collate_clause(column)
+        );
+        if is_identity_type(&column.data_type) && release >= (12, 1) {
+            definition.push_str(" GENERATED BY DEFAULT ON NULL AS IDENTITY");
+        } else if let Some(default_val) = &column.default {
+            definition.push_str(&format!(" DEFAULT {}", oracle_default(default_val)));
+        }
+        if !column.nullable {
+            definition.push_str(" NOT NULL");
+        }
+        
+        definition
+    }
+    
+    /// `ALTER TABLE ... ADD CONSTRAINT ... FOREIGN KEY` statement for Oracle
+    ///
+    /// Oracle has no `ON UPDATE` actions and only cascades or nulls on delete; other
+    /// actions are left out with a warning.
+    fn oracle_add_foreign_key_sql(&self, table_name: &str, fk: &crate::schema::types::ForeignKey) -> String {
+        let on_delete = match fk.on_delete.as_deref() {
+            Some(action @ ("CASCADE" | "SET NULL")) => format!(" ON DELETE {}", action),
+            Some("NO ACTION" | "RESTRICT") | None => String::new(),
+            Some(action) => {
+                self.warn(
+                    "unsupported_referential_action",
+                    format!("oracle has no ON DELETE {}; {} only restricts", action, fk.name),
+                    table_name,
+                    fk.columns.first().map(String::as_str),
+                );
+                String::new()
+            }
+        };
+        if let Some(action) = fk.on_update.as_deref().filter(|action| !matches!(*action, "NO ACTION" | "RESTRICT")) {
+            self.warn(
+                "unsupported_referential_action",
+                format!("oracle has no ON UPDATE {}; {} only restricts", action, fk.name),
+                table_name,
+                fk.columns.first().map(String::as_str),
+            );
+        }
+        
+        let fk_columns: Vec<String> = fk.columns.iter().map(|col| format!("\"{}\"", col)).collect();
+        let ref_columns: Vec<String> = fk.ref_columns.iter().map(|col| format!("\"{}\"", col)).collect();
+        format!(
+            "ALTER TABLE \"{}\" ADD CONSTRAINT \"{}\" FOREIGN KEY ({}) REFERENCES \"{}\" ({}){}{};\n",
+            table_name,
+            fk.name,
+            fk_columns.join(", "),
+            fk.ref_table,
+            ref_columns.join(", "),
+            on_delete,
+            deferrable_clause(fk)
+        )
+    }
+    
     /// Generate SQL to drop a table
     fn generate_drop_table_sql(&self, table_name: &str) -> Result<String> {
         let db_type = &self.config.database.driver;
@@ -430,15 +991,19 @@ impl<'a> MigrationGenerator<'a> {
             "postgres" => Ok(format!("DROP TABLE IF EXISTS {};", table_name)),
             "mysql" => Ok(format!("DROP TABLE IF EXISTS `{}`;", table_name)),
             "sqlite" => Ok(format!("DROP TABLE IF EXISTS \"{}\";", table_name)),
-            _ => Err(crate::error::Error::MigrationError(format!(
-                "Unsupported database type: {}", db_type
-            ))),
+            "clickhouse" => Ok(format!("DROP TABLE IF EXISTS `{}`;", table_name)),
+            "oracle" => Ok(oracle_guarded(&format!("DROP TABLE \"{}\" CASCADE CONSTRAINTS", table_name), &[942])),
+            _ => Err(unsupported_driver(db_type)),
         }
     }
     
     /// Generate SQL to add columns to a table
     fn generate_add_columns_sql(&self, table_name: &str, columns: &[Column]) -> Result<String> {
         let db_type = &self.config.database.driver;
+        self.check_comments_supported(table_name, None, columns);
+        self.check_charsets_supported(table_name, columns);
+        self.check_storage_supported(table_name, columns);
+        self.check_defaults(table_name, columns);
         
         match db_type.as_str() {
             "postgres" => {
@@ -470,6 +1035,8 @@ impl<'a> MigrationGenerator<'a> {
                             comment.replace('\'', "''")
                         ));
                     }
+                    
+                    sql.push_str(&postgres_storage_sql(table_name, column));
                 }
                 
                 Ok(sql)
@@ -478,39 +1045,52 @@ impl<'a> MigrationGenerator<'a> {
                 let mut sql = String::new();
                 
                 for column in columns {
-                    let nullable = if column.nullable { "NULL" } else { "NOT NULL" };
-                    let default = if let Some(default_val) = &column.default {
-                        format!(" DEFAULT {}", default_val)
-                    } else {
-                        String::new()
-                    };
-                    
-                    let mut column_def = format!(
-                        "ALTER TABLE `{}` ADD COLUMN `{}` {}{}",
+                    sql.push_str(&format!(
+                        "ALTER TABLE `{}` {};\n",
                         table_name,
-                        column.name,
-                        self.translate_data_type_for_mysql(&column.data_type),
-                        default
-                    );
-                    
-                    if !nullable.is_empty() {
-                        column_def.push_str(&format!(" {}", nullable));
-                    }
-                    
-                    // Add column comment if present
-                    if let Some(comment) = &column.comment {
-                        column_def.push_str(&format!(
-                            " COMMENT '{}'",
-                            comment.replace('\'', "''")
-                        ));
-                    }
-                    
-                    sql.push_str(&format!("{};\n", column_def));
+                        self.mysql_add_column_clause(column)
+                    ));
                 }
                 
                 Ok(sql)
             }
-            "sqlite" => {
+            "clickhouse" => {
+                let mut sql = String::new();
+                
+                for column in columns {
+                    sql.push_str(&format!(
+                        "ALTER TABLE `{}` ADD COLUMN IF NOT EXISTS {};\n",
+                        table_name,
+                        clickhouse_column_definition(&column.name, column)
+                    ));
+                }
+                
+                Ok(sql)
+            }
+            "oracle" => {
+                let release = self.config.database.oracle_release()?;
+                let definitions: Vec<String> = columns.iter()
+                    .map(|column| self.oracle_column_definition(&column.name, column, release))
+                    .collect();
+                let mut sql = format!("ALTER TABLE \"{}\" ADD ({});\n", table_name, definitions.join(", "));
+                
+                for column in columns {
+                    if release < (12, 1) && is_identity_type(&column.data_type) {
+                        sql.push_str(&oracle_identity_trigger_sql(table_name, &column.name));
+                    }
+                    if let Some(comment) = &column.comment {
+                        sql.push_str(&format!(
+                            "COMMENT ON COLUMN \"{}\".\"{}\" IS '{}';\n",
+                            table_name,
+                            column.name,
+                            comment.replace('\'', "''")
+                        ));
+                    }
+                }
+                
+                Ok(sql)
+            }
+            "sqlite" => {
                 // SQLite does not directly support adding NOT NULL columns without defaults
                 // We would need to use a transaction and rebuild table approach
                 // For now, we'll handle the simple case only
@@ -520,10 +1100,11 @@ impl<'a> MigrationGenerator<'a> {
                 for column in columns {
                     // SQLite can only add nullable columns or columns with defaults
                     if !column.nullable && column.default.is_none() {
-                        return Err(crate::error::Error::MigrationError(
+                        return Err(MigrationFailure::new(
+                            "table_rebuild_required",
                             format!("SQLite cannot add NOT NULL column '{}' without default value. \
-                                     Consider rebuilding the entire table.", column.name)
-                        ));
+                                     Consider rebuilding the entire table.", column.name),
+                        ).table(table_name).column(&column.name).into());
                     }
                     
                     let nullable = if column.nullable { "" } else { "NOT NULL" };
@@ -534,10 +1115,11 @@ impl<'a> MigrationGenerator<'a> {
                     };
                     
                     let mut column_def = format!(
-                        "ALTER TABLE \"{}\" ADD COLUMN \"{}\" {}{}",
+                        "ALTER TABLE \"{}\" ADD COLUMN \"{}\" {}{}{}",
                         table_name,
                         column.name,
                         self.translate_data_type_for_sqlite(&column.data_type),
+                        collate_clause(column),
                         default
                     );
                     
@@ -550,9 +1132,7 @@ impl<'a> MigrationGenerator<'a> {
                 
                 Ok(sql)
             }
-            _ => Err(crate::error::Error::MigrationError(format!(
-                "Unsupported database type: {}", db_type
-            ))),
+            _ => Err(unsupported_driver(db_type)),
         }
     }
     
@@ -560,13 +1140,15 @@ impl<'a> MigrationGenerator<'a> {
     fn generate_drop_columns_sql(&self, table_name: &str, column_names: &[String]) -> Result<String> {
         let db_type = &self.config.database.driver;
         
+        self.require(Capability::DropColumn, table_name, "recreate the table without those columns")?;
+        
         match db_type.as_str() {
             "postgres" => {
                 let mut sql = String::new();
                 
                 for column_name in column_names {
                     sql.push_str(&format!(
-                        "ALTER TABLE {} DROP COLUMN {};\n",
+                        "ALTER TABLE {} DROP COLUMN IF EXISTS {};\n",
                         table_name,
                         column_name
                     ));
@@ -577,9 +1159,24 @@ impl<'a> MigrationGenerator<'a> {
             "mysql" => {
                 let mut sql = String::new();
                 
+                for column_name in column_names {
+                    sql.push_str(&mysql_if_exists(
+                        "COLUMNS",
+                        "COLUMN_NAME",
+                        table_name,
+                        column_name,
+                        &format!("ALTER TABLE `{}` DROP COLUMN `{}`", table_name, column_name),
+                    ));
+                }
+                
+                Ok(sql)
+            }
+            "clickhouse" => {
+                let mut sql = String::new();
+                
                 for column_name in column_names {
                     sql.push_str(&format!(
-                        "ALTER TABLE `{}` DROP COLUMN `{}`;\n",
+                        "ALTER TABLE `{}` DROP COLUMN IF EXISTS `{}`;\n",
                         table_name,
                         column_name
                     ));
@@ -587,15 +1184,11 @@ impl<'a> MigrationGenerator<'a> {
                 
                 Ok(sql)
             }
-            "sqlite" => {
-                return Err(crate::error::Error::MigrationError(
-                    "SQLite does not support dropping columns directly. \
-                     You need to recreate the table without those columns.".to_string()
-                ));
+            "oracle" => {
+                let columns: Vec<String> = column_names.iter().map(|name| format!("\"{}\"", name)).collect();
+                Ok(format!("ALTER TABLE \"{}\" DROP ({});\n", table_name, columns.join(", ")))
             }
-            _ => Err(crate::error::Error::MigrationError(format!(
-                "Unsupported database type: {}", db_type
-            ))),
+            _ => Err(unsupported_driver(db_type)),
         }
     }
     
@@ -607,16 +1200,24 @@ impl<'a> MigrationGenerator<'a> {
     ) -> Result<String> {
         let db_type = &self.config.database.driver;
         
+        self.require(Capability::AlterColumn, table_name, "recreate the table with the new column definitions")?;
+        self.check_defaults(
+            table_name,
+            column_changes.iter().filter(|change| change.from.default != change.to.default).map(|change| &change.to),
+        );
+        
         match db_type.as_str() {
             "postgres" => {
-                let mut sql = String::new();
+                // Every change goes into one ALTER TABLE, so the table is locked (and, for
+                // type changes, rewritten) once rather than once per column
+                let mut actions = Vec::new();
+                let mut comments = String::new();
                 
                 for change in column_changes {
                     // Alter column type
                     if change.from.data_type != change.to.data_type {
-                        sql.push_str(&format!(
-                            "ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}::{};\n",
-                            table_name,
+                        actions.push(format!(
+                            "ALTER COLUMN {} TYPE {} USING {}::{}",
                             change.column_name,
                             change.to.data_type,
                             change.column_name,
@@ -626,50 +1227,45 @@ impl<'a> MigrationGenerator<'a> {
                     
                     // Alter nullability
                     if change.from.nullable != change.to.nullable {
-                        if change.to.nullable {
-                            sql.push_str(&format!(
-                                "ALTER TABLE {} ALTER COLUMN {} DROP NOT NULL;\n",
-                                table_name,
-                                change.column_name
-                            ));
-                        } else {
-                            sql.push_str(&format!(
-                                "ALTER TABLE {} ALTER COLUMN {} SET NOT NULL;\n",
-                                table_name,
-                                change.column_name
-                            ));
-                        }
+                        actions.push(format!(
+                            "ALTER COLUMN {} {} NOT NULL",
+                            change.column_name,
+                            if change.to.nullable { "DROP" } else { "SET" }
+                        ));
                     }
                     
                     // Alter default value
                     if change.from.default != change.to.default {
-                        if let Some(default_val) = &change.to.default {
-                            sql.push_str(&format!(
-                                "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};\n",
-                                table_name,
+                        match &change.to.default {
+                            Some(default_val) => actions.push(format!(
+                                "ALTER COLUMN {} SET DEFAULT {}",
                                 change.column_name,
                                 default_val
-                            ));
-                        } else {
-                            sql.push_str(&format!(
-                                "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT;\n",
-                                table_name,
-                                change.column_name
-                            ));
+                            )),
+                            None => actions.push(format!("ALTER COLUMN {} DROP DEFAULT", change.column_name)),
                         }
                     }
                     
+                    // Alter storage strategy
+                    if let Some(storage) = change.to.storage.as_ref().filter(|storage| change.from.storage.as_ref() != Some(*storage)) {
+                        actions.push(format!(
+                            "ALTER COLUMN {} SET STORAGE {}",
+                            change.column_name,
+                            storage.to_uppercase()
+                        ));
+                    }
+                    
                     // Alter comment
                     if change.from.comment != change.to.comment {
                         if let Some(comment) = &change.to.comment {
-                            sql.push_str(&format!(
+                            comments.push_str(&format!(
                                 "COMMENT ON COLUMN {}.{} IS '{}';\n",
                                 table_name,
                                 change.column_name,
                                 comment.replace('\'', "''")
                             ));
                         } else {
-                            sql.push_str(&format!(
+                            comments.push_str(&format!(
                                 "COMMENT ON COLUMN {}.{} IS NULL;\n",
                                 table_name,
                                 change.column_name
@@ -678,56 +1274,199 @@ impl<'a> MigrationGenerator<'a> {
                     }
                 }
                 
+                let mut sql = match actions.len() {
+                    0 => String::new(),
+                    1 => format!("ALTER TABLE {} {};\n", table_name, actions[0]),
+                    _ => format!("ALTER TABLE {}\n  {};\n", table_name, actions.join(",\n  ")),
+                };
+                sql.push_str(&comments);
                 Ok(sql)
             }
             "mysql" => {
                 let mut sql = String::new();
                 
                 for change in column_changes {
-                    let nullable = if change.to.nullable { "NULL" } else { "NOT NULL" };
-                    let default = if let Some(default_val) = &change.to.default {
-                        format!(" DEFAULT {}", default_val)
-                    } else {
-                        String::new()
-                    };
-                    
-                    let mut alter_sql = format!(
-                        "ALTER TABLE `{}` MODIFY COLUMN `{}` {}{}",
+                    sql.push_str(&format!(
+                        "ALTER TABLE `{}` {};\n",
                         table_name,
-                        change.column_name,
-                        self.translate_data_type_for_mysql(&change.to.data_type),
-                        default
-                    );
-                    
-                    if !nullable.is_empty() {
-                        alter_sql.push_str(&format!(" {}", nullable));
+                        self.mysql_modify_column_clause(change)
+                    ));
+                }
+                
+                Ok(sql)
+            }
+            "clickhouse" => {
+                let mut sql = String::new();
+                
+                for change in column_changes {
+                    // MODIFY COLUMN keeps a default or comment it doesn't mention, so
+                    // removing one takes its own statement
+                    sql.push_str(&format!(
+                        "ALTER TABLE `{}` MODIFY COLUMN {};\n",
+                        table_name,
+                        clickhouse_column_definition(&change.column_name, &change.to)
+                    ));
+                    if change.from.default.is_some() && change.to.default.is_none() {
+                        sql.push_str(&format!(
+                            "ALTER TABLE `{}` MODIFY COLUMN `{}` REMOVE DEFAULT;\n",
+                            table_name,
+                            change.column_name
+                        ));
                     }
-                    
-                    // Add column comment if present
-                    if let Some(comment) = &change.to.comment {
-                        alter_sql.push_str(&format!(
-                            " COMMENT '{}'",
-                            comment.replace('\'', "''")
+                    if change.from.comment.is_some() && change.to.comment.is_none() {
+                        sql.push_str(&format!(
+                            "ALTER TABLE `{}` COMMENT COLUMN `{}` '';\n",
+                            table_name,
+                            change.column_name
                         ));
                     }
-                    
-                    sql.push_str(&format!("{};\n", alter_sql));
                 }
                 
                 Ok(sql)
             }
-            "sqlite" => {
-                return Err(crate::error::Error::MigrationError(
-                    "SQLite does not support altering column definitions directly. \
-                     You need to recreate the table with the new column definitions.".to_string()
-                ));
+            "oracle" => {
+                // MODIFY names only what changed: repeating a column's current
+                // nullability is an error in Oracle
+                let release = self.config.database.oracle_release()?;
+                let mut modifications = Vec::new();
+                let mut comments = String::new();
+                
+                for change in column_changes {
+                    let mut modification = format!("\"{}\"", change.column_name);
+                    if change.from.data_type != change.to.data_type {
+                        modification.push_str(&format!(
+                            " {}",
+                            self.translate_data_type_for_oracle(&change.to.data_type, release)
+                        ));
+                    }
+                    if change.from.default != change.to.default {
+                        let default_val = change.to.default.as_deref().map(oracle_default);
+                        modification.push_str(&format!(" DEFAULT {}", default_val.as_deref().unwrap_or("NULL")));
+                    }
+                    if change.from.nullable != change.to.nullable {
+                        modification.push_str(if change.to.nullable { " NULL" } else { " NOT NULL" });
+                    }
+                    if modification.len() > change.column_name.len() + 2 {
+                        modifications.push(modification);
+                    }
+                    
+                    // An empty comment removes it
+                    if change.from.comment != change.to.comment {
+                        comments.push_str(&format!(
+                            "COMMENT ON COLUMN \"{}\".\"{}\" IS '{}';\n",
+                            table_name,
+                            change.column_name,
+                            change.to.comment.as_deref().unwrap_or_default().replace('\'', "''")
+                        ));
+                    }
+                }
+                
+                let mut sql = if modifications.is_empty() {
+                    String::new()
+                } else {
+                    format!("ALTER TABLE \"{}\" MODIFY ({});\n", table_name, modifications.join(", "))
+                };
+                sql.push_str(&comments);
+                Ok(sql)
             }
-            _ => Err(crate::error::Error::MigrationError(format!(
-                "Unsupported database type: {}", db_type
-            ))),
+            _ => Err(unsupported_driver(db_type)),
         }
     }
     
+    /// Generate a single MySQL ALTER TABLE covering all column changes for a table
+    fn generate_mysql_batched_alter_sql(
+        &self,
+        table_name: &str,
+        columns_to_add: &[Column],
+        columns_to_drop: &[String],
+        columns_to_alter: &[ColumnChange],
+    ) -> String {
+        self.check_defaults(table_name, columns_to_add);
+        self.check_defaults(
+            table_name,
+            columns_to_alter.iter().filter(|change| change.from.default != change.to.default).map(|change| &change.to),
+        );
+        
+        if columns_to_drop.is_empty() {
+            let mut clauses: Vec<String> = Vec::new();
+            clauses.extend(columns_to_add.iter().map(|column| self.mysql_add_column_clause(column)));
+            clauses.extend(columns_to_alter.iter().map(|change| self.mysql_modify_column_clause(change)));
+            
+            return format!("ALTER TABLE `{}`\n  {};\n", table_name, clauses.join(",\n  "));
+        }
+        
+        // MySQL has no DROP COLUMN IF EXISTS, so the statement is assembled at run time and
+        // leaves out columns a half-applied migration already dropped
+        let literal = |value: &str| format!("'{}'", value.replace('\'', "''"));
+        let (schema, table) = match table_name.rsplit_once('.') {
+            Some((schema, table)) => (literal(schema), table),
+            None => ("DATABASE()".to_string(), table_name),
+        };
+        let mut clauses: Vec<String> = Vec::new();
+        clauses.extend(columns_to_add.iter().map(|column| literal(&self.mysql_add_column_clause(column))));
+        clauses.extend(columns_to_drop.iter().map(|name| {
+            format!(
+                "IF(EXISTS(SELECT 1 FROM information_schema.COLUMNS \
+                 WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {} AND COLUMN_NAME = {}), {}, NULL)",
+                schema,
+                literal(table),
+                literal(name),
+                literal(&format!("DROP COLUMN `{}`", name)),
+            )
+        }));
+        clauses.extend(columns_to_alter.iter().map(|change| literal(&self.mysql_modify_column_clause(change))));
+        
+        format!(
+            "SET @schema_sync_ddl = CONCAT_WS(', ', {});\n\
+             SET @schema_sync_ddl = IF(@schema_sync_ddl = '', 'DO 0', CONCAT({}, @schema_sync_ddl));\n\
+             PREPARE schema_sync_ddl FROM @schema_sync_ddl;\n\
+             EXECUTE schema_sync_ddl;\n\
+             DEALLOCATE PREPARE schema_sync_ddl;\n",
+            clauses.join(",\n  "),
+            literal(&format!("ALTER TABLE `{}` ", table_name)),
+        )
+    }
+    
+    /// MySQL `ADD COLUMN` clause for a column
+    fn mysql_add_column_clause(&self, column: &Column) -> String {
+        format!("ADD COLUMN {}", self.mysql_column_definition(&column.name, column))
+    }
+    
+    /// MySQL `MODIFY COLUMN` clause for a column change
+    fn mysql_modify_column_clause(&self, change: &ColumnChange) -> String {
+        format!("MODIFY COLUMN {}", self.mysql_column_definition(&change.column_name, &change.to))
+    }
+    
+    /// MySQL column definition: name, type, character set, default, nullability, and comment
+    fn mysql_column_definition(&self, name: &str, column: &Column) -> String {
+        let nullable = if column.nullable { "NULL" } else { "NOT NULL" };
+        let default = if let Some(default_val) = &column.default {
+            format!(" DEFAULT {}", default_val)
+        } else {
+            String::new()
+        };
+        
+        let mut definition = format!(
+            "`{}` {}{}{}{} {}",
+            name,
+            self.translate_data_type_for_mysql(&column.data_type),
+            mysql_charset(column),
+            collate_clause(column),
+            default,
+            nullable
+        );
+        
+        // Add column comment if present
+        if let Some(comment) = &column.comment {
+            definition.push_str(&format!(
+                " COMMENT '{}'",
+                comment.replace('\'', "''")
+            ));
+        }
+        
+        definition
+    }
+    
     /// Generate SQL to create indices
     fn generate_create_indices_sql(
         &self,
@@ -735,71 +1474,80 @@ impl<'a> MigrationGenerator<'a> {
         indices: &[&crate::schema::types::Index],
     ) -> Result<String> {
         let db_type = &self.config.database.driver;
+        self.check_index_options(table_name, indices);
         
         match db_type.as_str() {
             "postgres" => {
                 let mut sql = String::new();
                 
+                for index in indices {
+                    sql.push_str(&self.postgres_create_index_sql(table_name, index, true));
+                }
+                
+                Ok(sql)
+            }
+            "mysql" => {
+                let mut sql = String::new();
+                
                 for index in indices {
                     let unique = if index.is_unique { "UNIQUE " } else { "" };
-                    let method = index.method.as_deref().unwrap_or("btree");
-                    let columns = index.columns.join(", ");
-                    
                     sql.push_str(&format!(
-                        "CREATE {}INDEX IF NOT EXISTS {} ON {} USING {} ({});\n",
+                        "CREATE {}INDEX `{}` ON `{}` ({}){};\n",
                         unique,
                         index.name,
                         table_name,
-                        method,
-                        columns
+                        index_key_list(index, |column| format!("`{}`", column), true),
+                        mysql_index_options(index)
                     ));
                 }
                 
                 Ok(sql)
             }
-            "mysql" => {
+            "sqlite" => {
                 let mut sql = String::new();
                 
                 for index in indices {
                     let unique = if index.is_unique { "UNIQUE " } else { "" };
-                    let index_columns: Vec<String> = index.columns.iter()
-                        .map(|col| format!("`{}`", col))
-                        .collect();
-                    
                     sql.push_str(&format!(
-                        "CREATE {}INDEX `{}` ON `{}` ({});\n",
+                        "CREATE {}INDEX IF NOT EXISTS \"{}\" ON \"{}\" ({});\n",
                         unique,
                         index.name,
                         table_name,
-                        index_columns.join(", ")
+                        index_key_list(index, |column| format!("\"{}\"", column), false)
                     ));
                 }
                 
                 Ok(sql)
             }
-            "sqlite" => {
+            "oracle" => {
                 let mut sql = String::new();
                 
                 for index in indices {
                     let unique = if index.is_unique { "UNIQUE " } else { "" };
-                    let index_columns: Vec<String> = index.columns.iter()
-                        .map(|col| format!("\"{}\"", col))
-                        .collect();
-                    
                     sql.push_str(&format!(
-                        "CREATE {}INDEX IF NOT EXISTS \"{}\" ON \"{}\" ({});\n",
+                        "CREATE {}INDEX \"{}\" ON \"{}\" ({});\n",
                         unique,
                         index.name,
                         table_name,
-                        index_columns.join(", ")
+                        index_key_list(index, |column| format!("\"{}\"", column), false)
                     ));
                 }
                 
                 Ok(sql)
             }
-            _ => Err(crate::error::Error::MigrationError(format!(
-                "Unsupported database type: {}", db_type
-            ))),
+            "clickhouse" => {
+                for index in indices {
+                    self.warn(
+                        "unsupported_index",
+                        format!("clickhouse has no secondary B-tree indexes; {} was not created", index.name),
+                        table_name,
+                        None,
+                    );
+                }
+                
+                Ok(String::new())
+            }
+            _ => Err(unsupported_driver(db_type)),
         }
     }
     
@@ -828,10 +1576,12 @@ impl<'a> MigrationGenerator<'a> {
                 let mut sql = String::new();
                 
                 for index_name in index_names {
-                    sql.push_str(&format!(
-                        "DROP INDEX `{}` ON `{}`;\n",
+                    sql.push_str(&mysql_if_exists(
+                        "STATISTICS",
+                        "INDEX_NAME",
+                        table_name,
                         index_name,
-                        table_name
+                        &format!("DROP INDEX `{}` ON `{}`", index_name, table_name),
                     ));
                 }
                 
@@ -849,40 +1599,426 @@ impl<'a> MigrationGenerator<'a> {
                 
                 Ok(sql)
             }
-            _ => Err(crate::error::Error::MigrationError(format!(
-                "Unsupported database type: {}", db_type
-            ))),
+            "clickhouse" => {
+                let mut sql = String::new();
+                
+                for index_name in index_names {
+                    sql.push_str(&format!(
+                        "ALTER TABLE `{}` DROP INDEX IF EXISTS `{}`;\n",
+                        table_name,
+                        index_name
+                    ));
+                }
+                
+                Ok(sql)
+            }
+            "oracle" => Ok(index_names.iter()
+                .map(|name| format!("{}\n", oracle_guarded(&format!("DROP INDEX \"{}\"", name), &[1418])))
+                .collect()),
+            _ => Err(unsupported_driver(db_type)),
         }
     }
     
     /// Generate SQL to create foreign keys
+    /// PostgreSQL `ALTER TABLE ... ADD CONSTRAINT ... FOREIGN KEY` statement
+    fn postgres_add_foreign_key_sql(
+        &self,
+        table_name: &str,
+        fk: &crate::schema::types::ForeignKey,
+        not_valid: bool,
+    ) -> String {
+        let match_type = match fk.match_type.as_deref() {
+            Some(match_type) => format!(" MATCH {}", match_type.to_uppercase()),
+            None => String::new(),
+        };
+        
+        format!(
+            "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({}){} ON DELETE {} ON UPDATE {}{}{};\n",
+            table_name,
+            fk.name,
+            fk.columns.join(", "),
+            fk.ref_table,
+            fk.ref_columns.join(", "),
+            match_type,
+            fk.on_delete.as_deref().unwrap_or("NO ACTION"),
+            fk.on_update.as_deref().unwrap_or("NO ACTION"),
+            deferrable_clause(fk),
+            if not_valid { " NOT VALID" } else { "" }
+        )
+    }
+    
+    /// Warn about exclusion constraints the target dialect cannot express
+    ///
+    /// Returns whether they can be generated.
+    fn exclusion_constraints_supported(&self, table_name: &str, constraints: &[Constraint]) -> bool {
+        let dialect = match Dialect::from_driver(&self.config.database.driver) {
+            Some(dialect) => dialect,
+            None => return false,
+        };
+        if dialect.capabilities().exclusion_constraints {
+            return true;
+        }
+        
+        for constraint in constraints.iter().filter(|c| c.constraint_type == "EXCLUDE") {
+            self.warn(
+                "unsupported_exclusion_constraint",
+                format!(
+                    "{} does not support exclusion constraints; {} was not applied",
+                    dialect.name(),
+                    constraint.name
+                ),
+                table_name,
+                None,
+            );
+        }
+        false
+    }
+    
+    /// Generate SQL to add exclusion and CHECK constraints to an existing table
+    ///
+    /// With `validate_constraints_separately`, CHECK constraints are added as NOT VALID
+    /// and validated in a later step.
+    fn generate_add_constraints_sql(&self, table_name: &str, constraints: &[Constraint]) -> Result<Option<String>> {
+        let checks: Vec<&Constraint> = constraints.iter().filter(|c| c.constraint_type == "CHECK").collect();
+        if !checks.is_empty() {
+            self.require(Capability::AlterCheckConstraints, table_name, "recreate the table with the CHECK constraints")?;
+        }
+        
+        let exclusions_supported = self.exclusion_constraints_supported(table_name, constraints);
+        let not_valid = if self.validate_constraints_separately() { " NOT VALID" } else { "" };
+        // Oracle would fold unquoted names to uppercase, and MySQL quotes every name
+        let quote = |name: &str| match self.config.database.driver.as_str() {
+            "oracle" => format!("\"{}\"", name),
+            "mysql" => format!("`{}`", name),
+            _ => name.to_string(),
+        };
+        let sql: String = constraints.iter()
+            .filter_map(|constraint| match constraint.constraint_type.as_str() {
+                "EXCLUDE" if exclusions_supported => Some(format!(
+                    "ALTER TABLE {} ADD CONSTRAINT {} {};\n",
+                    table_name, constraint.name, constraint.definition
+                )),
+                "CHECK" => Some(format!(
+                    "ALTER TABLE {} ADD CONSTRAINT {} {}{};\n",
+                    quote(table_name), quote(&constraint.name), constraint.definition, not_valid
+                )),
+                _ => None,
+            })
+            .collect();
+        
+        Ok(if sql.is_empty() { None } else { Some(sql) })
+    }
+    
+    /// `REFRESH MATERIALIZED VIEW` statement for a view, following its configured refresh mode
+    ///
+    /// `CONCURRENTLY` keeps the view readable during the refresh but needs a unique index;
+    /// without one the refresh falls back to the blocking form.
+    fn refresh_materialized_view_sql(&self, view: &View) -> Option<String> {
+        let mode = self.config.schema.materialized_views.as_ref()
+            .and_then(|views| views.iter().find(|v| v.name == view.name))
+            .and_then(|v| v.refresh.as_deref())
+            .unwrap_or("auto");
+        
+        let concurrently = match mode {
+            "never" => return None,
+            "blocking" => false,
+            "concurrently" if !view.has_unique_index => {
+                self.warn(
+                    "refresh_not_concurrent",
+                    format!(
+                        "materialized view {} has no unique index, so it cannot be refreshed concurrently",
+                        view.name
+                    ),
+                    &view.name,
+                    None,
+                );
+                false
+            }
+            _ => view.has_unique_index,
+        };
+        
+        let concurrently = if concurrently { "CONCURRENTLY " } else { "" };
+        Some(format!("REFRESH MATERIALIZED VIEW {}{};\n", concurrently, view.name))
+    }
+    
+    /// Generate a Postgres `CREATE INDEX` statement
+    fn postgres_create_index_sql(&self, table_name: &str, index: &crate::schema::types::Index, if_not_exists: bool) -> String {
+        let unique = if index.is_unique { "UNIQUE " } else { "" };
+        let if_not_exists = if if_not_exists { "IF NOT EXISTS " } else { "" };
+        let method = index.method.as_deref().unwrap_or("btree");
+        let include = if index.include.is_empty() {
+            String::new()
+        } else {
+            format!(" INCLUDE ({})", index.include.join(", "))
+        };
+        let storage: Vec<String> = index.options.iter()
+            .filter(|(key, _)| !MYSQL_INDEX_OPTIONS.contains(&key.as_str()))
+            .map(|(key, value)| format!("{} = {}", key, value))
+            .collect();
+        let with = if storage.is_empty() {
+            String::new()
+        } else {
+            format!(" WITH ({})", storage.join(", "))
+        };
+        let tablespace = match &index.tablespace {
+            Some(tablespace) => format!(" TABLESPACE {}", tablespace),
+            None => String::new(),
+        };
+        
+        // Postgres takes a function call as a key, but any other expression, such as
+        // `payload->>'type'`, only in parentheses
+        let operator_class = index.operator_class.as_ref().map(|opclass| format!(" {}", opclass)).unwrap_or_default();
+        let keys: Vec<String> = index.columns.iter()
+            .cloned()
+            .chain(index.expression.iter().map(|expression| {
+                let expression = expression.trim();
+                let callee = &expression[..expression.find('(').unwrap_or(0)];
+                if wraps_whole(expression) && callee.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+                    expression.to_string()
+                } else {
+                    format!("({})", expression)
+                }
+            }))
+            .map(|key| format!("{}{}", key, operator_class))
+            .collect();
+        
+        format!(
+            "CREATE {}INDEX {}{} ON {} USING {} ({}){}{}{};\n",
+            unique,
+            if_not_exists,
+            index.name,
+            table_name,
+            method,
+            keys.join(", "),
+            include,
+            with,
+            tablespace
+        )
+    }
+    
+    /// Warn about index options the target dialect ignores
+    fn check_index_options(&self, table_name: &str, indexes: &[&crate::schema::types::Index]) {
+        let driver = self.config.database.driver.as_str();
+        
+        for index in indexes {
+            for key in index.options.keys() {
+                let supported = match driver {
+                    "postgres" => !MYSQL_INDEX_OPTIONS.contains(&key.as_str()),
+                    "mysql" => MYSQL_INDEX_OPTIONS.contains(&key.as_str()),
+                    _ => false,
+                };
+                if !supported {
+                    self.warn(
+                        "unsupported_index_option",
+                        format!("{} does not support index option {}; not applied to {}", driver, key, index.name),
+                        table_name,
+                        None,
+                    );
+                }
+            }
+        }
+        
+        if driver == "postgres" {
+            return;
+        }
+        
+        for index in indexes.iter().filter(|index| index.tablespace.is_some()) {
+            self.warn(
+                "unsupported_tablespace",
+                format!("{} does not support index tablespaces; {} uses the table's", driver, index.name),
+                table_name,
+                None,
+            );
+        }
+        
+        for index in indexes.iter().filter(|index| !index.include.is_empty()) {
+            self.warn(
+                "unsupported_include_columns",
+                format!(
+                    "{} does not support INCLUDE columns; {} indexes only ({})",
+                    self.config.database.driver,
+                    index.name,
+                    index.columns.join(", ")
+                ),
+                table_name,
+                None,
+            );
+        }
+        
+        for index in indexes.iter().filter(|index| index.operator_class.is_some()) {
+            self.warn(
+                "unsupported_operator_class",
+                format!(
+                    "{} has no operator classes; {} uses the default for its keys",
+                    self.config.database.driver,
+                    index.name
+                ),
+                table_name,
+                None,
+            );
+        }
+    }
+    
+    /// Warn when a table is placed in a tablespace the target dialect doesn't have
+    ///
+    /// Returns whether the tablespace can be set.
+    fn check_tablespace_supported(&self, table_name: &str, tablespace: &str) -> bool {
+        let driver = self.config.database.driver.as_str();
+        if matches!(driver, "postgres" | "mysql" | "oracle") {
+            return true;
+        }
+        
+        self.warn(
+            "unsupported_tablespace",
+            format!("{} does not support tablespaces; {} was not placed in {}", driver, table_name, tablespace),
+            table_name,
+            None,
+        );
+        false
+    }
+    
+    /// Warn about table options the target dialect has no equivalent for
+    fn check_table_options(&self, table: &Table) {
+        let driver = self.config.database.driver.as_str();
+        
+        for key in table.options.keys() {
+            let supported = match driver {
+                "postgres" => key == "unlogged" || key == "access_method" || POSTGRES_STORAGE_PARAMETERS.contains(&key.as_str()),
+                "mysql" => matches!(key.as_str(), "engine" | "compression" | "row_format" | "key_block_size"),
+                "sqlite" => matches!(key.as_str(), "strict" | "without_rowid"),
+                "clickhouse" => matches!(key.as_str(), "engine" | "order_by" | "partition_by"),
+                _ => false,
+            };
+            if !supported {
+                self.warn(
+                    "unsupported_table_option",
+                    format!("{} does not support table option {}; not applied to {}", driver, key, table.name),
+                    &table.name,
+                    None,
+                );
+            }
+        }
+    }
+    
+    /// Generate SQL to move an existing table to another tablespace
+    fn generate_set_tablespace_sql(&self, table_name: &str, tablespace: &str) -> Option<String> {
+        if !self.check_tablespace_supported(table_name, tablespace) {
+            return None;
+        }
+        
+        match self.config.database.driver.as_str() {
+            "mysql" => Some(format!("ALTER TABLE `{}` TABLESPACE `{}`;\n", table_name, tablespace)),
+            "oracle" => {
+                // Moving a table offline leaves its indexes unusable until they are rebuilt
+                let online = self.config.database.oracle_release().map_or(true, |release| release >= (12, 2));
+                if !online {
+                    self.warn(
+                        "index_rebuild_required",
+                        format!("oracle before 12.2 cannot move a table online; rebuild the indexes of {} after the move", table_name),
+                        table_name,
+                        None,
+                    );
+                }
+                Some(format!(
+                    "ALTER TABLE \"{}\" MOVE {}TABLESPACE {};\n",
+                    table_name,
+                    if online { "ONLINE " } else { "" },
+                    tablespace
+                ))
+            }
+            _ => Some(format!("ALTER TABLE {} SET TABLESPACE {};\n", table_name, tablespace)),
+        }
+    }
+    
+    /// Generate SQL to change a table's comment, or `None` where the dialect has no table comments
+    fn generate_set_table_comment_sql(&self, table_name: &str, comment: &str) -> Option<String> {
+        let dialect = Dialect::from_driver(&self.config.database.driver);
+        if dialect.is_some_and(|dialect| !dialect.capabilities().table_comments) {
+            self.check_comments_supported(table_name, Some(comment), &[]);
+            return None;
+        }
+        
+        let comment = comment.replace('\'', "''");
+        match self.config.database.driver.as_str() {
+            "mysql" => Some(format!("ALTER TABLE `{}` COMMENT = '{}';\n", table_name, comment)),
+            "clickhouse" => Some(format!("ALTER TABLE `{}` MODIFY COMMENT '{}';\n", table_name, comment)),
+            "oracle" => Some(format!("COMMENT ON TABLE \"{}\" IS '{}';\n", table_name, comment)),
+            _ => Some(format!("COMMENT ON TABLE {} IS '{}';\n", table_name, comment)),
+        }
+    }
+    
+    /// Generate SQL to drop table constraints by name
+    ///
+    /// MySQL only syncs CHECK constraints, which it drops with `DROP CHECK`.
+    fn generate_drop_constraints_sql(&self, table_name: &str, constraint_names: &[String]) -> Result<String> {
+        self.require(Capability::AlterCheckConstraints, table_name, "recreate the table without the constraints")?;
+        
+        Ok(constraint_names.iter()
+            .map(|name| match self.config.database.driver.as_str() {
+                "mysql" => format!("ALTER TABLE `{}` DROP CHECK `{}`;\n", table_name, name),
+                "oracle" => format!(
+                    "{}\n",
+                    oracle_guarded(&format!("ALTER TABLE \"{}\" DROP CONSTRAINT \"{}\"", table_name, name), &[2443])
+                ),
+                _ => format!("ALTER TABLE {} DROP CONSTRAINT IF EXISTS {};\n", table_name, name),
+            })
+            .collect())
+    }
+    
+    /// Warn about foreign key options the target dialect ignores
+    fn check_foreign_key_options(&self, table_name: &str, fk: &crate::schema::types::ForeignKey) {
+        let dialect = match Dialect::from_driver(&self.config.database.driver) {
+            Some(dialect) => dialect,
+            None => return,
+        };
+        
+        if fk.deferrable.is_some() && !dialect.capabilities().deferrable_constraints {
+            self.warn(
+                "unsupported_deferrable",
+                format!(
+                    "{} does not support deferrable foreign keys; {} is checked immediately",
+                    dialect.name(),
+                    fk.name
+                ),
+                table_name,
+                fk.columns.first().map(String::as_str),
+            );
+        }
+        
+        if fk.match_type.is_some() && dialect != Dialect::Postgres {
+            self.warn(
+                "unsupported_match_type",
+                format!(
+                    "{} ignores MATCH options; {} uses MATCH SIMPLE semantics",
+                    dialect.name(),
+                    fk.name
+                ),
+                table_name,
+                fk.columns.first().map(String::as_str),
+            );
+        }
+    }
+    
     fn generate_create_foreign_keys_sql(
         &self,
         table_name: &str,
         foreign_keys: &[&crate::schema::types::ForeignKey],
     ) -> Result<String> {
         let db_type = &self.config.database.driver;
+        for fk in foreign_keys {
+            self.check_foreign_key_options(table_name, fk);
+        }
+        
+        self.require(Capability::AddForeignKey, table_name, "recreate the table with the foreign key constraints")?;
         
         match db_type.as_str() {
             "postgres" => {
                 let mut sql = String::new();
                 
+                let not_valid = self.validate_constraints_separately();
+                
                 for fk in foreign_keys {
-                    let columns = fk.columns.join(", ");
-                    let ref_columns = fk.ref_columns.join(", ");
-                    let on_delete = fk.on_delete.as_deref().unwrap_or("NO ACTION");
-                    let on_update = fk.on_update.as_deref().unwrap_or("NO ACTION");
-                    
-                    sql.push_str(&format!(
-                        "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({}) ON DELETE {} ON UPDATE {};\n",
-                        table_name,
-                        fk.name,
-                        columns,
-                        fk.ref_table,
-                        ref_columns,
-                        on_delete,
-                        on_update
-                    ));
+                    sql.push_str(&self.postgres_add_foreign_key_sql(table_name, fk, not_valid));
                 }
                 Ok(sql)
             }
@@ -902,11 +2038,11 @@ impl<'a> MigrationGenerator<'a> {
                     let on_update = fk.on_update.as_deref().unwrap_or("RESTRICT");
                     
                     sql.push_str(&format!(
-                        "ALTER TABLE `{}` ADD CONSTRAINT `{}` FOREIGN KEY ({}) REFERENCES `{}` ({}) ON DELETE {} ON UPDATE {};\n",
+                        "ALTER TABLE `{}` ADD CONSTRAINT `{}` FOREIGN KEY ({}) REFERENCES {} ({}) ON DELETE {} ON UPDATE {};\n",
                         table_name,
                         fk.name,
                         fk_columns.join(", "),
-                        fk.ref_table,
+                        mysql_qualified_name(&fk.ref_table),
                         ref_columns.join(", "),
                         on_delete,
                         on_update
@@ -915,15 +2051,8 @@ impl<'a> MigrationGenerator<'a> {
                 
                 Ok(sql)
             }
-            "sqlite" => {
-                return Err(crate::error::Error::MigrationError(
-                    "SQLite does not support adding foreign keys to existing tables. \
-                     You need to recreate the table with the foreign key constraints.".to_string()
-                ));
-            }
-            _ => Err(crate::error::Error::MigrationError(format!(
-                "Unsupported database type: {}", db_type
-            ))),
+            "oracle" => Ok(foreign_keys.iter().map(|fk| self.oracle_add_foreign_key_sql(table_name, fk)).collect()),
+            _ => Err(unsupported_driver(db_type)),
         }
     }
     
@@ -935,13 +2064,15 @@ impl<'a> MigrationGenerator<'a> {
     ) -> Result<String> {
         let db_type = &self.config.database.driver;
         
+        self.require(Capability::DropForeignKey, table_name, "recreate the table without the foreign key constraints")?;
+        
         match db_type.as_str() {
             "postgres" => {
                 let mut sql = String::new();
                 
                 for fk_name in fk_names {
                     sql.push_str(&format!(
-                        "ALTER TABLE {} DROP CONSTRAINT {};\n",
+                        "ALTER TABLE {} DROP CONSTRAINT IF EXISTS {};\n",
                         table_name,
                         fk_name
                     ));
@@ -953,27 +2084,39 @@ impl<'a> MigrationGenerator<'a> {
                 let mut sql = String::new();
                 
                 for fk_name in fk_names {
-                    sql.push_str(&format!(
-                        "ALTER TABLE `{}` DROP FOREIGN KEY `{}`;\n",
+                    sql.push_str(&mysql_if_exists(
+                        "TABLE_CONSTRAINTS",
+                        "CONSTRAINT_NAME",
                         table_name,
-                        fk_name
+                        fk_name,
+                        &format!("ALTER TABLE `{}` DROP FOREIGN KEY `{}`", table_name, fk_name),
                     ));
                 }
                 
                 Ok(sql)
             }
-            "sqlite" => {
-                return Err(crate::error::Error::MigrationError(
-                    "SQLite does not support dropping foreign keys from existing tables. \
-                     You need to recreate the table without the foreign key constraints.".to_string()
-                ));
-            }
-            _ => Err(crate::error::Error::MigrationError(format!(
-                "Unsupported database type: {}", db_type
-            ))),
+            "oracle" => Ok(fk_names.iter()
+                .map(|fk_name| format!(
+                    "{}\n",
+                    oracle_guarded(&format!("ALTER TABLE \"{}\" DROP CONSTRAINT \"{}\"", table_name, fk_name), &[2443])
+                ))
+                .collect()),
+            _ => Err(unsupported_driver(db_type)),
         }
     }
     
+    /// A model column type as `database.driver` spells it, e.g. `BOOLEAN` as `TINYINT(1)` on MySQL
+    ///
+    /// This is the type the analyzer reads back for a column the generator created.
+    pub fn native_data_type(&self, data_type: &str) -> Result<String> {
+        Ok(match self.config.database.driver.as_str() {
+            "mysql" => self.translate_data_type_for_mysql(data_type),
+            "sqlite" => self.translate_data_type_for_sqlite(data_type),
+            "oracle" => self.translate_data_type_for_oracle(data_type, self.config.database.oracle_release()?),
+            _ => data_type.to_string(),
+        })
+    }
+    
     /// Translate a PostgreSQL data type to MySQL
     fn translate_data_type_for_mysql(&self, pg_type: &str) -> String {
         let pg_type_lower = pg_type.to_lowercase();
@@ -1051,6 +2194,63 @@ impl<'a> MigrationGenerator<'a> {
         }
     }
     
+    /// Translate a PostgreSQL data type to Oracle
+    ///
+    /// Text is sized in characters rather than bytes. JSON has its own type from 21c and
+    /// vectors from 23ai; earlier releases keep both in a CLOB.
+    fn translate_data_type_for_oracle(&self, pg_type: &str, release: (u32, u32)) -> String {
+        let pg_type_lower = pg_type.trim().to_lowercase();
+        // Arrays are stored as JSON text
+        if pg_type_lower.ends_with("[]") {
+            return "CLOB".to_string();
+        }
+        let (base, params) = match pg_type_lower.split_once('(') {
+            Some((base, rest)) => (base.trim(), Some(rest.split(')').next().unwrap_or_default().replace(' ', ""))),
+            None => (pg_type_lower.as_str(), None),
+        };
+        
+        match base {
+            "smallint" | "int2" | "smallserial" => "NUMBER(5)".to_string(),
+            "integer" | "int" | "int4" | "serial" => "NUMBER(10)".to_string(),
+            "bigint" | "int8" | "bigserial" => "NUMBER(19)".to_string(),
+            t if t.starts_with("bigint generated") => "NUMBER(19)".to_string(),
+            "real" | "float4" => "BINARY_FLOAT".to_string(),
+            "double precision" | "float8" => "BINARY_DOUBLE".to_string(),
+            "numeric" | "decimal" => match params {
+                Some(params) => format!("NUMBER({})", params),
+                None => "NUMBER".to_string(),
+            },
+            "varchar" | "character varying" => format!("VARCHAR2({} CHAR)", params.as_deref().unwrap_or("4000")),
+            "char" | "character" => format!("CHAR({} CHAR)", params.as_deref().unwrap_or("1")),
+            "text" | "citext" | "hstore" => "CLOB".to_string(),
+            "inet" => "VARCHAR2(45 CHAR)".to_string(),
+            "boolean" | "bool" => "NUMBER(1)".to_string(),
+            "uuid" => "RAW(16)".to_string(),
+            "bytea" => "BLOB".to_string(),
+            "json" | "jsonb" if release >= (21, 0) => "JSON".to_string(),
+            "json" | "jsonb" => "CLOB".to_string(),
+            "vector" if release >= (23, 0) => match params {
+                Some(dims) => format!("VECTOR({}, FLOAT32)", dims),
+                None => "VECTOR".to_string(),
+            },
+            "vector" => "CLOB".to_string(),
+            // Oracle's DATE has a time part; TIMESTAMP keeps the precision
+            "date" => "DATE".to_string(),
+            t if t.starts_with("timestamp") => {
+                let precision = params.map(|p| format!("({})", p)).unwrap_or_default();
+                if t == "timestamptz" || pg_type_lower.contains("with time zone") {
+                    format!("TIMESTAMP{} WITH TIME ZONE", precision)
+                } else {
+                    format!("TIMESTAMP{}", precision)
+                }
+            }
+            // A time of day is kept as the interval since midnight
+            t if t.starts_with("time") => "INTERVAL DAY(0) TO SECOND(6)".to_string(),
+            "interval" => "INTERVAL DAY TO SECOND".to_string(),
+            _ => pg_type.to_string(),
+        }
+    }
+    
     /// Translate a PostgreSQL data type to SQLite
     fn translate_data_type_for_sqlite(&self, pg_type: &str) -> String {
         let pg_type_lower = pg_type.to_lowercase();
@@ -1100,4 +2300,183 @@ impl<'a> MigrationGenerator<'a> {
             _ => "TEXT".to_string(),
         }
     }
-}
\ No newline at end of file
+}
+
+/// ClickHouse column definition: name, type (wrapped in `Nullable` when the column is), default, and comment
+fn clickhouse_column_definition(name: &str, column: &Column) -> String {
+    let data_type = clickhouse_type(&column.data_type);
+    let data_type = if column.nullable { clickhouse_nullable(&data_type) } else { data_type };
+    
+    let mut definition = format!("`{}` {}", name, data_type);
+    if let Some(default_val) = &column.default {
+        definition.push_str(&format!(" DEFAULT {}", default_val));
+    }
+    if let Some(comment) = &column.comment {
+        definition.push_str(&format!(" COMMENT '{}'", comment.replace('\'', "''")));
+    }
+    
+    definition
+}
+
+/// `SET STORAGE` statement for a PostgreSQL column with a storage strategy
+fn postgres_storage_sql(table_name: &str, column: &Column) -> String {
+    column
+        .storage
+        .as_ref()
+        .map(|storage| {
+            format!(
+                "ALTER TABLE {} ALTER COLUMN {} SET STORAGE {};\n",
+                table_name,
+                column.name,
+                storage.to_uppercase()
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Error for a `database.driver` the generator has no SQL for
+fn unsupported_driver(db_type: &str) -> Error {
+    MigrationFailure::new("unsupported_driver", format!("Unsupported database type: {}", db_type)).into()
+}
+
+/// Whether a column type is an auto-incrementing integer (`SERIAL`, `... AS IDENTITY`)
+fn is_identity_type(data_type: &str) -> bool {
+    let data_type = data_type.trim().to_lowercase();
+    data_type.ends_with("serial") || data_type.contains("as identity")
+}
+
+/// Sequence and trigger that number a column on Oracle releases without identity columns
+///
+/// The trigger is written on two lines ending in `END;`, so splitting a migration into
+/// statements keeps its body together.
+fn oracle_identity_trigger_sql(table_name: &str, column_name: &str) -> String {
+    let limit = crate::utils::naming::get_max_identifier_length("oracle");
+    let sequence = crate::utils::naming::truncate_identifier(&format!("{}_{}_seq", table_name, column_name), limit);
+    let trigger = crate::utils::naming::truncate_identifier(&format!("{}_{}_trg", table_name, column_name), limit);
+    
+    format!(
+        "CREATE SEQUENCE \"{sequence}\";\n\
+         CREATE OR REPLACE TRIGGER \"{trigger}\" BEFORE INSERT ON \"{table_name}\" FOR EACH ROW WHEN (NEW.\"{column_name}\" IS NULL)\n\
+         BEGIN :NEW.\"{column_name}\" := \"{sequence}\".NEXTVAL; END;\n"
+    )
+}
+
+/// Oracle spelling of a column default written for Postgres
+///
+/// Booleans are stored as 0 and 1, and UUIDs come from `SYS_GUID()`.
+fn oracle_default(default: &str) -> String {
+    match default.trim().to_lowercase().as_str() {
+        "true" => "1".to_string(),
+        "false" => "0".to_string(),
+        "gen_random_uuid()" | "uuid_generate_v4()" => "SYS_GUID()".to_string(),
+        _ => default.to_string(),
+    }
+}
+
+/// `DROP VIEW` statement for a view about to be recreated
+fn drop_view_sql(view: &View) -> String {
+    let kind = if view.is_materialized { "MATERIALIZED VIEW" } else { "VIEW" };
+    format!("DROP {} IF EXISTS {};\n", kind, view.name)
+}
+
+/// Run a MySQL statement only when the object it drops exists
+///
+/// MySQL has no `IF EXISTS` on `DROP INDEX`, `DROP FOREIGN KEY`, or `DROP COLUMN`, so the
+/// statement is prepared from a string chosen by an `information_schema.<catalog>` lookup
+/// of `name` in `name_column`. A migration that failed partway can then be run again.
+fn mysql_if_exists(catalog: &str, name_column: &str, table_name: &str, name: &str, statement: &str) -> String {
+    let literal = |value: &str| format!("'{}'", value.replace('\'', "''"));
+    let (schema, table) = match table_name.rsplit_once('.') {
+        Some((schema, table)) => (literal(schema), table),
+        None => ("DATABASE()".to_string(), table_name),
+    };
+    
+    format!(
+        "SET @schema_sync_ddl = IF(EXISTS(SELECT 1 FROM information_schema.{} \
+         WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {} AND {} = {}), {}, 'DO 0');\n\
+         PREPARE schema_sync_ddl FROM @schema_sync_ddl;\n\
+         EXECUTE schema_sync_ddl;\n\
+         DEALLOCATE PREPARE schema_sync_ddl;\n",
+        catalog,
+        schema,
+        literal(table),
+        name_column,
+        literal(name),
+        literal(statement),
+    )
+}
+
+/// `CREATE VIEW` statement restoring a view from its stored definition
+fn create_view_sql(view: &View) -> String {
+    let kind = if view.is_materialized { "MATERIALIZED VIEW" } else { "VIEW" };
+    let definition = view.definition.trim().trim_end_matches(';').trim_end();
+    let mut sql = format!("CREATE {} {} AS\n{};\n", kind, view.name, definition);
+    // Recreating a view loses its comment, so it is set again
+    if let Some(comment) = &view.comment {
+        sql.push_str(&format!("COMMENT ON {} {} IS '{}';\n", kind, view.name, comment.replace('\'', "''")));
+    }
+    sql
+}
+
+/// ` CHARACTER SET ...` for a column that declares one
+fn mysql_charset(column: &Column) -> String {
+    column.charset.as_ref().map(|charset| format!(" CHARACTER SET {}", charset)).unwrap_or_default()
+}
+
+/// ` COLLATE ...` clause for a column with its own collation (MySQL and SQLite)
+fn collate_clause(column: &Column) -> String {
+    column.collation.as_ref().map(|collation| format!(" COLLATE {}", collation)).unwrap_or_default()
+}
+
+/// Table options Postgres takes as storage parameters (`WITH (...)`)
+const POSTGRES_STORAGE_PARAMETERS: [&str; 3] = ["fillfactor", "autovacuum_enabled", "parallel_workers"];
+
+/// Whether a boolean table option such as `unlogged` is set
+fn table_flag(table: &Table, key: &str) -> bool {
+    table.options.get(key).is_some_and(|value| value == "true")
+}
+
+/// Index options MySQL understands; everything else is a Postgres storage parameter
+const MYSQL_INDEX_OPTIONS: [&str; 1] = ["key_block_size"];
+
+/// MySQL index options, as appended after the key list
+fn mysql_index_options(index: &crate::schema::types::Index) -> String {
+    index.options.iter()
+        .filter(|(key, _)| MYSQL_INDEX_OPTIONS.contains(&key.as_str()))
+        .map(|(key, value)| format!(" {} = {}", key.to_uppercase(), value))
+        .collect()
+}
+
+/// Key list of an index: its quoted columns followed by its expression
+///
+/// MySQL needs functional key parts wrapped in their own parentheses.
+fn index_key_list(index: &crate::schema::types::Index, quote: impl Fn(&str) -> String, wrap_expression: bool) -> String {
+    let mut parts: Vec<String> = index.columns.iter().map(|column| quote(column)).collect();
+    
+    if let Some(expression) = &index.expression {
+        if wrap_expression {
+            parts.push(format!("({})", expression));
+        } else {
+            parts.push(expression.clone());
+        }
+    }
+    
+    parts.join(", ")
+}
+
+/// Quote a possibly database-qualified MySQL table name (`db.table` becomes `` `db`.`table` ``)
+fn mysql_qualified_name(name: &str) -> String {
+    name.split('.')
+        .map(|part| format!("`{}`", part))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// ` DEFERRABLE INITIALLY ...` clause for a deferrable foreign key
+fn deferrable_clause(fk: &crate::schema::types::ForeignKey) -> &'static str {
+    match fk.deferrable.as_deref() {
+        Some("initially_deferred") => " DEFERRABLE INITIALLY DEFERRED",
+        Some(_) => " DEFERRABLE INITIALLY IMMEDIATE",
+        None => "",
+    }
+}
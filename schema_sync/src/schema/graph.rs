@@ -0,0 +1,268 @@
+//! Schema dependency graph
+//!
+//! This module turns a `DatabaseSchema` into a directed graph of tables, columns and views,
+//! with an edge from each object to what it depends on: a table to the tables its foreign
+//! keys reference, a referencing column to the column it references, and a view to the
+//! relations and columns it reads. The graph orders table creation, finds reference cycles,
+//! and answers "what depends on users.email?".
+
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::{Bfs, Reversed};
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::fmt;
+
+use crate::schema::types::{DatabaseSchema, Table, View};
+
+/// A schema object in the dependency graph
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SchemaNode {
+    Table { name: String },
+    Column { table: String, name: String },
+    View { name: String },
+}
+
+impl SchemaNode {
+    /// Name of the table or view the node is, or belongs to
+    pub fn relation(&self) -> &str {
+        match self {
+            SchemaNode::Table { name } | SchemaNode::View { name } => name,
+            SchemaNode::Column { table, .. } => table,
+        }
+    }
+}
+
+impl fmt::Display for SchemaNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaNode::Table { name } => write!(f, "table {}", name),
+            SchemaNode::Column { table, name } => write!(f, "column {}.{}", table, name),
+            SchemaNode::View { name } => write!(f, "view {}", name),
+        }
+    }
+}
+
+/// Why one schema object depends on another
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "name", rename_all = "snake_case")]
+pub enum Dependency {
+    /// A foreign key, by name
+    ForeignKey(String),
+    /// A view reading a relation or column
+    ViewReads,
+}
+
+/// Dependencies between the tables, columns and views of a schema
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    graph: DiGraph<SchemaNode, Dependency>,
+    nodes: HashMap<SchemaNode, NodeIndex>,
+    /// Tables the graph was built from, as opposed to ones only referenced
+    defined: HashSet<String>,
+}
+
+impl DependencyGraph {
+    /// Build the graph of a schema
+    pub fn new(schema: &DatabaseSchema) -> Self {
+        Self::from_parts(schema.tables.values(), schema.views.values())
+    }
+
+    /// Build the graph of some tables and views
+    ///
+    /// Objects they reference but which are not among them (e.g. existing tables a new
+    /// table's foreign keys point at) still get a node.
+    pub fn from_parts<'a>(
+        tables: impl IntoIterator<Item = &'a Table>,
+        views: impl IntoIterator<Item = &'a View>,
+    ) -> Self {
+        let mut graph = Self::default();
+
+        let mut tables: Vec<&Table> = tables.into_iter().collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        for table in &tables {
+            graph.defined.insert(table.name.clone());
+            graph.node(SchemaNode::Table { name: table.name.clone() });
+            for column in &table.columns {
+                graph.node(SchemaNode::Column { table: table.name.clone(), name: column.name.clone() });
+            }
+        }
+
+        let mut views: Vec<&View> = views.into_iter().collect();
+        views.sort_by(|a, b| a.name.cmp(&b.name));
+        for view in &views {
+            graph.node(SchemaNode::View { name: view.name.clone() });
+        }
+
+        for table in &tables {
+            let from = SchemaNode::Table { name: table.name.clone() };
+            for fk in &table.foreign_keys {
+                let dependency = Dependency::ForeignKey(fk.name.clone());
+                graph.edge(from.clone(), SchemaNode::Table { name: fk.ref_table.clone() }, dependency.clone());
+                for (column, ref_column) in fk.columns.iter().zip(&fk.ref_columns) {
+                    graph.edge(
+                        SchemaNode::Column { table: table.name.clone(), name: column.clone() },
+                        SchemaNode::Column { table: fk.ref_table.clone(), name: ref_column.clone() },
+                        dependency.clone(),
+                    );
+                }
+            }
+        }
+
+        let view_names: HashSet<&str> = views.iter().map(|view| view.name.as_str()).collect();
+        for view in &views {
+            let from = SchemaNode::View { name: view.name.clone() };
+            for dependency in &view.depends_on {
+                let Some((relation, column)) = dependency.rsplit_once('.') else {
+                    continue;
+                };
+                let relation_node = if view_names.contains(relation) {
+                    SchemaNode::View { name: relation.to_string() }
+                } else {
+                    graph.edge(
+                        from.clone(),
+                        SchemaNode::Column { table: relation.to_string(), name: column.to_string() },
+                        Dependency::ViewReads,
+                    );
+                    SchemaNode::Table { name: relation.to_string() }
+                };
+                graph.edge(from.clone(), relation_node, Dependency::ViewReads);
+            }
+        }
+
+        graph
+    }
+
+    /// The underlying petgraph graph; edges point from an object to what it depends on
+    pub fn graph(&self) -> &DiGraph<SchemaNode, Dependency> {
+        &self.graph
+    }
+
+    /// Index of a node in `graph()`
+    pub fn index(&self, node: &SchemaNode) -> Option<NodeIndex> {
+        self.nodes.get(node).copied()
+    }
+
+    /// Everything that depends on `object` ("users" or "users.email"), directly or not
+    ///
+    /// A name that is not a table or view is looked up as `table.column`. The result is
+    /// sorted and excludes the object itself.
+    pub fn dependents(&self, object: &str) -> Vec<SchemaNode> {
+        let start = [
+            SchemaNode::Table { name: object.to_string() },
+            SchemaNode::View { name: object.to_string() },
+        ]
+        .into_iter()
+        .chain(object.rsplit_once('.').map(|(table, column)| SchemaNode::Column {
+            table: table.to_string(),
+            name: column.to_string(),
+        }))
+        .find_map(|node| self.index(&node));
+
+        let Some(start) = start else {
+            return Vec::new();
+        };
+
+        let reversed = Reversed(&self.graph);
+        let mut bfs = Bfs::new(reversed, start);
+        let mut dependents = BTreeSet::new();
+        while let Some(index) = bfs.next(reversed) {
+            if index != start {
+                dependents.insert(self.graph[index].clone());
+            }
+        }
+        dependents.into_iter().collect()
+    }
+
+    /// Table names ordered so each comes after the tables its foreign keys reference
+    ///
+    /// Only tables the graph was built from are listed; self-references impose no order.
+    /// Ties are broken by name, and tables in a cycle are taken in name order.
+    pub fn table_order(&self) -> Vec<String> {
+        let tables: BTreeSet<&str> = self.built_tables().collect();
+        let references = |table: &str| -> BTreeSet<&str> {
+            let index = self.nodes[&SchemaNode::Table { name: table.to_string() }];
+            self.graph
+                .neighbors_directed(index, Direction::Outgoing)
+                .filter_map(|neighbor| match &self.graph[neighbor] {
+                    SchemaNode::Table { name } if name != table && tables.contains(name.as_str()) => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let mut waiting_on: HashMap<&str, BTreeSet<&str>> =
+            tables.iter().map(|&table| (table, references(table))).collect();
+        let mut ready: BinaryHeap<Reverse<&str>> = BinaryHeap::new();
+        let mut ordered = Vec::with_capacity(tables.len());
+
+        while ordered.len() < tables.len() {
+            for (&table, pending) in &waiting_on {
+                if pending.is_empty() {
+                    ready.push(Reverse(table));
+                }
+            }
+            waiting_on.retain(|_, pending| !pending.is_empty());
+
+            // A cycle: take the first remaining table by name
+            let Reverse(next) = ready.pop().unwrap_or_else(|| {
+                let first = *waiting_on.keys().min().unwrap();
+                waiting_on.remove(first);
+                Reverse(first)
+            });
+            for pending in waiting_on.values_mut() {
+                pending.remove(next);
+            }
+            ordered.push(next.to_string());
+        }
+
+        ordered
+    }
+
+    /// Groups of tables whose foreign keys reference each other in a cycle, sorted by name
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut cycles: Vec<Vec<String>> = petgraph::algo::tarjan_scc(&self.graph)
+            .into_iter()
+            .map(|component| {
+                let mut tables: Vec<String> = component
+                    .into_iter()
+                    .filter_map(|index| match &self.graph[index] {
+                        SchemaNode::Table { name } => Some(name.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                tables.sort();
+                tables
+            })
+            .filter(|tables| tables.len() > 1)
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    fn built_tables(&self) -> impl Iterator<Item = &str> {
+        self.graph.node_indices().filter_map(|index| match &self.graph[index] {
+            SchemaNode::Table { name } if self.defined.contains(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    fn node(&mut self, node: SchemaNode) -> NodeIndex {
+        if let Some(index) = self.nodes.get(&node) {
+            return *index;
+        }
+        let index = self.graph.add_node(node.clone());
+        self.nodes.insert(node, index);
+        index
+    }
+
+    fn edge(&mut self, from: SchemaNode, to: SchemaNode, dependency: Dependency) {
+        let from = self.node(from);
+        let to = self.node(to);
+        if !self.graph.contains_edge(from, to) {
+            self.graph.add_edge(from, to, dependency);
+        }
+    }
+}
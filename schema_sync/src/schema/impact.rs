@@ -0,0 +1,208 @@
+//! DDL impact estimates
+//!
+//! This module estimates what each generated statement will cost on Postgres before it
+//! runs: the lock it takes, whether it rewrites or scans the table, and how big that table
+//! is (from `pg_class`). Nothing is executed; the output is a report, so slow statements
+//! can be spotted in a plan and scheduled accordingly.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::db::connection::DatabaseConnection;
+use crate::error::Result;
+
+/// Tables above this size make a rewrite or full scan worth flagging (100 MB)
+pub const SLOW_TABLE_BYTES: i64 = 100 * 1024 * 1024;
+
+static TABLE_NAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?i)^(?:ALTER TABLE(?:\s+IF EXISTS)?(?:\s+ONLY)?|DROP TABLE(?:\s+IF EXISTS)?|CREATE TABLE(?:\s+IF NOT EXISTS)?|CREATE(?:\s+UNIQUE)?\s+INDEX(?:\s+CONCURRENTLY)?(?:\s+IF NOT EXISTS)?(?:\s+\S+)?\s+ON(?:\s+ONLY)?)\s+("[^"]+"|`[^`]+`|[\w.]+)"#,
+    )
+    .unwrap()
+});
+static TYPE_CHANGE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)ALTER COLUMN\s+\S+\s+(?:SET DATA\s+)?TYPE\s+(\w+)").unwrap());
+static VOLATILE_DEFAULT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)DEFAULT\s+(?:RANDOM|GEN_RANDOM_UUID|UUID_GENERATE_V[14]|CLOCK_TIMESTAMP|NEXTVAL)\s*\(").unwrap()
+});
+static INDEX_CONSTRAINT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bADD\s+(?:CONSTRAINT\s+\S+\s+)?(?:UNIQUE|PRIMARY KEY)\b").unwrap());
+static VALIDATED_CONSTRAINT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bADD\s+(?:CONSTRAINT\s+\S+\s+)?(?:CHECK|FOREIGN KEY)\b").unwrap());
+
+/// Estimated impact of one DDL statement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementImpact {
+    pub sql: String,
+    /// Table the statement works on, if any
+    pub table: Option<String>,
+    /// Postgres lock level taken on the table (e.g. "ACCESS EXCLUSIVE"); `None` for new objects
+    pub lock: Option<String>,
+    /// Whether the table is rewritten
+    pub rewrite: bool,
+    /// Whether every row is read (to validate a constraint or build an index)
+    pub scan: bool,
+    /// Size of the table including indexes and TOAST, when it exists
+    pub table_bytes: Option<i64>,
+    /// Planner row estimate (`pg_class.reltuples`)
+    pub estimated_rows: Option<i64>,
+}
+
+impl StatementImpact {
+    /// Whether the statement rewrites or scans a large table
+    pub fn is_slow(&self) -> bool {
+        (self.rewrite || self.scan) && self.table_bytes.unwrap_or(0) > SLOW_TABLE_BYTES
+    }
+}
+
+impl fmt::Display for StatementImpact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.sql.lines().next().unwrap_or_default().trim_end_matches(';'))?;
+        match &self.lock {
+            Some(lock) => write!(f, "{} lock", lock)?,
+            None => write!(f, "no lock on existing tables")?,
+        }
+        if self.rewrite {
+            write!(f, ", rewrites the table")?;
+        } else if self.scan {
+            write!(f, ", scans the table")?;
+        }
+        if let Some(bytes) = self.table_bytes {
+            write!(f, " ({} bytes, ~{} rows)", bytes, self.estimated_rows.unwrap_or(0).max(0))?;
+        }
+        if self.is_slow() {
+            write!(f, " [slow]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Split a migration into its statements (one per `;` ending a line)
+pub fn split_statements(migration: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+
+    for line in migration.lines() {
+        if current.is_empty() && (line.trim().is_empty() || line.trim_start().starts_with("--")) {
+            continue;
+        }
+        current.push_str(line);
+        current.push('\n');
+        if line.trim_end().ends_with(';') {
+            statements.push(std::mem::take(&mut current).trim().to_string());
+        }
+    }
+    if !current.trim().is_empty() {
+        statements.push(current.trim().to_string());
+    }
+
+    statements
+}
+
+/// Lock, rewrite, and scan behaviour of a statement on Postgres, without table sizes
+pub fn classify(sql: &str) -> StatementImpact {
+    let normalized = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+    let upper = normalized.to_uppercase();
+    let table = TABLE_NAME
+        .captures(&normalized)
+        .map(|captures| captures[1].trim_matches(|c| c == '"' || c == '`').to_string());
+
+    let mut impact = StatementImpact {
+        sql: sql.to_string(),
+        table,
+        lock: None,
+        rewrite: false,
+        scan: false,
+        table_bytes: None,
+        estimated_rows: None,
+    };
+    let lock = |level: &str| Some(level.to_string());
+
+    if upper.starts_with("ALTER TABLE") {
+        let not_valid = upper.contains("NOT VALID");
+        let type_rewrite = TYPE_CHANGE
+            .captures_iter(&normalized)
+            .any(|captures| !matches!(captures[1].to_uppercase().as_str(), "TEXT" | "VARCHAR"));
+        let add_rewrite = upper.contains("ADD COLUMN")
+            && (VOLATILE_DEFAULT.is_match(&normalized)
+                || upper.contains("SERIAL")
+                || (upper.contains("GENERATED ALWAYS AS") && upper.contains("STORED")));
+
+        impact.rewrite = type_rewrite || add_rewrite || upper.contains("SET TABLESPACE");
+        impact.scan = upper.contains("SET NOT NULL")
+            || upper.contains("VALIDATE CONSTRAINT")
+            || INDEX_CONSTRAINT.is_match(&normalized)
+            || (VALIDATED_CONSTRAINT.is_match(&normalized) && !not_valid);
+
+        // Adding a foreign key or validating a constraint on its own takes a weaker lock
+        let adds = upper.matches(" ADD ").count();
+        let column_changes = upper.contains(" COLUMN ");
+        impact.lock = if adds == 0 && !column_changes && upper.contains("VALIDATE CONSTRAINT") {
+            lock("SHARE UPDATE EXCLUSIVE")
+        } else if adds == 1 && !column_changes && upper.contains("FOREIGN KEY") {
+            lock("SHARE ROW EXCLUSIVE")
+        } else {
+            lock("ACCESS EXCLUSIVE")
+        };
+    } else if upper.starts_with("CREATE INDEX") || upper.starts_with("CREATE UNIQUE INDEX") {
+        impact.scan = true;
+        impact.lock = if upper.contains(" CONCURRENTLY") {
+            lock("SHARE UPDATE EXCLUSIVE")
+        } else {
+            lock("SHARE")
+        };
+    } else if upper.starts_with("DROP INDEX") {
+        impact.lock = if upper.contains(" CONCURRENTLY") {
+            lock("SHARE UPDATE EXCLUSIVE")
+        } else {
+            lock("ACCESS EXCLUSIVE")
+        };
+    } else if upper.starts_with("DROP TABLE") {
+        impact.lock = lock("ACCESS EXCLUSIVE");
+    } else if upper.starts_with("REFRESH MATERIALIZED VIEW") {
+        impact.scan = true;
+        impact.lock = if upper.contains(" CONCURRENTLY") {
+            lock("EXCLUSIVE")
+        } else {
+            lock("ACCESS EXCLUSIVE")
+        };
+    } else if upper.starts_with("COMMENT ON") {
+        impact.lock = lock("SHARE UPDATE EXCLUSIVE");
+    }
+
+    impact
+}
+
+/// Estimate the impact of each statement in `migrations`
+///
+/// On Postgres the affected tables' sizes are read from `pg_class`. Lock levels and rewrite
+/// rules are Postgres's own, so other drivers return no estimates.
+pub async fn estimate(connection: &DatabaseConnection, migrations: &[String]) -> Result<Vec<StatementImpact>> {
+    match connection {
+        #[cfg(feature = "postgres")]
+        DatabaseConnection::Postgres(_) => {
+            let stats = crate::schema::stats::table_stats(connection).await?;
+            let mut impacts = Vec::new();
+            for statement in migrations.iter().flat_map(|migration| split_statements(migration)) {
+                let mut impact = classify(&statement);
+                let table_stats = impact
+                    .table
+                    .as_deref()
+                    .and_then(|table| stats.get(table.rsplit('.').next().unwrap_or(table)));
+                if let Some(table_stats) = table_stats {
+                    impact.table_bytes = table_stats.total_bytes;
+                    impact.estimated_rows = table_stats.estimated_rows;
+                }
+                impacts.push(impact);
+            }
+            Ok(impacts)
+        }
+        #[allow(unreachable_patterns)]
+        _ => {
+            let _ = migrations;
+            Ok(Vec::new())
+        }
+    }
+}
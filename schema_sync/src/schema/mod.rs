@@ -2,16 +2,30 @@
 //!
 //! This module handles database schema analysis, comparison, and generation.
 
+pub mod advisor;
 pub mod analyzer;
+pub mod dialect;
 pub mod diff;
+pub mod format;
 pub mod generator;
+pub mod graph;
+pub mod impact;
+pub mod plan;
+pub mod report;
+pub mod snapshot;
+pub mod stats;
+pub mod templates;
 pub mod types;
+pub mod validate;
 
 // Re-export key types
 pub use analyzer::SchemaAnalyzer;
-pub use diff::{ColumnChange, SchemaDiff};
+pub use dialect::{normalize_default, normalize_expression, Capability, Dialect, DialectCapabilities};
+pub use diff::{ColumnChange, DriftReport, ImpactedObject, SchemaDiff, TableDrift};
+pub use format::SqlFormatter;
 pub use generator::MigrationGenerator;
+pub use plan::{Plan, PLAN_FORMAT_VERSION};
 pub use types::{
     Column, Constraint, DatabaseSchema, FieldDefinition, ForeignKey, 
-    ForeignKeyDefinition, Index, PrimaryKey, Table, View,
+    ForeignKeyDefinition, Function, Index, PrimaryKey, Table, View,
 };
\ No newline at end of file
@@ -0,0 +1,385 @@
+//! Machine-readable plans
+//!
+//! This module serializes a diff and the migrations generated from it as a JSON plan, for
+//! tools outside SchemaSync such as approval bots and change-management systems. Each plan
+//! records the `plan_format_version` it was written with, and the format is kept stable
+//! within a version:
+//!
+//! - fields are only ever added, never removed, renamed, or given a different type;
+//! - new change and statement kinds may appear, so consumers should accept unknown kinds;
+//! - anything else bumps `PLAN_FORMAT_VERSION`.
+//!
+//! `Plan::json_schema` describes the current version as a JSON Schema document.
+//!
+//! With the `plan-signing` feature a plan can carry an approval signature: an HMAC-SHA256,
+//! under a key shared with the approver, of a digest of the driver and the exact SQL. When
+//! `migrations.require_signed_plans` is set, `SchemaSyncClient::apply_plan` runs only plans
+//! whose signature matches, so what was approved is what runs.
+//!
+//! `SchemaSyncClient::plan` also records a fingerprint of each table the plan touches.
+//! `apply_plan` analyzes those tables again first and refuses to run if any of them changed
+//! in between, since the SQL was written for the schema the plan was made against.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::error::{Error, Result};
+use crate::schema::diff::SchemaDiff;
+use crate::schema::impact::{classify, split_statements};
+use crate::schema::types::DatabaseSchema;
+use crate::schema::templates::statement_kind;
+
+/// Version of the plan format written by this release
+pub const PLAN_FORMAT_VERSION: u32 = 1;
+
+/// Change kinds a plan can contain in this format version
+pub const CHANGE_KINDS: [&str; 13] = [
+    "create_table", "drop_table", "add_column", "drop_column", "alter_column", "create_index",
+    "drop_index", "add_foreign_key", "drop_foreign_key", "add_constraint", "drop_constraint",
+    "set_tablespace", "set_comment",
+];
+
+/// A diff and its migrations, as handed to external tools
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Plan {
+    pub plan_format_version: u32,
+    /// Release that wrote the plan, e.g. "schema_sync 0.1.0"
+    pub generated_by: String,
+    pub generated_at: DateTime<Utc>,
+    /// Database driver the migrations were generated for
+    pub driver: String,
+    pub changes: Vec<PlanChange>,
+    pub migrations: Vec<PlanMigration>,
+    /// Hex HMAC-SHA256 of `digest()`, added when the plan is approved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Fingerprint of each table the plan touches, as analyzed when the plan was made;
+    /// tables that did not exist yet are absent. `None` when nothing was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_fingerprints: Option<BTreeMap<String, String>>,
+}
+
+/// One change the diff makes to a table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanChange {
+    /// One of `CHANGE_KINDS`
+    pub kind: String,
+    pub table: String,
+    /// Column, index, foreign key, or constraint changed; `None` for table-level changes
+    pub name: Option<String>,
+    /// Whether the change discards data (dropping a table or column)
+    pub destructive: bool,
+    pub description: String,
+}
+
+/// One generated migration and the statements it consists of
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanMigration {
+    pub sql: String,
+    pub statements: Vec<PlanStatement>,
+}
+
+/// One statement of a migration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanStatement {
+    /// Statement kind as used by `[[migrations.templates]]`, e.g. "add_column"
+    pub kind: String,
+    pub table: Option<String>,
+    pub sql: String,
+}
+
+impl Plan {
+    /// Build the plan for `diff`, whose migrations for `driver` are `migrations`
+    pub fn new(diff: &SchemaDiff, migrations: &[String], driver: &str) -> Self {
+        Self {
+            plan_format_version: PLAN_FORMAT_VERSION,
+            generated_by: format!("schema_sync {}", env!("CARGO_PKG_VERSION")),
+            generated_at: Utc::now(),
+            driver: driver.to_string(),
+            changes: changes(diff),
+            migrations: migrations
+                .iter()
+                .map(|sql| PlanMigration {
+                    sql: sql.clone(),
+                    statements: split_statements(sql)
+                        .into_iter()
+                        .map(|statement| PlanStatement {
+                            kind: statement_kind(&statement).to_string(),
+                            table: classify(&statement).table,
+                            sql: statement,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            signature: None,
+            schema_fingerprints: None,
+        }
+    }
+
+    /// Every table the plan changes, including those it creates
+    pub fn tables(&self) -> BTreeSet<String> {
+        self.changes.iter().map(|change| change.table.clone()).collect()
+    }
+
+    /// Tables whose fingerprint in `current` differs from the one recorded in the plan
+    ///
+    /// `current` should hold the plan's `tables()` as analyzed now. Empty when the plan
+    /// recorded no fingerprints.
+    pub fn changed_tables(&self, current: &DatabaseSchema) -> Vec<String> {
+        let Some(recorded) = &self.schema_fingerprints else {
+            return Vec::new();
+        };
+        let now = current.table_fingerprints();
+
+        self.tables()
+            .into_iter()
+            .filter(|table| recorded.get(table) != now.get(table))
+            .collect()
+    }
+
+    /// The SQL of every migration, in order
+    pub fn sql(&self) -> Vec<String> {
+        self.migrations.iter().map(|migration| migration.sql.clone()).collect()
+    }
+
+    /// Whether any change discards data
+    pub fn is_destructive(&self) -> bool {
+        self.changes.iter().any(|change| change.destructive)
+    }
+
+    /// The plan as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read a plan, rejecting plans written in a newer format version than this release reads
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let version = value
+            .get("plan_format_version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| Error::SerializationError("not a SchemaSync plan: plan_format_version is missing".to_string()))?;
+        if version > PLAN_FORMAT_VERSION as u64 {
+            return Err(Error::SerializationError(format!(
+                "plan format version {} is newer than the newest this release reads ({}); upgrade schema_sync",
+                version, PLAN_FORMAT_VERSION
+            )));
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// JSON Schema for the current plan format version
+    pub fn json_schema() -> serde_json::Value {
+        let string = json!({ "type": "string" });
+        let nullable_string = json!({ "type": ["string", "null"] });
+
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "SchemaSync plan",
+            "type": "object",
+            "required": ["plan_format_version", "generated_by", "generated_at", "driver", "changes", "migrations"],
+            "properties": {
+                "plan_format_version": { "const": PLAN_FORMAT_VERSION },
+                "generated_by": string,
+                "generated_at": { "type": "string", "format": "date-time" },
+                "driver": string,
+                "changes": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["kind", "table", "name", "destructive", "description"],
+                        "properties": {
+                            "kind": { "type": "string", "examples": CHANGE_KINDS },
+                            "table": string,
+                            "name": nullable_string,
+                            "destructive": { "type": "boolean" },
+                            "description": string,
+                        },
+                    },
+                },
+                "signature": { "type": "string", "pattern": "^[0-9a-f]{64}$" },
+                "schema_fingerprints": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string", "pattern": "^[0-9a-f]{32}$" },
+                },
+                "migrations": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["sql", "statements"],
+                        "properties": {
+                            "sql": string,
+                            "statements": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "required": ["kind", "table", "sql"],
+                                    "properties": {
+                                        "kind": string,
+                                        "table": nullable_string,
+                                        "sql": string,
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+        })
+    }
+}
+
+#[cfg(feature = "plan-signing")]
+impl Plan {
+    /// Hex SHA-256 of the driver and each migration's SQL; what a signature approves
+    pub fn digest(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.driver.as_bytes());
+        for migration in &self.migrations {
+            hasher.update([0]);
+            hasher.update(migration.sql.as_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Approve the plan by signing its digest with `key`
+    pub fn sign(&mut self, key: &[u8]) {
+        use hmac::Mac;
+
+        let mut mac = Self::mac(key);
+        mac.update(self.digest().as_bytes());
+        self.signature = Some(hex::encode(mac.finalize().into_bytes()));
+    }
+
+    /// Check that the plan carries a signature by `key` over its current SQL
+    ///
+    /// Fails with an "unsigned_plan" or "invalid_plan_signature" `MigrationFailure`.
+    pub fn verify(&self, key: &[u8]) -> Result<()> {
+        use crate::error::MigrationFailure;
+        use hmac::Mac;
+
+        let signature = self.signature.as_deref().ok_or_else(|| {
+            MigrationFailure::new("unsigned_plan", "the plan has no approval signature")
+        })?;
+        let mut mac = Self::mac(key);
+        mac.update(self.digest().as_bytes());
+        let valid = hex::decode(signature).map(|bytes| mac.verify_slice(&bytes).is_ok()).unwrap_or(false);
+        if !valid {
+            return Err(MigrationFailure::new(
+                "invalid_plan_signature",
+                "the plan's signature does not match its SQL; it was changed after approval or signed with another key",
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn mac(key: &[u8]) -> hmac::Hmac<sha2::Sha256> {
+        use hmac::Mac;
+
+        hmac::Hmac::new_from_slice(key).expect("HMAC accepts keys of any length")
+    }
+}
+
+/// Every change in `diff`, ordered by table, then kind, then name
+fn changes(diff: &SchemaDiff) -> Vec<PlanChange> {
+    let mut changes = Vec::new();
+    let mut push = |kind: &str, table: &str, name: Option<&str>, description: String| {
+        changes.push(PlanChange {
+            kind: kind.to_string(),
+            table: table.to_string(),
+            name: name.map(str::to_string),
+            destructive: matches!(kind, "drop_table" | "drop_column"),
+            description,
+        });
+    };
+
+    for table in &diff.tables_to_create {
+        push("create_table", &table.name, None, format!("create table {}", table.name));
+    }
+    for table in &diff.tables_to_drop {
+        push("drop_table", table, None, format!("drop table {}", table));
+    }
+    for (table, columns) in &diff.columns_to_add {
+        for column in columns {
+            push("add_column", table, Some(&column.name), format!("add column {}.{} {}", table, column.name, column.data_type));
+        }
+    }
+    for (table, columns) in &diff.columns_to_drop {
+        for column in columns {
+            push("drop_column", table, Some(column), format!("drop column {}.{}", table, column));
+        }
+    }
+    for (table, column_changes) in &diff.columns_to_alter {
+        for change in column_changes {
+            let nullability = |nullable: bool| if nullable { "NULL" } else { "NOT NULL" };
+            push(
+                "alter_column",
+                table,
+                Some(&change.column_name),
+                format!(
+                    "alter column {}.{} from {} {} to {} {}",
+                    table,
+                    change.column_name,
+                    change.from.data_type,
+                    nullability(change.from.nullable),
+                    change.to.data_type,
+                    nullability(change.to.nullable)
+                ),
+            );
+        }
+    }
+    for (table, indexes) in &diff.indices_to_create {
+        for index in indexes {
+            push("create_index", table, Some(&index.name), format!("create index {} on {}", index.name, table));
+        }
+    }
+    for (table, indexes) in &diff.indices_to_drop {
+        for index in indexes {
+            push("drop_index", table, Some(index), format!("drop index {} on {}", index, table));
+        }
+    }
+    for (table, foreign_keys) in &diff.foreign_keys_to_create {
+        for fk in foreign_keys {
+            push(
+                "add_foreign_key",
+                table,
+                Some(&fk.name),
+                format!("add foreign key {} on {} referencing {}", fk.name, table, fk.ref_table),
+            );
+        }
+    }
+    for (table, foreign_keys) in &diff.foreign_keys_to_drop {
+        for fk in foreign_keys {
+            push("drop_foreign_key", table, Some(fk), format!("drop foreign key {} on {}", fk, table));
+        }
+    }
+    for (table, constraints) in &diff.constraints_to_create {
+        for constraint in constraints {
+            push("add_constraint", table, Some(&constraint.name), format!("add constraint {} on {}", constraint.name, table));
+        }
+    }
+    for (table, constraints) in &diff.constraints_to_drop {
+        for constraint in constraints {
+            push("drop_constraint", table, Some(constraint), format!("drop constraint {} on {}", constraint, table));
+        }
+    }
+    for (table, tablespace) in &diff.tablespaces_to_set {
+        push("set_tablespace", table, None, format!("move table {} to tablespace {}", table, tablespace));
+    }
+    for (table, comment) in &diff.table_comments_to_set {
+        push("set_comment", table, None, format!("set the comment on table {} to '{}'", table, comment));
+    }
+
+    let order = |kind: &str| CHANGE_KINDS.iter().position(|k| *k == kind).unwrap_or(CHANGE_KINDS.len());
+    changes.sort_by(|a, b| {
+        (&a.table, order(&a.kind), &a.name).cmp(&(&b.table, order(&b.kind), &b.name))
+    });
+    changes
+}
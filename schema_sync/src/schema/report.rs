@@ -0,0 +1,332 @@
+//! HTML diff report
+//!
+//! This module renders a schema diff as a standalone HTML page: for every table that
+//! differs, its current and desired definitions side by side, with each column, index,
+//! foreign key, and constraint marked as added, removed, changed, or unchanged, followed by
+//! the migration SQL. It is meant for review by people who would rather not read the SQL.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::schema::diff::SchemaDiff;
+use crate::schema::types::{Column, Constraint, DatabaseSchema, ForeignKey, Index, Table};
+
+/// File name of the report inside the output directory
+pub const REPORT_FILE: &str = "schema_diff.html";
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }
+td { font-family: monospace; width: 50%; }
+tr.added td:last-child { background: #e6ffed; }
+tr.removed td:first-child { background: #ffeef0; }
+tr.changed td { background: #fff5b1; }
+tr.kept td:first-child { background: #f1f8ff; }
+.status { font-size: 0.8em; font-weight: normal; padding: 2px 6px; border-radius: 3px; background: #eee; }
+pre { background: #f6f8fa; padding: 1em; overflow-x: auto; }";
+
+/// How one object of a table compares between the current and desired schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowStatus {
+    Added,
+    Removed,
+    Changed,
+    /// Only in the database, but the diff leaves it in place (e.g. removal is not allowed)
+    Kept,
+    Unchanged,
+}
+
+impl RowStatus {
+    fn class(&self) -> &'static str {
+        match self {
+            RowStatus::Added => "added",
+            RowStatus::Removed => "removed",
+            RowStatus::Changed => "changed",
+            RowStatus::Kept => "kept",
+            RowStatus::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// One line of the side-by-side comparison
+struct Row {
+    status: RowStatus,
+    current: Option<String>,
+    desired: Option<String>,
+}
+
+/// Render the report for `diff`, which was computed from `current` to `desired`
+pub fn html_report(current: &DatabaseSchema, desired: &DatabaseSchema, diff: &SchemaDiff, migrations: &[String]) -> String {
+    let mut differences: Vec<(String, Vec<String>)> = Vec::new();
+    for (table, difference) in diff.table_differences() {
+        match differences.iter_mut().find(|(name, _)| *name == table) {
+            Some((_, list)) => list.push(difference),
+            None => differences.push((table, vec![difference])),
+        }
+    }
+    differences.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Schema diff</title>\n");
+    let _ = writeln!(html, "<style>\n{}\n</style>\n</head>\n<body>", STYLE);
+    html.push_str("<h1>Schema diff</h1>\n");
+
+    if differences.is_empty() {
+        html.push_str("<p>The database matches the models.</p>\n</body>\n</html>\n");
+        return html;
+    }
+    let _ = writeln!(
+        html,
+        "<p>{} table(s) differ between the database (left) and the models (right).</p>",
+        differences.len()
+    );
+
+    for (name, table_differences) in &differences {
+        let current_table = current.tables.get(name);
+        let desired_table = desired.tables.get(name);
+        let status = match (current_table, desired_table) {
+            (None, Some(_)) => "new table",
+            (Some(_), None) if diff.tables_to_drop.contains(name) => "dropped table",
+            (Some(_), None) => "not in the models",
+            _ => "changed",
+        };
+
+        let _ = writeln!(html, "<section id=\"table-{0}\">\n<h2>{0} <span class=\"status\">{1}</span></h2>", escape(name), status);
+        html.push_str("<ul>\n");
+        for difference in table_differences {
+            let _ = writeln!(html, "<li>{}</li>", escape(difference));
+        }
+        html.push_str("</ul>\n");
+
+        html.push_str("<table>\n<thead><tr><th>Database</th><th>Models</th></tr></thead>\n<tbody>\n");
+        for row in table_rows(name, current_table, desired_table, diff) {
+            let _ = writeln!(
+                html,
+                "<tr class=\"{}\"><td>{}</td><td>{}</td></tr>",
+                row.status.class(),
+                escape(row.current.as_deref().unwrap_or_default()),
+                escape(row.desired.as_deref().unwrap_or_default())
+            );
+        }
+        html.push_str("</tbody>\n</table>\n</section>\n");
+    }
+
+    if !migrations.is_empty() {
+        html.push_str("<h2>Migration SQL</h2>\n<pre>");
+        for migration in migrations {
+            html.push_str(&escape(migration));
+        }
+        html.push_str("</pre>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Write the report as `schema_diff.html` in `directory`, creating it if needed
+pub fn write_html_report(directory: &str, html: &str) -> Result<PathBuf> {
+    fs::create_dir_all(directory)?;
+    let path = Path::new(directory).join(REPORT_FILE);
+    fs::write(&path, html)?;
+    Ok(path)
+}
+
+/// The rows comparing a table's columns, keys, indexes, foreign keys, and constraints
+fn table_rows(name: &str, current: Option<&Table>, desired: Option<&Table>, diff: &SchemaDiff) -> Vec<Row> {
+    let names = |map: &std::collections::HashMap<String, Vec<String>>| -> HashSet<String> {
+        map.get(name).map(|names| names.iter().cloned().collect()).unwrap_or_default()
+    };
+    let dropped_columns = names(&diff.columns_to_drop);
+    let altered_columns: HashSet<String> = diff
+        .columns_to_alter
+        .get(name)
+        .map(|changes| changes.iter().map(|change| change.column_name.clone()).collect())
+        .unwrap_or_default();
+    let created_indexes: HashSet<String> = diff
+        .indices_to_create
+        .get(name)
+        .map(|indexes| indexes.iter().map(|index| index.name.clone()).collect())
+        .unwrap_or_default();
+    let created_foreign_keys: HashSet<String> = diff
+        .foreign_keys_to_create
+        .get(name)
+        .map(|fks| fks.iter().map(|fk| fk.name.clone()).collect())
+        .unwrap_or_default();
+    let created_constraints: HashSet<String> = diff
+        .constraints_to_create
+        .get(name)
+        .map(|constraints| constraints.iter().map(|c| c.name.clone()).collect())
+        .unwrap_or_default();
+    let table_dropped = diff.tables_to_drop.iter().any(|table| table == name);
+
+    let mut rows = Vec::new();
+    let empty = Table::new(name);
+    let (current_table, desired_table) = (current.unwrap_or(&empty), desired.unwrap_or(&empty));
+
+    compare(
+        &mut rows,
+        &current_table.columns,
+        &desired_table.columns,
+        |column| column.name.clone(),
+        column_text,
+        |column| altered_columns.contains(column),
+        |column| table_dropped || dropped_columns.contains(column),
+    );
+
+    let primary_key = |table: &Table| {
+        table.primary_key.as_ref().map(|pk| format!("PRIMARY KEY ({})", pk.columns.join(", ")))
+    };
+    match (primary_key(current_table), primary_key(desired_table)) {
+        (None, None) => {}
+        (current, desired) => {
+            let status = match (&current, &desired) {
+                (Some(_), None) if table_dropped => RowStatus::Removed,
+                (Some(_), None) => RowStatus::Kept,
+                (None, Some(_)) => RowStatus::Added,
+                (current, desired) if current == desired => RowStatus::Unchanged,
+                _ => RowStatus::Changed,
+            };
+            rows.push(Row { status, current, desired });
+        }
+    }
+
+    let dropped_indexes = names(&diff.indices_to_drop);
+    compare(
+        &mut rows,
+        &current_table.indexes,
+        &desired_table.indexes,
+        |index| index.name.clone(),
+        index_text,
+        |index| created_indexes.contains(index),
+        |index| table_dropped || dropped_indexes.contains(index),
+    );
+    let dropped_foreign_keys = names(&diff.foreign_keys_to_drop);
+    compare(
+        &mut rows,
+        &current_table.foreign_keys,
+        &desired_table.foreign_keys,
+        |fk| fk.name.clone(),
+        foreign_key_text,
+        |fk| created_foreign_keys.contains(fk),
+        |fk| table_dropped || dropped_foreign_keys.contains(fk),
+    );
+    let dropped_constraints = names(&diff.constraints_to_drop);
+    compare(
+        &mut rows,
+        &current_table.constraints,
+        &desired_table.constraints,
+        |constraint| constraint.name.clone(),
+        constraint_text,
+        |constraint| created_constraints.contains(constraint),
+        |constraint| table_dropped || dropped_constraints.contains(constraint),
+    );
+
+    rows
+}
+
+/// Pair up objects by name, desired order first, and mark each pair from the diff
+fn compare<T>(
+    rows: &mut Vec<Row>,
+    current: &[T],
+    desired: &[T],
+    name: impl Fn(&T) -> String,
+    text: impl Fn(&T) -> String,
+    changed: impl Fn(&str) -> bool,
+    dropped: impl Fn(&str) -> bool,
+) {
+    for object in desired {
+        let object_name = name(object);
+        let existing = current.iter().find(|other| name(other) == object_name);
+        let status = match existing {
+            None => RowStatus::Added,
+            Some(_) if changed(&object_name) => RowStatus::Changed,
+            Some(_) => RowStatus::Unchanged,
+        };
+        rows.push(Row {
+            status,
+            current: existing.map(&text),
+            desired: Some(text(object)),
+        });
+    }
+
+    for object in current {
+        let object_name = name(object);
+        if desired.iter().any(|other| name(other) == object_name) {
+            continue;
+        }
+        rows.push(Row {
+            status: if dropped(&object_name) { RowStatus::Removed } else { RowStatus::Kept },
+            current: Some(text(object)),
+            desired: None,
+        });
+    }
+}
+
+fn column_text(column: &Column) -> String {
+    let mut text = format!(
+        "{} {} {}",
+        column.name,
+        column.data_type,
+        if column.nullable { "NULL" } else { "NOT NULL" }
+    );
+    if let Some(default) = &column.default {
+        let _ = write!(text, " DEFAULT {}", default);
+    }
+    if column.is_unique {
+        text.push_str(" UNIQUE");
+    }
+    if let Some(expression) = &column.generation_expression {
+        let _ = write!(text, " GENERATED ALWAYS AS ({})", expression);
+    }
+    text
+}
+
+fn index_text(index: &Index) -> String {
+    let mut parts = index.columns.clone();
+    parts.extend(index.expression.clone());
+    let mut text = format!(
+        "{}INDEX {} ({})",
+        if index.is_unique { "UNIQUE " } else { "" },
+        index.name,
+        parts.join(", ")
+    );
+    if let Some(method) = &index.method {
+        let _ = write!(text, " USING {}", method);
+    }
+    if !index.include.is_empty() {
+        let _ = write!(text, " INCLUDE ({})", index.include.join(", "));
+    }
+    text
+}
+
+fn foreign_key_text(fk: &ForeignKey) -> String {
+    let mut text = format!(
+        "FOREIGN KEY {} ({}) REFERENCES {} ({})",
+        fk.name,
+        fk.columns.join(", "),
+        fk.ref_table,
+        fk.ref_columns.join(", ")
+    );
+    if let Some(action) = &fk.on_delete {
+        let _ = write!(text, " ON DELETE {}", action);
+    }
+    if let Some(action) = &fk.on_update {
+        let _ = write!(text, " ON UPDATE {}", action);
+    }
+    text
+}
+
+fn constraint_text(constraint: &Constraint) -> String {
+    format!("CONSTRAINT {} {}", constraint.name, constraint.definition)
+}
+
+/// Escape text for HTML element content and attribute values
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
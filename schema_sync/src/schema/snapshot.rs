@@ -0,0 +1,390 @@
+//! Schema snapshots
+//!
+//! This module loads schemas without a database connection, either from a serialized
+//! `DatabaseSchema` (as written by `schema_sync analyze`) or by replaying a directory of
+//! migrations, so that two versions can be diffed offline to produce an upgrade script.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sqlparser::ast::{
+    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, CommentObject, ConstraintCharacteristics,
+    DeferrableInitial, Expr, ObjectName, ObjectType, OrderByExpr, Statement, TableConstraint,
+};
+use sqlparser::dialect::{ClickHouseDialect, Dialect, GenericDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::parser::Parser;
+
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::schema::diff::SchemaDiff;
+use crate::schema::generator::MigrationGenerator;
+use crate::schema::types::{
+    Column, Constraint, DatabaseSchema, ForeignKey, Index, PrimaryKey, Table, View,
+};
+
+/// Load a schema from a snapshot file or a migrations directory
+pub fn load_schema(path: &Path, driver: &str) -> Result<DatabaseSchema> {
+    if path.is_dir() {
+        schema_from_migrations(path, driver)
+    } else {
+        load_snapshot(path)
+    }
+}
+
+/// Load a serialized schema snapshot (JSON, YAML, or TOML, chosen by file extension)
+pub fn load_snapshot(path: &Path) -> Result<DatabaseSchema> {
+    let content = fs::read_to_string(path)?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&content)
+            .map_err(|e| Error::SerializationError(format!("{}: {}", path.display(), e))),
+        Some("toml") => toml::from_str(&content)
+            .map_err(|e| Error::SerializationError(format!("{}: {}", path.display(), e))),
+        _ => Ok(serde_json::from_str(&content)?),
+    }
+}
+
+/// Reconstruct the schema implied by applying every `.sql` file in a directory in name order
+///
+/// Only DDL is interpreted (tables, columns, constraints, indexes, and views); other
+/// statements such as data backfills are skipped.
+pub fn schema_from_migrations(dir: &Path, driver: &str) -> Result<DatabaseSchema> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("sql"))
+        .collect();
+    files.sort();
+
+    let mut schema = DatabaseSchema::new(None);
+
+    for file in files {
+        let sql = fs::read_to_string(&file)?;
+        replay_sql(&mut schema, &sql, driver)
+            .map_err(|e| Error::SyntaxError(format!("{}: {}", file.display(), e)))?;
+    }
+
+    Ok(schema)
+}
+
+/// Generate the SQL that upgrades the `from` schema to the `to` schema
+pub async fn migration_sql_between(
+    from: DatabaseSchema,
+    to: DatabaseSchema,
+    config: &Config,
+) -> Result<Vec<String>> {
+    let diff = SchemaDiff::generate(from, to, &config.schema);
+    MigrationGenerator::new(config).generate_migration_sql(&diff).await
+}
+
+/// The `sqlparser` dialect for a `database.driver`
+pub(crate) fn parser_dialect(driver: &str) -> Box<dyn Dialect> {
+    match driver {
+        "postgres" => Box::new(PostgreSqlDialect {}),
+        "mysql" => Box::new(MySqlDialect {}),
+        "sqlite" => Box::new(SQLiteDialect {}),
+        "clickhouse" => Box::new(ClickHouseDialect {}),
+        _ => Box::new(GenericDialect {}),
+    }
+}
+
+/// Apply the DDL statements in `sql` to an in-memory schema
+pub fn replay_sql(schema: &mut DatabaseSchema, sql: &str, driver: &str) -> Result<()> {
+    let dialect = parser_dialect(driver);
+    let statements = Parser::parse_sql(dialect.as_ref(), sql)
+        .map_err(|e| Error::SyntaxError(e.to_string()))?;
+
+    for statement in statements {
+        match statement {
+            Statement::CreateTable { name, columns, constraints, if_not_exists, .. } => {
+                let table_name = object_name(&name);
+                if if_not_exists && schema.tables.contains_key(&table_name) {
+                    continue;
+                }
+
+                let mut table = Table::new(&table_name);
+                for column_def in &columns {
+                    add_column(&mut table, column_def);
+                }
+                for constraint in constraints {
+                    add_constraint(&mut table, constraint);
+                }
+                schema.add_table(table);
+            }
+            Statement::AlterTable { name, operations, .. } => {
+                let table_name = object_name(&name);
+                let mut table = schema.tables.remove(&table_name).ok_or_else(|| {
+                    Error::SchemaAnalysisError(format!("ALTER TABLE on unknown table: {}", table_name))
+                })?;
+
+                for operation in operations {
+                    alter_table(&mut table, operation);
+                }
+                schema.add_table(table);
+            }
+            Statement::Drop { object_type, names, .. } => {
+                for name in names {
+                    let name = object_name(&name);
+                    match object_type {
+                        ObjectType::Table => {
+                            schema.tables.remove(&name);
+                        }
+                        ObjectType::View => {
+                            schema.views.remove(&name);
+                        }
+                        ObjectType::Index => {
+                            for table in schema.tables.values_mut() {
+                                table.indexes.retain(|index| index.name != name);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Statement::CreateIndex { name, table_name, using, columns, unique, include, .. } => {
+                let table_name = object_name(&table_name);
+                // Plain columns are kept as columns; anything else is part of the expression
+                let (columns, expressions): (Vec<&OrderByExpr>, Vec<&OrderByExpr>) = columns
+                    .iter()
+                    .partition(|c| matches!(c.expr, Expr::Identifier(_)));
+                let columns: Vec<String> = columns.iter().map(|c| c.expr.to_string()).collect();
+                let expression = (!expressions.is_empty()).then(|| {
+                    expressions.iter().map(|c| c.expr.to_string()).collect::<Vec<_>>().join(", ")
+                });
+                let index_name = name
+                    .map(|name| object_name(&name))
+                    .unwrap_or_else(|| format!("{}_{}_idx", table_name, columns.join("_")));
+
+                if let Some(table) = schema.tables.get_mut(&table_name) {
+                    // A unique index on one column is how a unique column is generated
+                    if unique && expression.is_none() && columns.len() == 1 {
+                        if let Some(column) = table.columns.iter_mut().find(|c| c.name == columns[0]) {
+                            column.is_unique = true;
+                        }
+                    }
+                    table.indexes.retain(|index| index.name != index_name);
+                    table.add_index(Index {
+                        name: index_name,
+                        columns,
+                        is_unique: unique,
+                        method: using.map(|method| method.value),
+                        include: include.into_iter().map(|column| column.value).collect(),
+                        expression,
+                        options: Default::default(),
+                        tablespace: None,
+                        operator_class: None,
+                    });
+                }
+            }
+            Statement::CreateView { name, query, materialized, .. } => {
+                schema.add_view(View {
+                    name: object_name(&name),
+                    definition: query.to_string(),
+                    columns: Vec::new(),
+                    is_materialized: materialized,
+                    depends_on: Vec::new(),
+                    has_unique_index: false,
+                    comment: None,
+                });
+            }
+            Statement::Comment { object_type: CommentObject::Table, object_name: name, comment, .. } => {
+                if let Some(table) = schema.tables.get_mut(&object_name(&name)) {
+                    table.comment = comment;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Unqualified name of a (possibly schema-qualified) object
+fn object_name(name: &ObjectName) -> String {
+    name.0.last().map(|ident| ident.value.clone()).unwrap_or_default()
+}
+
+/// Add a column definition, including any inline constraints, to a table
+fn add_column(table: &mut Table, column_def: &ColumnDef) {
+    let mut column = Column::new(&column_def.name.value, &column_def.data_type.to_string())
+        .nullable(true);
+    column.collation = column_def.collation.as_ref().map(object_name);
+
+    for option in &column_def.options {
+        match &option.option {
+            ColumnOption::Null => column.nullable = true,
+            ColumnOption::NotNull => column.nullable = false,
+            ColumnOption::Default(expr) => column.default = Some(expr.to_string()),
+            ColumnOption::Unique { is_primary: true, .. } => {
+                column.nullable = false;
+                table.set_primary_key(PrimaryKey {
+                    name: option.name.as_ref().map(|name| name.value.clone()),
+                    columns: vec![column.name.clone()],
+                });
+            }
+            ColumnOption::Unique { is_primary: false, .. } => column.is_unique = true,
+            ColumnOption::ForeignKey { foreign_table, referred_columns, on_delete, on_update, characteristics } => {
+                table.add_foreign_key(ForeignKey {
+                    name: option
+                        .name
+                        .as_ref()
+                        .map(|name| name.value.clone())
+                        .unwrap_or_else(|| format!("fk_{}_{}", table.name, column.name)),
+                    columns: vec![column.name.clone()],
+                    ref_table: object_name(foreign_table),
+                    ref_columns: referred_columns.iter().map(|c| c.value.clone()).collect(),
+                    on_delete: on_delete.map(|action| action.to_string()),
+                    on_update: on_update.map(|action| action.to_string()),
+                    deferrable: deferrable(characteristics),
+                    match_type: None,
+                });
+            }
+            ColumnOption::Generated { generation_expr, .. } => {
+                column.is_generated = true;
+                column.generation_expression = generation_expr.as_ref().map(|e| e.to_string());
+            }
+            ColumnOption::Comment(comment) => column.comment = Some(comment.clone()),
+            ColumnOption::CharacterSet(charset) => column.charset = Some(object_name(charset)),
+            _ => {}
+        }
+    }
+
+    table.columns.retain(|existing| existing.name != column.name);
+    table.add_column(column);
+}
+
+/// Add a table-level constraint to a table
+fn add_constraint(table: &mut Table, constraint: TableConstraint) {
+    match constraint {
+        TableConstraint::Unique { name, columns, is_primary: true, .. } => {
+            let columns: Vec<String> = columns.into_iter().map(|c| c.value).collect();
+            for column in table.columns.iter_mut().filter(|c| columns.contains(&c.name)) {
+                column.nullable = false;
+            }
+            table.set_primary_key(PrimaryKey {
+                name: name.map(|name| name.value),
+                columns,
+            });
+        }
+        TableConstraint::Unique { name, columns, is_primary: false, .. } => {
+            let columns: Vec<String> = columns.into_iter().map(|c| c.value).collect();
+            table.add_index(Index {
+                name: name
+                    .map(|name| name.value)
+                    .unwrap_or_else(|| format!("{}_{}_key", table.name, columns.join("_"))),
+                columns,
+                is_unique: true,
+                method: None,
+                include: Vec::new(),
+                expression: None,
+                options: Default::default(),
+                tablespace: None,
+                operator_class: None,
+            });
+        }
+        TableConstraint::ForeignKey {
+            name, columns, foreign_table, referred_columns, on_delete, on_update, characteristics,
+        } => {
+            let columns: Vec<String> = columns.into_iter().map(|c| c.value).collect();
+            table.add_foreign_key(ForeignKey {
+                name: name
+                    .map(|name| name.value)
+                    .unwrap_or_else(|| format!("fk_{}_{}", table.name, columns.join("_"))),
+                columns,
+                ref_table: object_name(&foreign_table),
+                ref_columns: referred_columns.into_iter().map(|c| c.value).collect(),
+                on_delete: on_delete.map(|action| action.to_string()),
+                on_update: on_update.map(|action| action.to_string()),
+                deferrable: deferrable(&characteristics),
+                match_type: None,
+            });
+        }
+        TableConstraint::Check { name, expr } => {
+            table.constraints.push(Constraint {
+                name: name
+                    .map(|name| name.value)
+                    .unwrap_or_else(|| format!("{}_check", table.name)),
+                definition: format!("CHECK ({})", expr),
+                constraint_type: "CHECK".to_string(),
+            });
+        }
+        TableConstraint::Index { name, columns, .. } => {
+            let columns: Vec<String> = columns.into_iter().map(|c| c.value).collect();
+            table.add_index(Index {
+                name: name
+                    .map(|name| name.value)
+                    .unwrap_or_else(|| format!("{}_{}_idx", table.name, columns.join("_"))),
+                columns,
+                is_unique: false,
+                method: None,
+                include: Vec::new(),
+                expression: None,
+                options: Default::default(),
+                tablespace: None,
+                operator_class: None,
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Apply a single ALTER TABLE operation to a table
+fn alter_table(table: &mut Table, operation: AlterTableOperation) {
+    match operation {
+        AlterTableOperation::AddColumn { column_def, .. } => add_column(table, &column_def),
+        AlterTableOperation::DropColumn { column_name, .. } => {
+            table.columns.retain(|column| column.name != column_name.value);
+        }
+        AlterTableOperation::RenameColumn { old_column_name, new_column_name } => {
+            if let Some(column) = table.columns.iter_mut().find(|c| c.name == old_column_name.value) {
+                column.name = new_column_name.value;
+            }
+        }
+        AlterTableOperation::RenameTable { table_name } => table.name = object_name(&table_name),
+        AlterTableOperation::AlterColumn { column_name, op } => {
+            let column = match table.columns.iter_mut().find(|c| c.name == column_name.value) {
+                Some(column) => column,
+                None => return,
+            };
+
+            match op {
+                AlterColumnOperation::SetNotNull => column.nullable = false,
+                AlterColumnOperation::DropNotNull => column.nullable = true,
+                AlterColumnOperation::SetDefault { value } => column.default = Some(value.to_string()),
+                AlterColumnOperation::DropDefault => column.default = None,
+                AlterColumnOperation::SetDataType { data_type, .. } => {
+                    column.data_type = data_type.to_string();
+                }
+                _ => {}
+            }
+        }
+        AlterTableOperation::ChangeColumn { old_name, new_name, data_type, options } => {
+            if let Some(column) = table.columns.iter_mut().find(|c| c.name == old_name.value) {
+                column.name = new_name.value;
+                column.data_type = data_type.to_string();
+                column.nullable = !options.contains(&ColumnOption::NotNull);
+            }
+        }
+        AlterTableOperation::AddConstraint(constraint) => add_constraint(table, constraint),
+        AlterTableOperation::DropConstraint { name, .. } => {
+            let name = name.value;
+            table.foreign_keys.retain(|fk| fk.name != name);
+            table.constraints.retain(|constraint| constraint.name != name);
+            table.indexes.retain(|index| index.name != name);
+            if table.primary_key.as_ref().and_then(|pk| pk.name.as_deref()) == Some(name.as_str()) {
+                table.primary_key = None;
+            }
+        }
+        AlterTableOperation::DropPrimaryKey => table.primary_key = None,
+        _ => {}
+    }
+}
+
+/// Deferrable setting of a parsed constraint
+fn deferrable(characteristics: &Option<ConstraintCharacteristics>) -> Option<String> {
+    let characteristics = characteristics.as_ref()?;
+    match (characteristics.deferrable, characteristics.initially) {
+        (Some(true), Some(DeferrableInitial::Deferred)) => Some("initially_deferred".to_string()),
+        (Some(true), _) => Some("initially_immediate".to_string()),
+        _ => None,
+    }
+}
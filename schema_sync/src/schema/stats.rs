@@ -0,0 +1,172 @@
+//! Table statistics
+//!
+//! This module reads row counts and table sizes from the statistics each database already
+//! keeps (`pg_class`, `information_schema.TABLES`, `sqlite_stat1`) instead of counting rows,
+//! so reports can show how big the tables a change touches are. The figures are estimates
+//! and only as fresh as the database's last ANALYZE.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::db::connection::DatabaseConnection;
+use crate::error::Result;
+
+/// Estimated size of a table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableStats {
+    /// Row estimate; `None` when the database has not gathered statistics
+    pub estimated_rows: Option<i64>,
+    /// Size on disk including indexes, where the database reports it
+    pub total_bytes: Option<i64>,
+}
+
+impl fmt::Display for TableStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.estimated_rows, self.total_bytes) {
+            (Some(rows), Some(bytes)) => write!(f, "~{} rows, {}", rows.max(0), format_bytes(bytes)),
+            (Some(rows), None) => write!(f, "~{} rows", rows.max(0)),
+            (None, Some(bytes)) => write!(f, "{}", format_bytes(bytes)),
+            (None, None) => write!(f, "no statistics"),
+        }
+    }
+}
+
+/// Human-readable byte count, e.g. "1.5 GB"
+pub fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 5] = ["bytes", "kB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} bytes", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Statistics for every table in the connection's schema, keyed by table name
+pub async fn table_stats(connection: &DatabaseConnection) -> Result<HashMap<String, TableStats>> {
+    match connection {
+        #[cfg(feature = "postgres")]
+        DatabaseConnection::Postgres(pool) => {
+            // reltuples is -1 for tables never analyzed (Postgres 14+)
+            let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+                "SELECT c.relname::text, c.reltuples::bigint, pg_total_relation_size(c.oid) \
+                 FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace \
+                 WHERE c.relkind IN ('r', 'p') AND n.nspname = $1",
+            )
+            .bind(connection.get_schema().unwrap_or("public"))
+            .fetch_all(pool)
+            .await?;
+
+            Ok(rows
+                .into_iter()
+                .map(|(table, rows, bytes)| {
+                    let stats = TableStats {
+                        estimated_rows: (rows >= 0).then_some(rows),
+                        total_bytes: Some(bytes),
+                    };
+                    (table, stats)
+                })
+                .collect())
+        }
+        #[cfg(feature = "mysql")]
+        DatabaseConnection::MySql(pool) => {
+            let rows: Vec<(String, Option<i64>, Option<i64>)> = sqlx::query_as(
+                "SELECT CAST(TABLE_NAME AS CHAR), CAST(TABLE_ROWS AS SIGNED), \
+                 CAST(DATA_LENGTH + INDEX_LENGTH AS SIGNED) \
+                 FROM information_schema.TABLES \
+                 WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE'",
+            )
+            .fetch_all(pool)
+            .await?;
+
+            Ok(rows
+                .into_iter()
+                .map(|(table, rows, bytes)| (table, TableStats { estimated_rows: rows, total_bytes: bytes }))
+                .collect())
+        }
+        #[cfg(feature = "sqlite")]
+        DatabaseConnection::Sqlite(pool) => {
+            // sqlite_stat1 exists once ANALYZE has run; each row's stat starts with the row count
+            let result = sqlx::query_as("SELECT tbl, stat FROM sqlite_stat1").fetch_all(pool).await;
+            let rows: Vec<(String, String)> = match result {
+                Ok(rows) => rows,
+                Err(sqlx::Error::Database(_)) => Vec::new(),
+                Err(e) => return Err(e.into()),
+            };
+
+            let mut stats: HashMap<String, TableStats> = HashMap::new();
+            for (table, stat) in rows {
+                let count = stat.split_whitespace().next().and_then(|n| n.parse::<i64>().ok());
+                let entry = stats.entry(table).or_default();
+                entry.estimated_rows = entry.estimated_rows.max(count);
+            }
+            Ok(stats)
+        }
+        #[cfg(feature = "clickhouse")]
+        DatabaseConnection::ClickHouse(client) => {
+            // total_rows and total_bytes are NULL for engines that don't track them;
+            // JSONEachRow quotes 64-bit integers, so they are read as strings
+            #[derive(serde::Deserialize)]
+            struct Row {
+                name: String,
+                total_rows: Option<String>,
+                total_bytes: Option<String>,
+            }
+
+            let rows: Vec<Row> = client
+                .query_rows(
+                    "SELECT name, total_rows, total_bytes FROM system.tables \
+                     WHERE database = currentDatabase() AND NOT is_temporary",
+                )
+                .await?;
+
+            Ok(rows
+                .into_iter()
+                .map(|row| {
+                    let stats = TableStats {
+                        estimated_rows: row.total_rows.and_then(|n| n.parse().ok()),
+                        total_bytes: row.total_bytes.and_then(|n| n.parse().ok()),
+                    };
+                    (row.name, stats)
+                })
+                .collect())
+        }
+        #[cfg(feature = "oracle")]
+        DatabaseConnection::Oracle(client) => {
+            // num_rows is NULL until statistics are gathered; the size is the table's segments
+            #[derive(serde::Deserialize)]
+            struct Row {
+                table_name: String,
+                num_rows: Option<i64>,
+                total_bytes: Option<i64>,
+            }
+
+            let rows: Vec<Row> = client
+                .query_rows(
+                    "SELECT t.table_name, t.num_rows, \
+                     (SELECT SUM(s.bytes) FROM user_segments s \
+                      WHERE s.segment_name = t.table_name AND s.segment_type LIKE 'TABLE%') AS total_bytes \
+                     FROM user_tables t",
+                )
+                .await?;
+
+            Ok(rows
+                .into_iter()
+                .map(|row| {
+                    let stats = TableStats {
+                        estimated_rows: row.num_rows,
+                        total_bytes: row.total_bytes,
+                    };
+                    (row.table_name, stats)
+                })
+                .collect())
+        }
+    }
+}
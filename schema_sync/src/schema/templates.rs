@@ -0,0 +1,143 @@
+//! SQL templates
+//!
+//! This module rewrites generated statements through the templates configured under
+//! `[[migrations.templates]]`, for output the generator has no option for, such as storage
+//! parameters on particular tables. A template receives the statement the dialect would
+//! have produced as `{sql}`, so it usually extends the default rather than replacing it:
+//!
+//! ```toml
+//! [[migrations.templates]]
+//! statement = "create_table"
+//! tables = ["events"]
+//! template = "{sql} WITH (autovacuum_enabled = false)"
+//! ```
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::config::SqlTemplateConfig;
+use crate::error::{Error, Result};
+use crate::schema::impact::{classify, split_statements};
+
+/// Statement kinds a template can apply to
+pub const STATEMENT_KINDS: [&str; 14] = [
+    "create_table", "drop_table", "add_column", "drop_column", "alter_column", "add_constraint",
+    "drop_constraint", "alter_table", "create_index", "drop_index", "create_view", "drop_view",
+    "comment", "other",
+];
+
+/// The kinds a template for "alter_table" also covers
+const ALTER_TABLE_KINDS: [&str; 5] = ["add_column", "drop_column", "alter_column", "add_constraint", "drop_constraint"];
+
+static ALTER_ACTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^ALTER TABLE(?: IF EXISTS)?(?: ONLY)? \S+ (ADD|DROP|ALTER|MODIFY|CHANGE)(?: (\w+))?").unwrap()
+});
+static KINDS: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    [
+        (r"^CREATE (?:(?:TEMPORARY|TEMP|UNLOGGED) )?TABLE\b", "create_table"),
+        (r"^DROP TABLE\b", "drop_table"),
+        (r"^CREATE (?:UNIQUE )?INDEX\b", "create_index"),
+        (r"^DROP INDEX\b", "drop_index"),
+        (r"^CREATE (?:OR REPLACE )?(?:MATERIALIZED )?VIEW\b", "create_view"),
+        (r"^DROP (?:MATERIALIZED )?VIEW\b", "drop_view"),
+        (r"^COMMENT ON\b", "comment"),
+    ]
+    .iter()
+    .map(|(pattern, kind)| (Regex::new(&format!("(?i){}", pattern)).unwrap(), *kind))
+    .collect()
+});
+
+/// The kind of a statement, one of `STATEMENT_KINDS`
+pub fn statement_kind(sql: &str) -> &'static str {
+    let normalized = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if let Some(captures) = ALTER_ACTION.captures(&normalized) {
+        let action = captures[1].to_uppercase();
+        let object = captures.get(2).map(|m| m.as_str().to_uppercase()).unwrap_or_default();
+        return match (action.as_str(), object.as_str()) {
+            ("ADD", "COLUMN") => "add_column",
+            ("ADD", _) => "add_constraint",
+            ("DROP", "COLUMN") => "drop_column",
+            ("DROP", "CONSTRAINT" | "FOREIGN" | "PRIMARY" | "CHECK" | "INDEX" | "KEY") => "drop_constraint",
+            ("ALTER", "COLUMN") | ("MODIFY", _) | ("CHANGE", _) => "alter_column",
+            _ => "alter_table",
+        };
+    }
+    if normalized.to_uppercase().starts_with("ALTER TABLE") {
+        return "alter_table";
+    }
+
+    KINDS
+        .iter()
+        .find(|(pattern, _)| pattern.is_match(&normalized))
+        .map_or("other", |(_, kind)| kind)
+}
+
+/// Check that every template names a known statement kind
+pub fn check_templates(templates: &[SqlTemplateConfig]) -> Result<()> {
+    for template in templates {
+        if !STATEMENT_KINDS.contains(&template.statement.as_str()) {
+            return Err(Error::ConfigError(format!(
+                "unknown template statement '{}', expected one of: {}",
+                template.statement,
+                STATEMENT_KINDS.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Rewrite the statements of each migration that a template applies to
+///
+/// Migrations without a matching statement are returned exactly as generated.
+pub fn apply_templates(migrations: Vec<String>, templates: &[SqlTemplateConfig]) -> Result<Vec<String>> {
+    check_templates(templates)?;
+
+    Ok(migrations
+        .into_iter()
+        .map(|migration| {
+            let statements = split_statements(&migration);
+            let rendered: Vec<Option<String>> = statements.iter().map(|sql| render(sql, templates)).collect();
+            if rendered.iter().all(Option::is_none) {
+                return migration;
+            }
+
+            statements
+                .iter()
+                .zip(rendered)
+                .map(|(sql, rendered)| rendered.unwrap_or_else(|| sql.clone()) + "\n")
+                .collect()
+        })
+        .collect())
+}
+
+/// A statement through the first template that applies to it, if any
+fn render(sql: &str, templates: &[SqlTemplateConfig]) -> Option<String> {
+    let kind = statement_kind(sql);
+    let table = classify(sql).table.map(|table| table.rsplit('.').next().unwrap_or(&table).to_string());
+
+    let template = templates.iter().find(|template| {
+        let kind_matches = template.statement == kind
+            || (template.statement == "alter_table" && ALTER_TABLE_KINDS.contains(&kind));
+        let table_matches = match (&template.tables, &table) {
+            (None, _) => true,
+            (Some(tables), Some(table)) => tables.iter().any(|t| t == table),
+            (Some(_), None) => false,
+        };
+        kind_matches && table_matches
+    })?;
+
+    let statement = sql.trim().trim_end_matches(';').trim_end();
+    let rendered = template
+        .template
+        .replace("{table}", table.as_deref().unwrap_or_default())
+        .replace("{sql}", statement);
+    let rendered = rendered.trim_end();
+
+    // Templates that end the statement themselves (e.g. "{sql}; -- note") get no extra `;`
+    Some(if template.template.contains(';') {
+        rendered.to_string()
+    } else {
+        format!("{};", rendered)
+    })
+}
@@ -1,7 +1,9 @@
 //! Type definitions for database schema objects
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::schema::dialect::{normalize_expression, Dialect};
 
 /// Represents a complete database schema
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +11,15 @@ pub struct DatabaseSchema {
     pub tables: HashMap<String, Table>,
     pub views: HashMap<String, View>,
     pub schema_name: Option<String>,
+    /// Schema version tag this snapshot corresponds to, if known
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Stored functions and procedures (introspected on Postgres only)
+    #[serde(default)]
+    pub functions: Vec<Function>,
+    /// Postgres enum types by name, with their labels in order
+    #[serde(default)]
+    pub enum_types: BTreeMap<String, Vec<String>>,
 }
 
 impl DatabaseSchema {
@@ -18,9 +29,17 @@ impl DatabaseSchema {
             tables: HashMap::new(),
             views: HashMap::new(),
             schema_name,
+            version: None,
+            functions: Vec::new(),
+            enum_types: BTreeMap::new(),
         }
     }
     
+    /// Dependencies between the schema's tables, columns and views
+    pub fn dependency_graph(&self) -> crate::schema::graph::DependencyGraph {
+        crate::schema::graph::DependencyGraph::new(self)
+    }
+    
     /// Add a table to the schema
     pub fn add_table(&mut self, table: Table) {
         self.tables.insert(table.name.clone(), table);
@@ -30,6 +49,110 @@ impl DatabaseSchema {
     pub fn add_view(&mut self, view: View) {
         self.views.insert(view.name.clone(), view);
     }
+    
+    /// Compact hash of the tables, independent of map and declaration order
+    ///
+    /// Indexes, foreign keys, and constraints are sorted by name before hashing, so two
+    /// schemas describing the same tables always produce the same fingerprint.
+    pub fn fingerprint(&self) -> String {
+        let tables: BTreeMap<&String, Table> = self.tables.iter()
+            .map(|(name, table)| (name, table.normalized()))
+            .collect();
+        let normalized = serde_json::to_string(&tables).unwrap_or_default();
+        
+        format!("{:x}", md5::compute(normalized.as_bytes()))
+    }
+    
+    /// Fingerprint of each table on its own, keyed by table name
+    ///
+    /// Lets a caller tell which tables changed, where `fingerprint` only says that one did.
+    pub fn table_fingerprints(&self) -> BTreeMap<String, String> {
+        self.tables.iter()
+            .map(|(name, table)| {
+                let normalized = serde_json::to_string(&table.normalized()).unwrap_or_default();
+                (name.clone(), format!("{:x}", md5::compute(normalized.as_bytes())))
+            })
+            .collect()
+    }
+    
+    /// Respell table and column names the way `current` stores them, wherever the
+    /// database would treat the two spellings as the same identifier
+    ///
+    /// Without this a model table `UserProfiles` and the `userprofiles` Postgres created
+    /// for it diff as a create plus a drop. Keys, indexes, and foreign keys that refer
+    /// to a respelled name are updated with it.
+    pub fn align_identifiers(&mut self, current: &DatabaseSchema, dialect: Dialect) {
+        // Work out every new spelling first, so references across tables can follow
+        let mut table_names = HashMap::new();
+        let mut column_names: HashMap<String, HashMap<String, String>> = HashMap::new();
+        for (name, table) in &self.tables {
+            let table_name = if current.tables.contains_key(name) {
+                name.clone()
+            } else {
+                stored_spelling(dialect, name, current.tables.keys()).unwrap_or_else(|| name.clone())
+            };
+            let Some(existing) = current.tables.get(&table_name) else { continue };
+            
+            let columns: HashMap<String, String> = table.columns.iter()
+                .filter(|column| !existing.columns.iter().any(|c| c.name == column.name))
+                .filter_map(|column| {
+                    let stored = stored_spelling(dialect, &column.name, existing.columns.iter().map(|c| &c.name))?;
+                    Some((column.name.clone(), stored))
+                })
+                .collect();
+            if table_name != *name {
+                table_names.insert(name.clone(), table_name.clone());
+            }
+            if !columns.is_empty() {
+                column_names.insert(name.clone(), columns);
+            }
+        }
+        
+        let rename = |columns: &mut Vec<String>, renames: Option<&HashMap<String, String>>| {
+            for column in columns.iter_mut() {
+                if let Some(new_name) = renames.and_then(|renames| renames.get(column)) {
+                    *column = new_name.clone();
+                }
+            }
+        };
+        
+        let tables = std::mem::take(&mut self.tables);
+        for (name, mut table) in tables {
+            let renames = column_names.get(&name);
+            for column in &mut table.columns {
+                if let Some(new_name) = renames.and_then(|renames| renames.get(&column.name)) {
+                    column.name = new_name.clone();
+                }
+            }
+            if let Some(pk) = &mut table.primary_key {
+                rename(&mut pk.columns, renames);
+            }
+            for index in &mut table.indexes {
+                rename(&mut index.columns, renames);
+                rename(&mut index.include, renames);
+            }
+            for fk in &mut table.foreign_keys {
+                rename(&mut fk.columns, renames);
+                rename(&mut fk.ref_columns, column_names.get(&fk.ref_table));
+                if let Some(new_name) = table_names.get(&fk.ref_table) {
+                    fk.ref_table = new_name.clone();
+                }
+            }
+            if let Some(new_name) = table_names.get(&name) {
+                table.name = new_name.clone();
+            }
+            self.add_table(table);
+        }
+    }
+}
+
+/// The one name in `stored` the database treats as `declared`, when it is spelled differently
+fn stored_spelling<'a>(dialect: Dialect, declared: &str, stored: impl Iterator<Item = &'a String>) -> Option<String> {
+    let candidates: Vec<&String> = stored.filter(|name| dialect.same_identifier(declared, name)).collect();
+    match candidates.as_slice() {
+        [name] if name.as_str() != declared => Some(name.to_string()),
+        _ => None,
+    }
 }
 
 /// Represents a database table
@@ -42,6 +165,16 @@ pub struct Table {
     pub foreign_keys: Vec<ForeignKey>,
     pub constraints: Vec<Constraint>,
     pub comment: Option<String>,
+    /// Tablespace the table is stored in; `None` is the database default
+    #[serde(default)]
+    pub tablespace: Option<String>,
+    /// Literal statements run after the table is created, from the model's `extra_sql` hooks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_sql: Vec<String>,
+    /// Storage options such as `unlogged` (Postgres), `engine` (MySQL), or `strict` (SQLite);
+    /// each dialect renders the ones it supports when creating the table
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub options: BTreeMap<String, String>,
 }
 
 impl Table {
@@ -55,7 +188,18 @@ impl Table {
             foreign_keys: Vec::new(),
             constraints: Vec::new(),
             comment: None,
+            tablespace: None,
+            extra_sql: Vec::new(),
+            options: BTreeMap::new(),
+        }
+    }
+    
+    /// Checksum of the table's extra SQL, recorded when it is applied; `None` without any
+    pub fn extra_sql_checksum(&self) -> Option<String> {
+        if self.extra_sql.is_empty() {
+            return None;
         }
+        Some(format!("{:x}", md5::compute(self.extra_sql.join("\n").as_bytes())))
     }
     
     /// Add a column to the table
@@ -77,6 +221,15 @@ impl Table {
     pub fn add_foreign_key(&mut self, fk: ForeignKey) {
         self.foreign_keys.push(fk);
     }
+    
+    /// A copy with indexes, foreign keys, and constraints sorted by name, for fingerprinting
+    fn normalized(&self) -> Table {
+        let mut table = self.clone();
+        table.indexes.sort_by(|a, b| a.name.cmp(&b.name));
+        table.foreign_keys.sort_by(|a, b| a.name.cmp(&b.name));
+        table.constraints.sort_by(|a, b| a.name.cmp(&b.name));
+        table
+    }
 }
 
 /// Represents a database column
@@ -90,6 +243,21 @@ pub struct Column {
     pub is_unique: bool,
     pub is_generated: bool,
     pub generation_expression: Option<String>,
+    /// Character set of a text column (MySQL), e.g. "ascii"; `None` uses the table's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charset: Option<String>,
+    /// Rust function the application fills the column with, e.g. "Utc::now"; metadata for
+    /// generated code, never part of the database schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_fn: Option<String>,
+    /// How Postgres stores large values of the column: "plain", "main", "external", or
+    /// "extended" (compressed and moved out of line, the default for `BYTEA` and `TEXT`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<String>,
+    /// Collation of a text column, e.g. "utf8mb4_unicode_ci" (MySQL) or "NOCASE" (SQLite);
+    /// `None` uses the table's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collation: Option<String>,
 }
 
 impl Column {
@@ -104,6 +272,10 @@ impl Column {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         }
     }
     
@@ -134,8 +306,57 @@ pub struct Index {
     pub columns: Vec<String>,
     pub is_unique: bool,
     pub method: Option<String>,
+    /// Non-key columns stored in the index (`INCLUDE`, Postgres)
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Indexed expression such as `lower(email)`, after any plain columns
+    #[serde(default)]
+    pub expression: Option<String>,
+    /// Storage options such as `fillfactor`, `fastupdate` (Postgres), or `key_block_size` (MySQL)
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+    /// Tablespace the index is stored in; `None` is the database default
+    #[serde(default)]
+    pub tablespace: Option<String>,
+    /// Operator class for the index keys, such as `jsonb_path_ops` (Postgres); `None` is the type's default
+    #[serde(default)]
+    pub operator_class: Option<String>,
 }
 
+impl Index {
+    /// Check whether this existing index serves as `target`, whatever their names
+    ///
+    /// Storage options, the tablespace, and the operator class only count when `target` sets them, so tuning done
+    /// directly in the database (a DBA's fillfactor, say) does not make the index look changed.
+    pub fn satisfies(&self, target: &Index) -> bool {
+        let method = |index: &Index| index.method.as_deref().unwrap_or("btree").to_lowercase();
+        let expression = |index: &Index| index.expression.as_deref().map(normalize_expression);
+        let options_match = target.options.iter().all(|(key, value)| {
+            self.options.get(key).map(|current| normalize_option_value(current))
+                == Some(normalize_option_value(value))
+        });
+        
+        self.columns == target.columns
+            && self.is_unique == target.is_unique
+            && self.include == target.include
+            && method(self) == method(target)
+            && expression(self) == expression(target)
+            && options_match
+            && (target.tablespace.is_none() || self.tablespace == target.tablespace)
+            && (target.operator_class.is_none() || self.operator_class == target.operator_class)
+    }
+}
+
+/// Normalize an index option value, so `off`, `false`, and `FALSE` compare equal
+fn normalize_option_value(value: &str) -> String {
+    match value.trim().to_lowercase().as_str() {
+        "on" | "true" | "yes" | "1" => "true".to_string(),
+        "off" | "false" | "no" | "0" => "false".to_string(),
+        other => other.to_string(),
+    }
+}
+
+
 /// Represents a foreign key constraint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForeignKey {
@@ -145,6 +366,12 @@ pub struct ForeignKey {
     pub ref_columns: Vec<String>,
     pub on_delete: Option<String>,
     pub on_update: Option<String>,
+    /// "initially_immediate" or "initially_deferred"; `None` for a non-deferrable key
+    #[serde(default)]
+    pub deferrable: Option<String>,
+    /// "full" or "partial"; `None` for the default MATCH SIMPLE
+    #[serde(default)]
+    pub match_type: Option<String>,
 }
 
 /// Represents a general constraint
@@ -155,6 +382,48 @@ pub struct Constraint {
     pub constraint_type: String,
 }
 
+impl Constraint {
+    /// Create an `EXCLUDE USING <method> (<elements>)` constraint (Postgres)
+    pub fn exclusion(name: impl Into<String>, using: &str, elements: &str) -> Self {
+        Self {
+            name: name.into(),
+            definition: format!("EXCLUDE USING {} ({})", using.trim().to_lowercase(), elements.trim()),
+            constraint_type: "EXCLUDE".to_string(),
+        }
+    }
+    
+    /// Create a `CHECK (<expression>)` constraint
+    pub fn check(name: impl Into<String>, expression: &str) -> Self {
+        Self {
+            name: name.into(),
+            definition: format!("CHECK ({})", expression.trim()),
+            constraint_type: "CHECK".to_string(),
+        }
+    }
+    
+    /// Check whether two constraints have the same type and definition,
+    /// ignoring case and whitespace (`pg_get_constraintdef` spaces elements its own way)
+    ///
+    /// CHECK expressions also ignore the parentheses and casts Postgres adds when it
+    /// reports them, e.g. `CHECK ((char_length((name)::text) >= 3))`.
+    pub fn same_definition(&self, other: &Constraint) -> bool {
+        if self.constraint_type == "CHECK" && other.constraint_type == "CHECK" {
+            return normalize_expression(&self.definition) == normalize_expression(&other.definition);
+        }
+        
+        let normalize = |definition: &str| {
+            definition
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>()
+                .to_lowercase()
+        };
+        
+        self.constraint_type == other.constraint_type
+            && normalize(&self.definition) == normalize(&other.definition)
+    }
+}
+
 /// Represents a database view
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct View {
@@ -162,6 +431,24 @@ pub struct View {
     pub definition: String,
     pub columns: Vec<Column>,
     pub is_materialized: bool,
+    /// Columns this view reads, as `relation.column`; the relation may itself be a view
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Whether a unique index on plain columns exists, which
+    /// `REFRESH MATERIALIZED VIEW CONCURRENTLY` requires
+    #[serde(default)]
+    pub has_unique_index: bool,
+    /// Comment set with `COMMENT ON VIEW` (Postgres)
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// A stored function or procedure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Function {
+    pub name: String,
+    /// Full `CREATE FUNCTION` statement
+    pub definition: String,
 }
 
 /// Represents a field definition from a Rust model
@@ -186,4 +473,26 @@ pub struct ForeignKeyDefinition {
     pub ref_column: String,
     pub on_delete: Option<String>,
     pub on_update: Option<String>,
-}
\ No newline at end of file
+    #[serde(default)]
+    pub deferrable: Option<String>,
+    #[serde(default)]
+    pub match_type: Option<String>,
+}
+
+/// Canonical form of a `deferrable` setting: "initially_immediate" or "initially_deferred"
+pub fn normalize_deferrable(value: &str) -> Option<String> {
+    match value.to_lowercase().replace([' ', '-'], "_").as_str() {
+        "deferrable" | "initially_immediate" => Some("initially_immediate".to_string()),
+        "initially_deferred" | "deferred" => Some("initially_deferred".to_string()),
+        _ => None,
+    }
+}
+
+/// Canonical SQL spelling of a referential action ("cascade" becomes "CASCADE", "set_null" becomes "SET NULL")
+pub fn normalize_referential_action(value: &str) -> Option<String> {
+    let action = value.trim().to_uppercase().replace(['_', '-'], " ");
+    match action.as_str() {
+        "CASCADE" | "RESTRICT" | "SET NULL" | "SET DEFAULT" | "NO ACTION" => Some(action),
+        _ => None,
+    }
+}
@@ -0,0 +1,131 @@
+//! Generated SQL validation
+//!
+//! This module parses each generated statement with `sqlparser`, in the dialect of the
+//! target database, so a malformed statement is reported when migrations are generated
+//! (including dry runs) rather than when the database rejects it. A statement that does not
+//! parse points at a bug in the generator, not in the models.
+//!
+//! `sqlparser` does not know every clause the generator emits, so those clauses are removed
+//! before parsing, and the few statements it cannot parse at all are skipped.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sqlparser::ast::Expr;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Token;
+
+use crate::error::{MigrationFailure, Result};
+use crate::schema::impact::{classify, split_statements};
+use crate::schema::snapshot::parser_dialect;
+
+/// Statements the parser cannot read at all
+static UNPARSEABLE: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        r"^REFRESH\s+MATERIALIZED\s+VIEW\b",
+        r"^CREATE\s+TYPE\s+\S+\s+AS\s+ENUM\b",
+        r"^PREPARE\b",
+        r"^PRAGMA\b",
+        r"^DROP\s+INDEX\s+\S+\s+ON\b",
+        r"\bEXCLUDE\s+USING\b",
+        r"\bVALIDATE\s+CONSTRAINT\b",
+        r"\bSET\s+TABLESPACE\b",
+        r"\bDROP\s+FOREIGN\s+KEY\b",
+        r"^ALTER\s+TABLE\s+\S+\s+(?:COMMENT|TABLESPACE)\b",
+        r"^CREATE\s+(?:OR\s+REPLACE\s+)?TRIGGER\b",
+        r"^ALTER\s+TABLE\s+\S+\s+(?:ADD|MODIFY|DROP)\s*\(",
+        r"^ALTER\s+TABLE\s+\S+\s+MOVE\b",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(&format!("(?is){}", pattern)).unwrap())
+    .collect()
+});
+
+/// Clauses the parser does not support, with what to parse in their place
+static UNSUPPORTED_CLAUSES: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    [
+        (r"\s+NOT\s+VALID\b", ""),
+        (r"\bCONCURRENTLY\s+", ""),
+        (r"\s+TABLESPACE\s+[\w`\x22.]+", ""),
+        (r"\s+WITH\s*\([^()]*\)", ""),
+        (r"\s+DEFAULT\s+CHARACTER\s+SET\s*=\s*\w+", ""),
+        (r"\s+COLLATE\s*=\s*\w+", ""),
+        (r"\)\s*USING\s+(?:BTREE|HASH)\b", ")"),
+        (r"^DROP\s+MATERIALIZED\s+VIEW\b", "DROP VIEW"),
+        (r"\bMODIFY\s+COLUMN\s+([\w`\x22]+)", "CHANGE COLUMN $1 $1"),
+    ]
+    .iter()
+    .map(|(pattern, replacement)| (Regex::new(&format!("(?is){}", pattern)).unwrap(), *replacement))
+    .collect()
+});
+
+/// Check that every statement in `migrations` parses as SQL for `driver`
+///
+/// Fails with an "invalid_sql" `MigrationFailure` naming the first statement that does not.
+pub fn validate_sql(migrations: &[String], driver: &str) -> Result<()> {
+    let dialect = parser_dialect(driver);
+
+    for (i, migration) in migrations.iter().enumerate() {
+        for statement in split_statements(migration) {
+            if UNPARSEABLE.iter().any(|pattern| pattern.is_match(&statement)) {
+                tracing::debug!(statement = %statement, "Skipping validation of a statement sqlparser cannot read");
+                continue;
+            }
+
+            let parseable = UNSUPPORTED_CLAUSES
+                .iter()
+                .fold(statement.clone(), |sql, (pattern, replacement)| pattern.replace_all(&sql, *replacement).into_owned());
+
+            if let Err(e) = Parser::parse_sql(dialect.as_ref(), &parseable) {
+                let mut failure = MigrationFailure::new(
+                    "invalid_sql",
+                    format!(
+                        "Generated SQL in migration {} does not parse as {} SQL ({}): {}\n\
+                         This is a bug in the SQL generator; please report it along with the statement.",
+                        i + 1,
+                        driver,
+                        e,
+                        statement
+                    ),
+                )
+                .statement(&statement);
+                if let Some(table) = classify(&statement).table {
+                    failure = failure.table(&table);
+                }
+                return Err(failure.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Words that read as identifiers but are valid column defaults
+const DEFAULT_KEYWORDS: [&str; 8] = [
+    "CURRENT_TIMESTAMP", "CURRENT_DATE", "CURRENT_TIME", "LOCALTIME", "LOCALTIMESTAMP",
+    "CURRENT_USER", "SESSION_USER", "USER",
+];
+
+/// Why `default` is not a valid column default for `driver`, or `None` if it is
+///
+/// The default must parse as a single literal or expression. A bare word such as `active`
+/// parses, but as a column reference, which no database accepts in a default; it was almost
+/// always meant as the string `'active'`.
+pub fn default_problem(default: &str, driver: &str) -> Option<String> {
+    let dialect = parser_dialect(driver);
+    let mut parser = match Parser::new(dialect.as_ref()).try_with_sql(default) {
+        Ok(parser) => parser,
+        Err(e) => return Some(format!("does not parse as {} SQL ({})", driver, e)),
+    };
+
+    match parser.parse_expr() {
+        Err(e) => Some(format!("does not parse as {} SQL ({})", driver, e)),
+        Ok(_) if parser.peek_token().token != Token::EOF => {
+            Some(format!("is not a single {} expression", driver))
+        }
+        Ok(Expr::Identifier(ident)) if !DEFAULT_KEYWORDS.contains(&ident.value.to_uppercase().as_str()) => {
+            Some(format!("is a column reference; write '{}' if it is a string", ident.value))
+        }
+        Ok(Expr::CompoundIdentifier(_)) => Some("is a column reference".to_string()),
+        Ok(_) => None,
+    }
+}
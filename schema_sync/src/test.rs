@@ -201,6 +201,10 @@ mod tests {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         users_table.add_column(Column {
             name: "name".to_string(),
@@ -211,6 +215,10 @@ mod tests {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         users_table.set_primary_key(PrimaryKey {
             name: Some("pk_users".to_string()),
@@ -232,6 +240,10 @@ mod tests {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         users_table.add_column(Column {
             name: "name".to_string(),
@@ -242,6 +254,10 @@ mod tests {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         // New column
         users_table.add_column(Column {
@@ -253,6 +269,10 @@ mod tests {
             is_unique: true,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         users_table.set_primary_key(PrimaryKey {
             name: Some("pk_users".to_string()),
@@ -272,6 +292,10 @@ mod tests {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         posts_table.add_column(Column {
             name: "title".to_string(),
@@ -282,6 +306,10 @@ mod tests {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         posts_table.add_column(Column {
             name: "user_id".to_string(),
@@ -292,6 +320,10 @@ mod tests {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         posts_table.set_primary_key(PrimaryKey {
             name: Some("pk_posts".to_string()),
@@ -352,6 +384,10 @@ mod tests {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         users_table.add_column(Column {
             name: "name".to_string(),
@@ -362,6 +398,10 @@ mod tests {
             is_unique: false,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         });
         users_table.set_primary_key(PrimaryKey {
             name: Some("pk_users".to_string()),
@@ -380,6 +420,10 @@ mod tests {
             is_unique: true,
             is_generated: false,
             generation_expression: None,
+            charset: None,
+            default_fn: None,
+            storage: None,
+            collation: None,
         };
         
         diff.columns_to_add.insert("users".to_string(), vec![email_column]);
@@ -8,5 +8,6 @@ pub mod logging;
 // Re-export key utility functions
 pub use naming::{
     apply_naming_convention, format_name, get_table_name, 
-    get_column_name, get_index_name, get_foreign_key_name,
+    get_column_name, get_index_name, get_foreign_key_name, get_constraint_name,
+    render_name, NameParts,
 };  
\ No newline at end of file
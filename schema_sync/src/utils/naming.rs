@@ -5,8 +5,32 @@
 use inflector::Inflector;
 use std::collections::HashMap;
 
+use unicode_ident::{is_xid_continue, is_xid_start};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::config::PluralizationConfig;
+use crate::schema::dialect::Dialect;
+
 /// Apply a naming convention to a string
 pub fn apply_naming_convention(name: &str, convention: &str) -> String {
+    // The inflector splits words at every non-ASCII letter
+    if !name.is_ascii() {
+        let words = split_into_words(name);
+        let capitalized = || words.iter().map(|word| capitalize(word)).collect::<Vec<_>>();
+        match convention {
+            "snake_case" => return words.join("_"),
+            "kebab_case" => return words.join("-"),
+            "screaming_snake_case" => return words.join("_").to_uppercase(),
+            "pascal_case" => return capitalized().concat(),
+            "camel_case" => {
+                let rest = capitalized().into_iter().skip(1).collect::<String>();
+                return format!("{}{}", words.first().map(String::as_str).unwrap_or(""), rest);
+            }
+            _ => {}
+        }
+    }
+    
     match convention {
         "snake_case" => name.to_snake_case(),
         "camel_case" => name.to_camel_case(),
@@ -39,18 +63,7 @@ pub fn get_table_name(
     let name = apply_naming_convention(model_name, style);
     
     if pluralize {
-        // Handle special pluralization cases that the inflector might not handle correctly
-        match name.to_lowercase().as_str() {
-            "person" => "people".to_string(),
-            "child" => "children".to_string(),
-            "man" => "men".to_string(),
-            "woman" => "women".to_string(),
-            "foot" => "feet".to_string(),
-            "tooth" => "teeth".to_string(),
-            "goose" => "geese".to_string(),
-            "mouse" => "mice".to_string(),
-            _ => name.to_plural()
-        }
+        self::pluralize(&name)
     } else {
         name
     }
@@ -103,19 +116,114 @@ pub fn get_constraint_name(
     ])
 }
 
+/// Values substituted into an index or constraint name pattern
+pub struct NameParts<'a> {
+    pub table: &'a str,
+    pub columns: &'a [String],
+    /// Short kind of object: "pk", "uq", "ix", "fk", or "excl"
+    pub kind: &'a str,
+    pub schema: Option<&'a str>,
+}
+
+/// Render an index or constraint name pattern, shortened to `max_length` if needed
+///
+/// Placeholders are `{table}`, `{columns}` (joined with `_`, also available as `{column}`),
+/// `{type}`, `{schema}`, and `{hash}`, the first 8 hex digits of an MD5 of the table, columns,
+/// and type. A name over the limit keeps its start and ends in a hash of the full name, so
+/// it stays unique. Separators left dangling by a missing schema are dropped.
+pub fn render_name(pattern: &str, parts: &NameParts, max_length: usize) -> String {
+    let columns = parts.columns.join("_");
+    let hash = format!("{:x}", md5::compute(format!("{}|{}|{}", parts.table, columns, parts.kind)));
+    
+    let name = format_name(pattern, &[
+        ("table", parts.table),
+        ("columns", &columns),
+        ("column", &columns),
+        ("type", parts.kind),
+        ("schema", parts.schema.unwrap_or("")),
+        ("hash", &hash[..8]),
+    ]);
+    let name = name.trim_matches(|c| c == '_' || c == '.');
+    
+    truncate_identifier(name, max_length)
+}
+
 /// Sanitize identifiers for SQL
 pub fn sanitize_identifier(name: &str) -> String {
-    // Remove or replace characters not allowed in SQL identifiers
-    let mut sanitized = name.replace(|c: char| !c.is_alphanumeric() && c != '_', "_");
+    sanitize_identifier_for(name, None, false)
+}
+
+/// Sanitize an identifier for a dialect, keeping letters from any script
+///
+/// The name is NFC-normalized first, so names that look the same always produce the same
+/// identifier. Characters the dialect does not accept in an unquoted identifier become
+/// underscores: Postgres and SQLite take Unicode letters and digits, MySQL only those in
+/// the Basic Multilingual Plane. With `transliterate`, letters are spelled in ASCII instead
+/// (`Bücher` becomes `Bucher`, `Заказы` becomes `Zakazy`).
+pub fn sanitize_identifier_for(name: &str, dialect: Option<Dialect>, transliterate: bool) -> String {
+    let name: String = if transliterate {
+        transliterate_identifier(name)
+    } else {
+        name.nfc().collect()
+    };
+    
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            let allowed = c == '_'
+                || c.is_ascii_alphanumeric()
+                || (is_xid_continue(c) && !(dialect == Some(Dialect::MySql) && c > '\u{FFFF}'));
+            if allowed { c } else { '_' }
+        })
+        .collect();
     
-    // Ensure identifier doesn't start with a number
-    if sanitized.chars().next().map_or(false, |c| c.is_numeric()) {
+    // Ensure identifier doesn't start with a digit or combining mark
+    if sanitized.chars().next().is_some_and(|c| c != '_' && !is_xid_start(c)) {
         sanitized = format!("_{}", sanitized);
     }
     
     sanitized
 }
 
+/// Spell an identifier in ASCII: accents are dropped, and Cyrillic, Greek, and a few
+/// other letters are transliterated. Anything else is left for sanitizing.
+pub fn transliterate_identifier(name: &str) -> String {
+    let mut ascii = String::new();
+    
+    for c in name.nfkd().filter(|c| !is_combining_mark(*c)) {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        match TRANSLITERATIONS.iter().find(|(letter, _)| *letter == lower) {
+            Some((_, spelling)) if c != lower => {
+                // Keep the case of the first letter, e.g. Ж becomes Zh
+                let mut letters = spelling.chars();
+                ascii.extend(letters.next().map(|first| first.to_ascii_uppercase()));
+                ascii.push_str(letters.as_str());
+            }
+            Some((_, spelling)) => ascii.push_str(spelling),
+            None => ascii.push(c),
+        }
+    }
+    
+    ascii
+}
+
+/// ASCII spellings of lowercase letters that have no decomposition
+const TRANSLITERATIONS: &[(char, &str)] = &[
+    // Latin
+    ('ß', "ss"), ('æ', "ae"), ('œ', "oe"), ('ø', "o"), ('ł', "l"), ('đ', "d"), ('ð', "d"), ('þ', "th"), ('ı', "i"),
+    // Cyrillic
+    ('а', "a"), ('б', "b"), ('в', "v"), ('г', "g"), ('д', "d"), ('е', "e"), ('ж', "zh"), ('з', "z"),
+    ('и', "i"), ('й', "y"), ('к', "k"), ('л', "l"), ('м', "m"), ('н', "n"), ('о', "o"), ('п', "p"),
+    ('р', "r"), ('с', "s"), ('т', "t"), ('у', "u"), ('ф', "f"), ('х', "kh"), ('ц', "ts"), ('ч', "ch"),
+    ('ш', "sh"), ('щ', "shch"), ('ъ', ""), ('ы', "y"), ('ь', ""), ('э', "e"), ('ю', "yu"), ('я', "ya"),
+    ('є', "ye"), ('і', "i"), ('ї', "yi"), ('ґ', "g"),
+    // Greek
+    ('α', "a"), ('β', "v"), ('γ', "g"), ('δ', "d"), ('ε', "e"), ('ζ', "z"), ('η', "i"), ('θ', "th"),
+    ('ι', "i"), ('κ', "k"), ('λ', "l"), ('μ', "m"), ('ν', "n"), ('ξ', "x"), ('ο', "o"), ('π', "p"),
+    ('ρ', "r"), ('σ', "s"), ('ς', "s"), ('τ', "t"), ('υ', "y"), ('φ', "f"), ('χ', "ch"), ('ψ', "ps"),
+    ('ω', "o"),
+];
+
 /// Check for name conflicts in a list of identifiers
 pub fn check_identifier_conflicts(
     names: &[String],
@@ -150,12 +258,12 @@ pub fn truncate_identifier(name: &str, max_length: usize) -> String {
         // Generate hash of the full name for uniqueness
         let hash = format!("{:x}", md5::compute(name.as_bytes()));
         
-        // Make sure we don't try to slice beyond the string length
-        let prefix = if keep_length < name.len() {
-            &name[0..keep_length]
-        } else {
-            name
-        };
+        // Make sure we don't slice beyond the string or through a multi-byte character
+        let mut keep_length = keep_length.min(name.len());
+        while !name.is_char_boundary(keep_length) {
+            keep_length -= 1;
+        }
+        let prefix = &name[0..keep_length];
         
         format!("{}_{}", prefix, &hash[0..8])
     }
@@ -174,36 +282,75 @@ pub fn get_max_identifier_length(db_type: &str) -> usize {
     }
 }
 
+/// Singular and plural forms the inflector gets wrong
+const IRREGULAR_PLURALS: [(&str, &str); 8] = [
+    ("person", "people"),
+    ("child", "children"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("foot", "feet"),
+    ("tooth", "teeth"),
+    ("goose", "geese"),
+    ("mouse", "mice"),
+];
+
 /// Convert a singular name to plural
 pub fn pluralize(name: &str) -> String {
-    // Handle special cases first
-    match name.to_lowercase().as_str() {
-        "person" => "people".to_string(),
-        "child" => "children".to_string(),
-        "man" => "men".to_string(),
-        "woman" => "women".to_string(),
-        "foot" => "feet".to_string(),
-        "tooth" => "teeth".to_string(),
-        "goose" => "geese".to_string(),
-        "mouse" => "mice".to_string(),
-        _ => name.to_plural()
-    }
+    pluralize_with(name, None)
 }
 
 /// Convert a plural name to singular
 pub fn singularize(name: &str) -> String {
-    // Handle special cases first
-    match name.to_lowercase().as_str() {
-        "people" => "person".to_string(),
-        "children" => "child".to_string(),
-        "men" => "man".to_string(),
-        "women" => "woman".to_string(),
-        "feet" => "foot".to_string(),
-        "teeth" => "tooth".to_string(),
-        "geese" => "goose".to_string(),
-        "mice" => "mouse".to_string(),
-        _ => name.to_singular()
+    singularize_with(name, None)
+}
+
+/// Convert a singular name to plural, checking the `[naming.pluralization]` rules first
+///
+/// Irregular and uncountable words are matched against the last word of a snake_case
+/// name, so `user_person` becomes `user_people` and `user_metadata` stays as it is.
+pub fn pluralize_with(name: &str, rules: Option<&PluralizationConfig>) -> String {
+    inflect(name, rules, |(singular, plural)| (singular, plural), Inflector::to_plural)
+}
+
+/// Convert a plural name to singular, using the same rules as `pluralize_with`
+pub fn singularize_with(name: &str, rules: Option<&PluralizationConfig>) -> String {
+    inflect(name, rules, |(singular, plural)| (plural, singular), Inflector::to_singular)
+}
+
+/// Replace the last word of `name` from the irregular pairs (oriented by `direction`),
+/// keep it for uncountable words, and otherwise fall back to the inflector
+fn inflect(
+    name: &str,
+    rules: Option<&PluralizationConfig>,
+    direction: fn((String, String)) -> (String, String),
+    fallback: fn(&str) -> String,
+) -> String {
+    let (prefix, word) = match name.rfind('_') {
+        Some(i) => name.split_at(i + 1),
+        None => ("", name),
+    };
+    let word = word.to_lowercase();
+    
+    let uncountable = rules.map_or(&[][..], |rules| rules.uncountable.as_slice());
+    if uncountable.iter().any(|u| u.to_lowercase() == word) {
+        return name.to_string();
     }
+    
+    // Configured words take precedence over the built-in ones
+    let configured = rules.into_iter().flat_map(|rules| {
+        rules.irregular.iter().map(|(singular, plural)| (singular.to_lowercase(), plural.to_lowercase()))
+    });
+    let built_in = IRREGULAR_PLURALS.iter().map(|(singular, plural)| (singular.to_string(), plural.to_string()));
+    for (from, to) in configured.chain(built_in).map(direction) {
+        if from == word {
+            return format!("{}{}", prefix, to);
+        }
+        if to == word {
+            return name.to_string();
+        }
+    }
+    
+    fallback(name)
 }
 
 /// Generate a unique name with a suffix if name exists in the list
@@ -304,6 +451,15 @@ pub fn split_into_words(name: &str) -> Vec<String> {
     words.iter().map(|w| w.to_lowercase()).collect()
 }
 
+/// Uppercase the first letter of a word
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
 /// Check if a name is a reserved SQL keyword
 pub fn is_sql_keyword(name: &str) -> bool {
     // Common SQL keywords across databases
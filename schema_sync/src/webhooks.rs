@@ -0,0 +1,92 @@
+//! Schema change notifications
+//!
+//! This module posts the outcome of applying a schema diff to the webhooks configured
+//! under `[hooks]`: `on_schema_change` after a successful apply, and `on_error` (or
+//! `on_schema_change` when that is unset) when applying fails. Payloads are plain JSON,
+//! or Slack incoming-webhook messages with `webhook_format = "slack"`. Sending requires
+//! the `webhooks` Cargo feature; a failed notification is logged and never fails the sync.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::config::HooksConfig;
+
+/// Outcome of applying a schema diff, as sent to a webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaChangeEvent {
+    /// "applied" or "failed"
+    pub status: String,
+    /// One line per change in the diff, e.g. "missing column users.email"
+    pub changes: Vec<String>,
+    /// IDs of the migrations that were applied
+    pub migration_ids: Vec<String>,
+    /// Error message when applying failed
+    pub error: Option<String>,
+}
+
+impl SchemaChangeEvent {
+    /// Whether this reports a failure
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// Webhook URL that should receive an event, if any
+pub fn webhook_url<'a>(hooks: &'a HooksConfig, event: &SchemaChangeEvent) -> Option<&'a str> {
+    if event.is_failure() {
+        hooks.on_error.as_deref().or(hooks.on_schema_change.as_deref())
+    } else {
+        hooks.on_schema_change.as_deref()
+    }
+}
+
+/// Request body for an event in the configured `webhook_format` ("json" or "slack")
+pub fn payload(hooks: &HooksConfig, event: &SchemaChangeEvent) -> Value {
+    if hooks.webhook_format.as_deref() != Some("slack") {
+        return json!({ "event": "schema_change", "data": event });
+    }
+    
+    let mut text = match &event.error {
+        Some(error) => format!("Schema migration failed: {}", error),
+        None => format!("Schema migration applied ({} migration(s))", event.migration_ids.len()),
+    };
+    for change in &event.changes {
+        text.push_str(&format!("\n• {}", change));
+    }
+    
+    json!({ "text": text })
+}
+
+/// Post an event to its webhook, logging (not returning) any failure
+pub async fn notify(hooks: &HooksConfig, event: &SchemaChangeEvent) {
+    let url = match webhook_url(hooks, event) {
+        Some(url) => url,
+        None => return,
+    };
+    
+    if let Err(e) = send(url, &payload(hooks, event)).await {
+        tracing::warn!(url = url, error = %e, "Failed to send schema change webhook");
+    }
+}
+
+#[cfg(feature = "webhooks")]
+async fn send(url: &str, body: &Value) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    
+    client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "webhooks"))]
+async fn send(_url: &str, _body: &Value) -> Result<(), String> {
+    Err("webhooks require SchemaSync to be built with the 'webhooks' feature".to_string())
+}
@@ -7,7 +7,8 @@ use once_cell::sync::Lazy;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, Data, DeriveInput, Fields};
+use syn::{parse_macro_input, Data, DeriveInput, Field, Fields, Lit};
+use std::collections::HashMap;
 use std::sync::Mutex;
 
 /// Registry for models that are decorated with the #[schema_sync] attribute
@@ -24,10 +25,11 @@ pub fn schema_sync(attr: TokenStream, item: TokenStream) -> TokenStream {
     MODEL_REGISTRY.lock().unwrap().push(name.clone());
     
     // Parse attribute arguments
-    let attr_args = parse_attribute_args(proc_macro2::TokenStream::from(attr));
+    let attr = proc_macro2::TokenStream::from(attr);
+    let attr_args = parse_attribute_args(attr.clone());
     
     // Generate the modified struct with additional attributes
-    let expanded = expand_struct(input, attr_args);
+    let expanded = expand_struct(input, attr_args, attr);
     
     proc_macro::TokenStream::from(expanded)
 }
@@ -41,7 +43,10 @@ fn parse_attribute_args(attr: TokenStream2) -> Vec<(String, String)> {
 }
 
 /// Expand the struct definition with required traits and methods
-fn expand_struct(input: DeriveInput, attr_args: Vec<(String, String)>) -> TokenStream2 {
+///
+/// The attribute's own arguments are kept in a hidden `SCHEMA_SYNC_OPTIONS` constant, so the
+/// model scanner can still read options like `table = "users"` from macro-expanded source.
+fn expand_struct(input: DeriveInput, attr_args: Vec<(String, String)>, attr: TokenStream2) -> TokenStream2 {
     let name = &input.ident;
     let generics = &input.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
@@ -50,16 +55,36 @@ fn expand_struct(input: DeriveInput, attr_args: Vec<(String, String)>) -> TokenS
     let fields = match &input.data {
         Data::Struct(data) => match &data.fields {
             Fields::Named(fields) => &fields.named,
-            _ => panic!("SchemaSync only supports structs with named fields"),
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed,
+            _ => panic!("SchemaSync only supports structs with named fields and newtypes"),
         },
-        _ => panic!("SchemaSync only supports structs"),
+        // Enums are column types, read from the source by the model scanner
+        Data::Enum(_) => return quote!(#input),
+        _ => panic!("SchemaSync only supports structs and enums"),
+    };
+    
+    let field_definitions = match field_definitions(fields.iter()) {
+        Ok(tokens) => tokens,
+        Err(err) => return err.to_compile_error(),
     };
     
+    let options = (!attr.is_empty()).then(|| {
+        let attr = attr.to_string();
+        quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #[doc(hidden)]
+                pub const SCHEMA_SYNC_OPTIONS: &'static str = #attr;
+            }
+        }
+    });
+    
     // Generate implementation of SchemaSync trait
     let expanded = quote! {
         // Original struct
         #input
         
+        #options
+        
         #[automatically_derived]
         impl #impl_generics schema_sync::models::SchemaSyncModel for #name #ty_generics #where_clause {
             fn get_table_name() -> String {
@@ -69,8 +94,7 @@ fn expand_struct(input: DeriveInput, attr_args: Vec<(String, String)>) -> TokenS
             }
             
             fn get_field_definitions() -> Vec<schema_sync::schema::types::FieldDefinition> {
-                // In a real implementation, this would extract field types and attributes
-                vec![]
+                #field_definitions
             }
             
             fn register_with_schema_sync() {
@@ -87,6 +111,20 @@ fn expand_struct(input: DeriveInput, attr_args: Vec<(String, String)>) -> TokenS
 pub fn derive_schema_sync(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
+    
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed,
+            _ => panic!("SchemaSync only supports structs with named fields and newtypes"),
+        },
+        _ => panic!("SchemaSync only supports structs"),
+    };
+    let field_definitions = match field_definitions(fields.iter()) {
+        Ok(tokens) => tokens,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    
     let expanded = quote! {
         impl schema_sync::models::SchemaSyncModel for #name {
             // Implementation details
@@ -95,7 +133,7 @@ pub fn derive_schema_sync(input: TokenStream) -> TokenStream {
             }
             
             fn get_field_definitions() -> Vec<schema_sync::schema::types::FieldDefinition> {
-                vec![]
+                #field_definitions
             }
             
             fn register_with_schema_sync() {
@@ -105,4 +143,178 @@ pub fn derive_schema_sync(input: TokenStream) -> TokenStream {
     };
     
     TokenStream::from(expanded)
-}
\ No newline at end of file
+}
+
+/// Compile a directory of migration files into the binary
+///
+/// `embed_migrations!("./migrations")` reads the `.sql` files in the directory (relative
+/// to the crate's `Cargo.toml`) at build time and expands to a
+/// `schema_sync::db::migrations::EmbeddedMigrations`. The files are included with
+/// `include_str!`, so editing one rebuilds the crate.
+#[proc_macro]
+pub fn embed_migrations(input: TokenStream) -> TokenStream {
+    let directory = parse_macro_input!(input as syn::LitStr);
+    
+    match embedded_migration_files(&directory) {
+        Ok(files) => {
+            let entries = files.iter().map(|(filename, path)| quote!((#filename, include_str!(#path))));
+            quote! {
+                schema_sync::db::migrations::EmbeddedMigrations::new(&[#(#entries),*])
+            }
+            .into()
+        }
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// File names and absolute paths of the `.sql` files in a migrations directory, sorted by name
+fn embedded_migration_files(directory: &syn::LitStr) -> syn::Result<Vec<(String, String)>> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let path = std::path::Path::new(&manifest_dir).join(directory.value());
+    let entries = std::fs::read_dir(&path).map_err(|e| {
+        syn::Error::new(directory.span(), format!("cannot read migrations directory {}: {}", path.display(), e))
+    })?;
+    
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let file_path = entry.path();
+        if file_path.extension().and_then(|ext| ext.to_str()) != Some("sql") {
+            continue;
+        }
+        if let (Some(filename), Some(full_path)) = (
+            file_path.file_name().and_then(|name| name.to_str()),
+            file_path.to_str(),
+        ) {
+            files.push((filename.to_string(), full_path.to_string()));
+        }
+    }
+    
+    files.sort();
+    Ok(files)
+}
+
+/// Build the `FieldDefinition` list for a struct's fields and their `#[schema_sync_field]` attributes
+fn field_definitions<'a>(fields: impl Iterator<Item = &'a Field>) -> syn::Result<TokenStream2> {
+    let mut definitions = Vec::new();
+    
+    for field in fields {
+        // A newtype's single field is its `value` column
+        let name = field.ident.as_ref().map(|ident| ident.to_string()).unwrap_or_else(|| "value".to_string());
+        let rust_type = field.ty.to_token_stream().to_string();
+        let options = field_options(field)?;
+        
+        let flag = |key: &str| options.get(key).is_some_and(|value| value == "true");
+        let optional = |key: &str| match options.get(key) {
+            Some(value) => quote!(Some(#value.to_string())),
+            None => quote!(None),
+        };
+        
+        let nullable = flag("nullable") || rust_type.starts_with("Option <");
+        let primary_key = flag("primary_key");
+        let unique = flag("unique");
+        let db_type = optional("db_type");
+        let default = optional("default");
+        let comment = optional("comment");
+        
+        let foreign_key = match options.get("foreign_key").and_then(|value| value.rsplit_once('.')) {
+            Some((ref_table, ref_column)) => {
+                let action = |key: &str| match options.get(key) {
+                    Some(value) => quote!(schema_sync::schema::types::normalize_referential_action(#value)),
+                    None => quote!(None),
+                };
+                let on_delete = action("on_delete");
+                let on_update = action("on_update");
+                let deferrable = match options.get("deferrable") {
+                    Some(value) => quote!(schema_sync::schema::types::normalize_deferrable(#value)),
+                    None => quote!(None),
+                };
+                let match_type = match options.get("match_type") {
+                    Some(value) => {
+                        let value = value.to_lowercase();
+                        quote!(Some(#value.to_string()))
+                    }
+                    None => quote!(None),
+                };
+                
+                quote! {
+                    Some(schema_sync::schema::types::ForeignKeyDefinition {
+                        ref_table: #ref_table.to_string(),
+                        ref_column: #ref_column.to_string(),
+                        on_delete: #on_delete,
+                        on_update: #on_update,
+                        deferrable: #deferrable,
+                        match_type: #match_type,
+                    })
+                }
+            }
+            None => quote!(None),
+        };
+        
+        // Type parameters, application defaults, and validation rules are passed through the
+        // attribute map, as the file scanner does
+        let attribute_keys = [
+            "length", "precision", "scale", "default_fn", "default_variant", "min", "max", "min_length",
+            "max_length", "pattern", "json", "storage", "max_bytes", "case_insensitive",
+            "currency", "bits", "bit_storage", "vector_dims",
+        ];
+        let attribute_entries = attribute_keys.iter().filter_map(|key| {
+            options.get(*key).map(|value| quote!(attributes.insert(#key.to_string(), #value.to_string());))
+        });
+        
+        definitions.push(quote! {
+            schema_sync::schema::types::FieldDefinition {
+                name: #name.to_string(),
+                rust_type: #rust_type.to_string(),
+                db_type: #db_type,
+                nullable: #nullable,
+                primary_key: #primary_key,
+                unique: #unique,
+                default: #default,
+                foreign_key: #foreign_key,
+                comment: #comment,
+                attributes: {
+                    #[allow(unused_mut)]
+                    let mut attributes = std::collections::HashMap::new();
+                    #(#attribute_entries)*
+                    attributes
+                },
+            }
+        });
+    }
+    
+    Ok(quote! { vec![#(#definitions),*] })
+}
+
+/// Collect `key = value` pairs from a field's `#[schema_sync_field(...)]` attributes
+fn field_options(field: &Field) -> syn::Result<HashMap<String, String>> {
+    let mut options = HashMap::new();
+    
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("schema_sync_field")) {
+        attr.parse_nested_meta(|meta| {
+            let key = meta.path.to_token_stream().to_string();
+            // Grouped options such as `vector(dims = 1536)` become `vector_dims`
+            if meta.input.peek(syn::token::Paren) {
+                return meta.parse_nested_meta(|inner| {
+                    let name = format!("{}_{}", key, inner.path.to_token_stream());
+                    let value = inner.value()?.parse::<syn::LitInt>()?;
+                    options.insert(name, value.base10_digits().to_string());
+                    Ok(())
+                });
+            }
+            let input = meta.value()?;
+            // Bounds such as `min = -5` may be negative
+            let sign = if input.parse::<Option<syn::Token![-]>>()?.is_some() { "-" } else { "" };
+            let value = match input.parse::<Lit>()? {
+                Lit::Str(value) if sign.is_empty() => value.value(),
+                Lit::Bool(value) if sign.is_empty() => value.value.to_string(),
+                Lit::Int(value) => format!("{}{}", sign, value.base10_digits()),
+                Lit::Float(value) => format!("{}{}", sign, value.base10_digits()),
+                other => return Err(syn::Error::new_spanned(other, "unsupported schema_sync_field value")),
+            };
+            options.insert(key, value);
+            Ok(())
+        })?;
+    }
+    
+    Ok(options)
+}